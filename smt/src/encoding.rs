@@ -0,0 +1,153 @@
+// How cards, and the numbers the hand scoring derives from them, are
+// represented in the formula. The model in main.rs is written once against
+// the `Encoding` trait and built with either implementation.
+
+use z3::ast::{Ast, Bool, Int, BV};
+use z3::{Context, Model};
+
+// The operations the model is built from
+pub trait Encoding<'ctx> {
+    /// A card id, 0..=51
+    type Card: Ast<'ctx> + Clone;
+    /// A number derived from cards: a suit, a value, a hand rank, or a score
+    type Num: Ast<'ctx> + Clone;
+
+    /// An unknown card, named `name` in the formula
+    fn card_const(ctx: &'ctx Context, name: String) -> Self::Card;
+    /// The card with id `id`
+    fn card(ctx: &'ctx Context, id: u8) -> Self::Card;
+    /// Whether `card` is one of the 52 card ids
+    fn in_range(ctx: &'ctx Context, card: &Self::Card) -> Bool<'ctx>;
+    /// The card id `model` gives `card`
+    fn card_id(model: &Model<'ctx>, card: &Self::Card) -> u8;
+
+    /// card / 13: 0=clubs, 1=spades, 2=hearts, 3=diamonds
+    fn suit(ctx: &'ctx Context, card: &Self::Card) -> Self::Num;
+    /// The rank with aces high: (card % 13 + 12) % 13, so 0=2, ..., 11=K, 12=A
+    fn value(ctx: &'ctx Context, card: &Self::Card) -> Self::Num;
+
+    fn num(ctx: &'ctx Context, n: u64) -> Self::Num;
+    fn add(a: &Self::Num, b: &Self::Num) -> Self::Num;
+    fn mul(a: &Self::Num, b: &Self::Num) -> Self::Num;
+    fn le(a: &Self::Num, b: &Self::Num) -> Bool<'ctx>;
+    fn gt(a: &Self::Num, b: &Self::Num) -> Bool<'ctx>;
+}
+
+// Everything is an unbounded integer, and suits and values are extracted with
+// integer division and modulo
+pub struct IntEncoding;
+
+impl<'ctx> Encoding<'ctx> for IntEncoding {
+    type Card = Int<'ctx>;
+    type Num = Int<'ctx>;
+
+    fn card_const(ctx: &'ctx Context, name: String) -> Int<'ctx> {
+        Int::new_const(ctx, name)
+    }
+
+    fn card(ctx: &'ctx Context, id: u8) -> Int<'ctx> {
+        Int::from_u64(ctx, u64::from(id))
+    }
+
+    fn in_range(ctx: &'ctx Context, card: &Int<'ctx>) -> Bool<'ctx> {
+        Bool::and(ctx, &[&card.ge(&Int::from_i64(ctx, 0)), &card.le(&Int::from_i64(ctx, 51))])
+    }
+
+    fn card_id(model: &Model<'ctx>, card: &Int<'ctx>) -> u8 {
+        model.eval(card, true).unwrap().as_i64().unwrap() as u8
+    }
+
+    fn suit(ctx: &'ctx Context, card: &Int<'ctx>) -> Int<'ctx> {
+        card / Int::from_i64(ctx, 13)
+    }
+
+    fn value(ctx: &'ctx Context, card: &Int<'ctx>) -> Int<'ctx> {
+        let thirteen = Int::from_i64(ctx, 13);
+        (card.modulo(&thirteen) + Int::from_i64(ctx, 12)).modulo(&thirteen)
+    }
+
+    fn num(ctx: &'ctx Context, n: u64) -> Int<'ctx> {
+        Int::from_u64(ctx, n)
+    }
+
+    fn add(a: &Int<'ctx>, b: &Int<'ctx>) -> Int<'ctx> {
+        a + b
+    }
+
+    fn mul(a: &Int<'ctx>, b: &Int<'ctx>) -> Int<'ctx> {
+        a * b
+    }
+
+    fn le(a: &Int<'ctx>, b: &Int<'ctx>) -> Bool<'ctx> {
+        a.le(b)
+    }
+
+    fn gt(a: &Int<'ctx>, b: &Int<'ctx>) -> Bool<'ctx> {
+        a.gt(b)
+    }
+}
+
+// Cards are 6-bit bitvectors and everything derived from them is a
+// `NUM_BITS`-bit bitvector, so the solver can bit-blast instead of reasoning
+// about unbounded integer division
+pub struct BvEncoding;
+
+/// Enough bits for card ids up to 63
+const CARD_BITS: u32 = 6;
+/// Enough bits for the largest hand score, 8 * 15^5 + 14 * (15^4 + ... + 1) < 2^23
+const NUM_BITS: u32 = 24;
+
+impl<'ctx> Encoding<'ctx> for BvEncoding {
+    type Card = BV<'ctx>;
+    type Num = BV<'ctx>;
+
+    fn card_const(ctx: &'ctx Context, name: String) -> BV<'ctx> {
+        BV::new_const(ctx, name, CARD_BITS)
+    }
+
+    fn card(ctx: &'ctx Context, id: u8) -> BV<'ctx> {
+        BV::from_u64(ctx, u64::from(id), CARD_BITS)
+    }
+
+    fn in_range(ctx: &'ctx Context, card: &BV<'ctx>) -> Bool<'ctx> {
+        card.bvule(&BV::from_u64(ctx, 51, CARD_BITS))
+    }
+
+    fn card_id(model: &Model<'ctx>, card: &BV<'ctx>) -> u8 {
+        model.eval(card, true).unwrap().as_u64().unwrap() as u8
+    }
+
+    fn suit(ctx: &'ctx Context, card: &BV<'ctx>) -> BV<'ctx> {
+        let thirteen = BV::from_u64(ctx, 13, CARD_BITS);
+        card.bvudiv(&thirteen).zero_ext(NUM_BITS - CARD_BITS)
+    }
+
+    fn value(ctx: &'ctx Context, card: &BV<'ctx>) -> BV<'ctx> {
+        let thirteen = BV::from_u64(ctx, 13, CARD_BITS);
+        let twelve = BV::from_u64(ctx, 12, CARD_BITS);
+        card.bvurem(&thirteen)
+            .bvadd(&twelve)
+            .bvurem(&thirteen)
+            .zero_ext(NUM_BITS - CARD_BITS)
+    }
+
+    fn num(ctx: &'ctx Context, n: u64) -> BV<'ctx> {
+        BV::from_u64(ctx, n, NUM_BITS)
+    }
+
+    fn add(a: &BV<'ctx>, b: &BV<'ctx>) -> BV<'ctx> {
+        a.bvadd(b)
+    }
+
+    fn mul(a: &BV<'ctx>, b: &BV<'ctx>) -> BV<'ctx> {
+        a.bvmul(b)
+    }
+
+    fn le(a: &BV<'ctx>, b: &BV<'ctx>) -> Bool<'ctx> {
+        a.bvule(b)
+    }
+
+    fn gt(a: &BV<'ctx>, b: &BV<'ctx>) -> Bool<'ctx> {
+        a.bvugt(b)
+    }
+}
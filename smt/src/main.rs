@@ -1,7 +1,12 @@
 use clap::Parser;
-use z3::ast::{Ast, Bool, Int};
+use poker_wins::card_ids;
+use poker_wins::combinations::combinations;
+use z3::ast::{Ast, Bool};
 use z3::{Config, Context, SatResult, Solver};
 
+mod encoding;
+use encoding::{BvEncoding, Encoding, IntEncoding};
+
 #[derive(Parser, Debug)]
 #[command(name = "poker_smt")]
 #[command(about = "SMT-based solver for optimal poker deck ordering")]
@@ -17,53 +22,138 @@ struct Args {
     /// Dump the generated SMT-LIB2 formula to this file and exit without solving
     #[arg(short, long)]
     dump: Option<String>,
+
+    /// Pin deck positions to cards before solving, as comma-separated pos=cardid pairs
+    /// e.g. "0=12,1=25" puts K♣ and K♠ at the bottom of the deck, the last two cards dealt
+    #[arg(short, long)]
+    fixed: Option<FixedCards>,
+
+    /// How cards are represented in the formula
+    #[arg(short, long, value_enum, default_value_t = CardEncoding::Int)]
+    encoding: CardEncoding,
+}
+
+/// The `Encoding` the model is built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum CardEncoding {
+    /// Unbounded integers, with division and modulo for suits and values
+    Int,
+    /// 6-bit bitvector cards, with bitvector arithmetic for suits and values
+    Bv,
+}
+
+/// Deck positions pinned to card ids, as parsed from `--fixed`
+/// No position holds two cards and no card sits at two positions
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FixedCards(Vec<(usize, u8)>);
+
+/// Why a `--fixed` argument was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FixedError {
+    /// An entry that isn't `pos=cardid`
+    Malformed(String),
+    PositionOutOfRange(usize),
+    CardOutOfRange(usize),
+    /// One position pinned to two different cards
+    Conflict { pos: usize, cards: (u8, u8) },
+    /// One card pinned to two different positions
+    Duplicate { card: u8, positions: (usize, usize) },
+}
+
+impl std::fmt::Display for FixedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixedError::Malformed(entry) => write!(f, "expected pos=cardid, got '{entry}'"),
+            FixedError::PositionOutOfRange(pos) => {
+                write!(f, "position {pos} is out of range (0-51)")
+            }
+            FixedError::CardOutOfRange(card) => write!(f, "card id {card} is out of range (0-51)"),
+            FixedError::Conflict { pos, cards } => {
+                write!(f, "position {pos} is fixed to both card {} and card {}", cards.0, cards.1)
+            }
+            FixedError::Duplicate { card, positions } => write!(
+                f,
+                "card {card} is fixed at both position {} and position {}",
+                positions.0, positions.1
+            ),
+        }
+    }
 }
 
-// Card encoding: card_id = suit * 13 + value
-// suit: 0=clubs, 1=diamonds, 2=hearts, 3=spades
-// value: 0=2, 1=3, ..., 9=J, 10=Q, 11=K, 12=A
+impl std::error::Error for FixedError {}
 
+impl std::str::FromStr for FixedCards {
+    type Err = FixedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fixed: Vec<(usize, u8)> = Vec::new();
+        for entry in s.trim().split(',') {
+            let malformed = || FixedError::Malformed(entry.to_string());
+            let (pos, card) = entry.split_once('=').ok_or_else(malformed)?;
+            let pos: usize = pos.trim().parse().map_err(|_| malformed())?;
+            let card: usize = card.trim().parse().map_err(|_| malformed())?;
+            if pos >= 52 {
+                return Err(FixedError::PositionOutOfRange(pos));
+            }
+            let card = u8::try_from(card)
+                .ok()
+                .filter(|&card| card < 52)
+                .ok_or(FixedError::CardOutOfRange(card))?;
+
+            // Repeating the same pair is harmless, so only differing pairs are rejected
+            if let Some(&(_, other)) = fixed.iter().find(|&&(p, _)| p == pos) {
+                if other != card {
+                    return Err(FixedError::Conflict { pos, cards: (other, card) });
+                }
+                continue;
+            }
+            if let Some(&(other, _)) = fixed.iter().find(|&&(_, c)| c == card) {
+                return Err(FixedError::Duplicate { card, positions: (other, pos) });
+            }
+            fixed.push((pos, card));
+        }
+        Ok(FixedCards(fixed))
+    }
+}
+
+// Card encoding, shared with the main crate (../src/cards.rs) so card ids are
+// portable between the two tools: card_id = suit * 13 + value
+// suit: 0=clubs, 1=spades, 2=hearts, 3=diamonds
+// value: 0=A, 1=2, ..., 9=10, 10=J, 11=Q, 12=K
+
+// Render a card the way the main crate's `Card` Display does, e.g. "K♠"
 fn card_to_string(card_id: i64) -> String {
     let suit = card_id / 13;
     let value = card_id % 13;
 
     let suit_char = match suit {
-        0 => 'c',
-        1 => 'd',
-        2 => 'h',
-        3 => 's',
+        0 => '♣',
+        1 => '♠',
+        2 => '♥',
+        3 => '♦',
         _ => '?',
     };
 
     let value_str = match value {
-        0..=8 => format!("{}", value + 2),
-        9 => "J".to_string(),
-        10 => "Q".to_string(),
-        11 => "K".to_string(),
-        12 => "A".to_string(),
+        0 => "A".to_string(),
+        1..=8 => format!("{}", value + 1),
+        9 => "T".to_string(),
+        10 => "J".to_string(),
+        11 => "Q".to_string(),
+        12 => "K".to_string(),
         _ => "?".to_string(),
     };
 
     format!("{}{}", value_str, suit_char)
 }
 
-// Helper to create suit extraction: card / 13
-fn suit<'ctx>(ctx: &'ctx Context, card: &Int<'ctx>) -> Int<'ctx> {
-    card / Int::from_i64(ctx, 13)
-}
-
-// Helper to create value extraction: card % 13
-fn value<'ctx>(ctx: &'ctx Context, card: &Int<'ctx>) -> Int<'ctx> {
-    card.modulo(&Int::from_i64(ctx, 13))
-}
-
 // Check if all 5 cards have the same suit
-fn is_flush<'ctx>(ctx: &'ctx Context, cards: &[Int<'ctx>; 5]) -> Bool<'ctx> {
-    let s0 = suit(ctx, &cards[0]);
-    let s1 = suit(ctx, &cards[1]);
-    let s2 = suit(ctx, &cards[2]);
-    let s3 = suit(ctx, &cards[3]);
-    let s4 = suit(ctx, &cards[4]);
+fn is_flush<'ctx, E: Encoding<'ctx>>(ctx: &'ctx Context, cards: &[E::Card; 5]) -> Bool<'ctx> {
+    let s0 = E::suit(ctx, &cards[0]);
+    let s1 = E::suit(ctx, &cards[1]);
+    let s2 = E::suit(ctx, &cards[2]);
+    let s3 = E::suit(ctx, &cards[3]);
+    let s4 = E::suit(ctx, &cards[4]);
 
     Bool::and(ctx, &[
         &s0._eq(&s1),
@@ -77,12 +167,12 @@ fn is_flush<'ctx>(ctx: &'ctx Context, cards: &[Int<'ctx>; 5]) -> Bool<'ctx> {
 // for n=5 elements). Everything downstream (straight detection, pair
 // pattern, high card) reads off this sorted order instead of re-deriving it
 // with per-value existential searches.
-fn sort5<'ctx>(values: [Int<'ctx>; 5]) -> [Int<'ctx>; 5] {
+fn sort5<'ctx, E: Encoding<'ctx>>(values: [E::Num; 5]) -> [E::Num; 5] {
     let mut v = values;
     const NETWORK: [(usize, usize); 9] =
         [(0, 1), (3, 4), (2, 4), (2, 3), (0, 3), (0, 2), (1, 4), (1, 3), (1, 2)];
     for &(i, j) in &NETWORK {
-        let le = v[i].le(&v[j]);
+        let le = E::le(&v[i], &v[j]);
         let lo = le.ite(&v[i], &v[j]);
         let hi = le.ite(&v[j], &v[i]);
         v[i] = lo;
@@ -91,11 +181,11 @@ fn sort5<'ctx>(values: [Int<'ctx>; 5]) -> [Int<'ctx>; 5] {
     v
 }
 
-// Determine hand rank + high-card tiebreaker for 5 cards, matching the
-// simplified (rank, high_card) scoring the rest of this project uses (see
+// Determine hand rank + kicker tiebreakers for 5 cards, ordered exactly as the
+// (rank, kickers) scoring the rest of this project uses (see
 // ../src/hands.rs::score_five_cards) so a solution found here means the same
 // thing as a win anywhere else in the codebase. Returned as a single
-// comparable integer: rank * 100 + high_card.
+// comparable integer: rank followed by the 5 kickers as base-15 digits.
 //
 // Once the 5 values are sorted, equal values are necessarily contiguous, so
 // the entire pair/two-pair/trips/full-house/quad pattern is determined by
@@ -107,10 +197,10 @@ fn sort5<'ctx>(values: [Int<'ctx>; 5]) -> [Int<'ctx>; 5] {
 // straight/flush ranks never collide and can be layered without the
 // "and not X" guards the old code needed to avoid clobbering straight
 // flushes.
-fn hand_score<'ctx>(ctx: &'ctx Context, cards: &[Int<'ctx>; 5]) -> Int<'ctx> {
-    let raw_values: [Int; 5] = std::array::from_fn(|i| value(ctx, &cards[i]));
-    let v = sort5(raw_values);
-    let is_flush_val = is_flush(ctx, cards);
+fn hand_score<'ctx, E: Encoding<'ctx>>(ctx: &'ctx Context, cards: &[E::Card; 5]) -> E::Num {
+    let raw_values: [E::Num; 5] = std::array::from_fn(|i| E::value(ctx, &cards[i]));
+    let v = sort5::<E>(raw_values);
+    let is_flush_val = is_flush::<E>(ctx, cards);
 
     let e01 = v[0]._eq(&v[1]);
     let e12 = v[1]._eq(&v[2]);
@@ -143,55 +233,76 @@ fn hand_score<'ctx>(ctx: &'ctx Context, cards: &[Int<'ctx>; 5]) -> Int<'ctx> {
     ]);
 
     let is_wheel = Bool::and(ctx, &[
-        &v[0]._eq(&Int::from_i64(ctx, 0)),
-        &v[1]._eq(&Int::from_i64(ctx, 1)),
-        &v[2]._eq(&Int::from_i64(ctx, 2)),
-        &v[3]._eq(&Int::from_i64(ctx, 3)),
-        &v[4]._eq(&Int::from_i64(ctx, 12)),
+        &v[0]._eq(&E::num(ctx, 0)),
+        &v[1]._eq(&E::num(ctx, 1)),
+        &v[2]._eq(&E::num(ctx, 2)),
+        &v[3]._eq(&E::num(ctx, 3)),
+        &v[4]._eq(&E::num(ctx, 12)),
     ]);
+    let one = E::num(ctx, 1);
     let is_straight_val = Bool::or(ctx, &[
         &is_wheel,
         &Bool::and(ctx, &[
-            &v[1]._eq(&(v[0].clone() + Int::from_i64(ctx, 1))),
-            &v[2]._eq(&(v[1].clone() + Int::from_i64(ctx, 1))),
-            &v[3]._eq(&(v[2].clone() + Int::from_i64(ctx, 1))),
-            &v[4]._eq(&(v[3].clone() + Int::from_i64(ctx, 1))),
+            &v[1]._eq(&E::add(&v[0], &one)),
+            &v[2]._eq(&E::add(&v[1], &one)),
+            &v[3]._eq(&E::add(&v[2], &one)),
+            &v[4]._eq(&E::add(&v[3], &one)),
         ]),
     ]);
 
-    let mut rank = Int::from_i64(ctx, 0); // default: high card
-    rank = one_pair.ite(&Int::from_i64(ctx, 1), &rank);
-    rank = two_pair.ite(&Int::from_i64(ctx, 2), &rank);
-    rank = trips.ite(&Int::from_i64(ctx, 3), &rank);
-    rank = full_house.ite(&Int::from_i64(ctx, 6), &rank);
-    rank = quad.ite(&Int::from_i64(ctx, 7), &rank);
-    rank = is_straight_val.ite(&Int::from_i64(ctx, 4), &rank);
-    rank = is_flush_val.ite(&Int::from_i64(ctx, 5), &rank);
-    rank = Bool::and(ctx, &[&is_straight_val, &is_flush_val]).ite(&Int::from_i64(ctx, 8), &rank);
-
-    // High card tiebreaker, matching hands.rs::score_five_cards exactly: an
-    // ace counts as 14 unless it's completing the wheel, where the "5"
-    // (v[3], since the ace sorts to the top as value 12 in this encoding)
-    // is the effective high card.
-    let has_ace = v[4]._eq(&Int::from_i64(ctx, 12));
-    let hi = is_wheel.ite(
-        &Int::from_i64(ctx, 5),
-        &has_ace.ite(&Int::from_i64(ctx, 14), &(v[4].clone() + Int::from_i64(ctx, 2))),
-    );
-
-    rank * Int::from_i64(ctx, 100) + hi
+    let mut rank = E::num(ctx, 0); // default: high card
+    rank = one_pair.ite(&E::num(ctx, 1), &rank);
+    rank = two_pair.ite(&E::num(ctx, 2), &rank);
+    rank = trips.ite(&E::num(ctx, 3), &rank);
+    rank = full_house.ite(&E::num(ctx, 6), &rank);
+    rank = quad.ite(&E::num(ctx, 7), &rank);
+    rank = is_straight_val.ite(&E::num(ctx, 4), &rank);
+    rank = is_flush_val.ite(&E::num(ctx, 5), &rank);
+    rank = Bool::and(ctx, &[&is_straight_val, &is_flush_val]).ite(&E::num(ctx, 8), &rank);
+
+    // Kicker tiebreakers, matching hands.rs::score_five_cards exactly: the
+    // values grouped by count, then by value, both descending. Equal values
+    // are contiguous once sorted, so each pair pattern reads the sorted values
+    // in a fixed order; every pattern not listed (no pair, a pair of the top
+    // two, two pair above a low kicker, ...) reads them from the top down.
+    let orders: [(Bool, [usize; 5]); 7] = [
+        // Pair of the bottom two, the middle two, or the upper middle two
+        (Bool::and(ctx, &[&e01, &e12.not(), &e23.not(), &e34.not()]), [1, 0, 4, 3, 2]),
+        (Bool::and(ctx, &[&e12, &e01.not(), &e23.not(), &e34.not()]), [2, 1, 4, 3, 0]),
+        (Bool::and(ctx, &[&e23, &e01.not(), &e12.not(), &e34.not()]), [3, 2, 4, 1, 0]),
+        // Two pair over a top kicker, or quads over a top kicker
+        (Bool::and(ctx, &[&e01, &e23, &e34.not()]), [3, 2, 1, 0, 4]),
+        // Two pair around a middle kicker
+        (Bool::and(ctx, &[&e01, &e34, &e12.not(), &e23.not()]), [4, 3, 1, 0, 2]),
+        // Bottom trips, with or without a pair above them
+        (Bool::and(ctx, &[&e01, &e12, &e23.not()]), [2, 1, 0, 4, 3]),
+        // Middle trips
+        (Bool::and(ctx, &[&e12, &e23, &e01.not(), &e34.not()]), [3, 2, 1, 4, 0]),
+    ];
+    // Face values 2..=14, aces high
+    let face = |i: usize| E::add(&v[i], &E::num(ctx, 2));
+    let mut kickers: [E::Num; 5] = std::array::from_fn(|i| face(4 - i));
+    for (pattern, order) in &orders {
+        kickers = std::array::from_fn(|i| pattern.ite(&face(order[i]), &kickers[i]));
+    }
+    // Straights compare on their top card, so the wheel's ace counts as a 1
+    kickers = std::array::from_fn(|i| is_wheel.ite(&E::num(ctx, 5 - i as u64), &kickers[i]));
+
+    let fifteen = E::num(ctx, 15);
+    kickers
+        .iter()
+        .fold(rank, |score, kicker| E::add(&E::mul(&score, &fifteen), kicker))
 }
 
 // Get the best hand from 7 cards (2 hole + 5 community): the max hand_score
 // over all C(7,5) = 21 combinations. Because hand_score is already a single
 // comparable integer, the running-best update is one comparison instead of
 // a 6-field (rank + 5 tiebreakers) lexicographic compare.
-fn best_hand_from_seven<'ctx>(
+fn best_hand_from_seven<'ctx, E: Encoding<'ctx>>(
     ctx: &'ctx Context,
-    hole_cards: &[Int<'ctx>; 2],
-    community: &[Int<'ctx>; 5],
-) -> Int<'ctx> {
-    // All C(7,5) = 21 combinations
+    hole_cards: &[E::Card; 2],
+    community: &[E::Card; 5],
+) -> E::Num {
     let all_cards = [
         hole_cards[0].clone(),
         hole_cards[1].clone(),
@@ -202,27 +313,67 @@ fn best_hand_from_seven<'ctx>(
         community[4].clone(),
     ];
 
-    let combinations: [[usize; 5]; 21] = [
-        [0,1,2,3,4], [0,1,2,3,5], [0,1,2,3,6], [0,1,2,4,5], [0,1,2,4,6],
-        [0,1,2,5,6], [0,1,3,4,5], [0,1,3,4,6], [0,1,3,5,6], [0,1,4,5,6],
-        [0,2,3,4,5], [0,2,3,4,6], [0,2,3,5,6], [0,2,4,5,6], [0,3,4,5,6],
-        [1,2,3,4,5], [1,2,3,4,6], [1,2,3,5,6], [1,2,4,5,6], [1,3,4,5,6],
-        [2,3,4,5,6],
-    ];
+    // All C(7,5) = 21 combinations
+    combinations::<5>(all_cards.len())
+        .map(|combo| hand_score::<E>(ctx, &combo.map(|i| all_cards[i].clone())))
+        .reduce(|best, score| E::gt(&score, &best).ite(&score, &best))
+        .unwrap()
+}
 
-    let mut best = Int::from_i64(ctx, -1);
-    for combo in combinations {
-        let hand = [
-            all_cards[combo[0]].clone(),
-            all_cards[combo[1]].clone(),
-            all_cards[combo[2]].clone(),
-            all_cards[combo[3]].clone(),
-            all_cards[combo[4]].clone(),
-        ];
-        let score = hand_score(ctx, &hand);
-        best = score.gt(&best).ite(&score, &best);
+// Create 52 card variables for the deck, constrained to be a permutation
+// of the card ids 0..=51
+fn deck_variables<'ctx, E: Encoding<'ctx>>(
+    ctx: &'ctx Context,
+    solver: &Solver<'ctx>,
+) -> Vec<E::Card> {
+    let deck: Vec<E::Card> = (0..52)
+        .map(|i| E::card_const(ctx, format!("card_{}", i)))
+        .collect();
+
+    // Constraint: each card is in range [0, 51]
+    for card in &deck {
+        solver.assert(&E::in_range(ctx, card));
+    }
+
+    // Constraint: all cards are distinct (valid permutation)
+    solver.assert(&Ast::distinct(ctx, &deck.iter().collect::<Vec<_>>()));
+    deck
+}
+
+// The hole cards of each player and the board, as the main crate's standard
+// deal gives them out after cutting `deck` at `cut` (see
+// ../src/game.rs::deal_a_round), so a deck solved here wins there too. The cut
+// rotates the deck left, cards come off its end, the hole cards go round the
+// table twice, and one card is burned before each of the flop, turn, and river.
+fn deal<C: Clone>(deck: &[C], cut: usize, num_players: usize) -> (Vec<[C; 2]>, [C; 5]) {
+    let len = deck.len();
+    let dealt = |k: usize| deck[(cut + len - 1 - k) % len].clone();
+    let hole_cards = (0..num_players)
+        .map(|p| [dealt(p), dealt(num_players + p)])
+        .collect();
+    // Burn, flop, burn, turn, burn, river
+    let b = 2 * num_players;
+    let community = [dealt(b + 1), dealt(b + 2), dealt(b + 3), dealt(b + 5), dealt(b + 7)];
+    (hole_cards, community)
+}
+
+// Constrain the dealer (player 0) to beat every other player outright when
+// `deck` is cut at `cut`. A tie isn't a win, as in the main crate.
+fn assert_dealer_wins<'ctx, E: Encoding<'ctx>>(
+    ctx: &'ctx Context,
+    solver: &Solver<'ctx>,
+    deck: &[E::Card],
+    cut: usize,
+    num_players: usize,
+) {
+    let (hole_cards, community) = deal(deck, cut, num_players);
+    let best_hands: Vec<E::Num> = hole_cards
+        .iter()
+        .map(|hole| best_hand_from_seven::<E>(ctx, hole, &community))
+        .collect();
+    for other in &best_hands[1..] {
+        solver.assert(&E::gt(&best_hands[0], other));
     }
-    best
 }
 
 fn main() {
@@ -235,84 +386,47 @@ fn main() {
 
     let cfg = Config::new();
     let ctx = Context::new(&cfg);
-    let solver = Solver::new(&ctx);
+    match args.encoding {
+        CardEncoding::Int => solve::<IntEncoding>(&ctx, &args),
+        CardEncoding::Bv => solve::<BvEncoding>(&ctx, &args),
+    }
+}
+
+// Build the model with encoding `E`, then dump or solve it as `args` asks
+fn solve<'ctx, E: Encoding<'ctx>>(ctx: &'ctx Context, args: &Args) {
+    let solver = Solver::new(ctx);
 
     // Set timeout
     if args.timeout > 0 {
-        let mut params = z3::Params::new(&ctx);
+        let mut params = z3::Params::new(ctx);
         params.set_u32("timeout", (args.timeout * 1000) as u32);
         solver.set_params(&params);
     }
 
     println!("Creating deck variables (52 cards)...");
+    let deck = deck_variables::<E>(ctx, &solver);
 
-    // Create 52 integer variables for the deck
-    let deck: Vec<Int> = (0..52)
-        .map(|i| Int::new_const(&ctx, format!("card_{}", i)))
-        .collect();
-
-    // Constraint: each card is in range [0, 51]
-    for card in &deck {
-        solver.assert(&card.ge(&Int::from_i64(&ctx, 0)));
-        solver.assert(&card.le(&Int::from_i64(&ctx, 51)));
-    }
-
-    println!("Adding permutation constraint...");
-
-    // Constraint: all cards are distinct (valid permutation)
-    solver.assert(&Int::distinct(&ctx, &deck.iter().collect::<Vec<_>>()));
-
-    println!("Generating constraints for all 52 cut positions...");
-    println!("(This will take a while - generating thousands of constraints)");
-    println!();
-
-    // For each cut position
-    for cut in 0..52 {
-        if cut % 10 == 0 {
-            println!("  Processing cut position {}/52...", cut);
-        }
-
-        // Create the cut deck: [cut, cut+1, ..., 51, 0, 1, ..., cut-1]
-        let cut_deck: Vec<Int> = (0..52)
-            .map(|i| deck[(cut + i) % 52].clone())
-            .collect();
-
-        // Deal cards to players
-        let n = args.num_players;
-        let mut player_hands: Vec<[Int; 2]> = Vec::new();
-
-        for p in 0..n {
-            player_hands.push([
-                cut_deck[2 * p].clone(),
-                cut_deck[2 * p + 1].clone(),
-            ]);
-        }
-
-        // Community cards
-        let community = [
-            cut_deck[2 * n].clone(),
-            cut_deck[2 * n + 1].clone(),
-            cut_deck[2 * n + 2].clone(),
-            cut_deck[2 * n + 3].clone(),
-            cut_deck[2 * n + 4].clone(),
-        ];
-
-        // Get best hand score for each player
-        let player_best_hands: Vec<Int> = (0..n)
-            .map(|p| best_hand_from_seven(&ctx, &player_hands[p], &community))
-            .collect();
-
-        // Constraint: player 0 must win (beat all other players)
-        for p in 1..n {
-            solver.assert(&player_best_hands[0].gt(&player_best_hands[p]));
+    if let Some(FixedCards(fixed)) = &args.fixed {
+        println!("Fixing {} deck positions...", fixed.len());
+        for &(pos, card) in fixed {
+            solver.assert(&deck[pos]._eq(&E::card(ctx, card)));
         }
     }
 
-    println!();
-    println!("All constraints generated!");
-
     // If requested, dump the SMT-LIB2 formula to disk and exit without solving.
     if let Some(path) = &args.dump {
+        println!("Generating constraints for all 52 cut positions...");
+        println!("(This will take a while - generating thousands of constraints)");
+        println!();
+        for cut in 0..52 {
+            if cut % 10 == 0 {
+                println!("  Processing cut position {}/52...", cut);
+            }
+            assert_dealer_wins::<E>(ctx, &solver, &deck, cut, args.num_players);
+        }
+        println!();
+        println!("All constraints generated!");
+
         let formula = solver.to_string();
         std::fs::write(path, &formula)
             .unwrap_or_else(|e| panic!("Failed to write formula to {}: {}", path, e));
@@ -325,35 +439,277 @@ fn main() {
         return;
     }
 
-    println!("Starting solver (this may take a very long time)...");
+    println!("Solving one cut position at a time (this may take a very long time)...");
+    println!();
+
+    let started = std::time::Instant::now();
+    let progress = solve_incrementally::<E>(ctx, &solver, &deck, args.num_players, |cut, result| {
+        println!("  Cut position {:2}: {:?} ({:.1?})", cut, result, started.elapsed());
+    });
     println!();
+    println!("Solving took {:.1?}", started.elapsed());
 
-    match solver.check() {
+    match progress.stopped {
         SatResult::Sat => {
             println!("SAT! Found a solution!");
             println!();
-
-            let model = solver.get_model().unwrap();
-
-            println!("Winning deck ordering:");
-            for i in 0..52 {
-                let card_val = model.eval(&deck[i], true).unwrap().as_i64().unwrap();
-                println!("  Position {}: {}", i, card_to_string(card_val));
-            }
-
-            println!();
-            println!("Deck as comma-separated card IDs:");
-            let card_ids: Vec<String> = (0..52)
-                .map(|i| model.eval(&deck[i], true).unwrap().as_i64().unwrap().to_string())
-                .collect();
-            println!("{}", card_ids.join(","));
         }
         SatResult::Unsat => {
-            println!("UNSAT: No deck ordering exists where player 0 wins all {} positions.", 52);
+            println!(
+                "UNSAT: No deck ordering exists where player 0 wins cut positions 0 through {}.",
+                progress.satisfiable
+            );
         }
         SatResult::Unknown => {
             println!("UNKNOWN: Solver could not determine satisfiability (likely timeout).");
             println!("Try increasing the timeout or simplifying the problem.");
         }
     }
+    let Some(card_ids) = progress.deck else {
+        return;
+    };
+    if progress.satisfiable < 52 {
+        println!(
+            "Largest satisfiable prefix: player 0 wins cut positions 0 through {} with:",
+            progress.satisfiable - 1
+        );
+        println!();
+    }
+
+    println!("Winning deck ordering:");
+    for (i, &id) in card_ids.iter().enumerate() {
+        println!("  Position {}: {}", i, card_to_string(i64::from(id)));
+    }
+
+    println!();
+    println!("Deck as comma-separated card IDs:");
+    println!("{}", card_ids::to_id_string(&card_ids));
+}
+
+/// How far `solve_incrementally` got
+struct IncrementalResult {
+    /// How many cut positions, counting from 0, the dealer can win at once
+    satisfiable: usize,
+    /// A deck winning those cut positions, if there are any
+    deck: Option<Vec<u8>>,
+    /// `Sat` if every cut position was satisfied, otherwise why the solving stopped
+    stopped: SatResult,
+}
+
+// Add the cut positions' constraints one at a time, checking after each, so an
+// unsatisfiable prefix is found without building the whole formula. Each cut
+// is added in its own scope, which is popped again if it can't be satisfied,
+// leaving `solver` holding the largest satisfiable prefix. `on_check` hears
+// about every check. The solver's timeout applies to each check separately.
+fn solve_incrementally<'ctx, E: Encoding<'ctx>>(
+    ctx: &'ctx Context,
+    solver: &Solver<'ctx>,
+    deck: &[E::Card],
+    num_players: usize,
+    mut on_check: impl FnMut(usize, SatResult),
+) -> IncrementalResult {
+    let mut progress = IncrementalResult { satisfiable: 0, deck: None, stopped: SatResult::Sat };
+    for cut in 0..deck.len() {
+        solver.push();
+        assert_dealer_wins::<E>(ctx, solver, deck, cut, num_players);
+        let result = solver.check();
+        on_check(cut, result);
+        if result != SatResult::Sat {
+            solver.pop(1);
+            progress.stopped = result;
+            break;
+        }
+        let model = solver.get_model().unwrap();
+        progress.satisfiable = cut + 1;
+        progress.deck = Some(deck.iter().map(|card| E::card_id(&model, card)).collect());
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poker_wins::{cards, known_decks, ranked_hands};
+
+    /// The score `hand_score` gives a hand of this rank and these kickers
+    fn packed(rank: u64, kickers: [u64; 5]) -> u64 {
+        kickers.iter().fold(rank, |score, kicker| score * 15 + kicker)
+    }
+
+    /// Assert that the five cards with these ids can only score `expected` in encoding `E`
+    fn assert_scores_in<E: for<'ctx> Encoding<'ctx>>(ids: [u8; 5], expected: u64) {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let cards = ids.map(|id| E::card(&ctx, id));
+        let solver = Solver::new(&ctx);
+        solver.assert(&hand_score::<E>(&ctx, &cards)._eq(&E::num(&ctx, expected)).not());
+        assert_eq!(solver.check(), SatResult::Unsat, "{ids:?} should score {expected}");
+    }
+
+    /// `assert_scores_in` both encodings
+    fn assert_scores(ids: [u8; 5], expected: u64) {
+        assert_scores_in::<IntEncoding>(ids, expected);
+        assert_scores_in::<BvEncoding>(ids, expected);
+    }
+
+    #[test]
+    fn wheel_is_a_five_high_straight() {
+        // A♣ 2♠ 3♥ 4♦ 5♣, in either order
+        let wheel = packed(4, [5, 4, 3, 2, 1]);
+        assert_scores([0, 14, 28, 42, 4], wheel);
+        assert_scores([4, 42, 28, 14, 0], wheel);
+        // 2♠ 3♥ 4♦ 5♣ 6♠ beats it, A♣ K♠ Q♥ J♦ T♣ beats both
+        assert_scores([14, 28, 42, 4, 18], packed(4, [6, 5, 4, 3, 2]));
+        assert_scores([0, 25, 37, 49, 9], packed(4, [14, 13, 12, 11, 10]));
+        // A♣ 2♣ 3♣ 4♣ 5♣ is a 5-high straight flush
+        assert_scores([0, 1, 2, 3, 4], packed(8, [5, 4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn kickers_are_grouped_by_count() {
+        // 2♣ 2♠ A♥ K♦ Q♣: the pair comes before the higher cards
+        assert_scores([1, 14, 26, 51, 11], packed(1, [2, 2, 14, 13, 12]));
+        // K♣ K♠ 2♥ 2♦ A♣: two pair, high pair first, then the kicker
+        assert_scores([12, 25, 27, 40, 0], packed(2, [13, 13, 2, 2, 14]));
+        // 2♣ 2♠ 2♥ A♣ A♦: full house, trips before the pair
+        assert_scores([1, 14, 27, 0, 39], packed(6, [2, 2, 2, 14, 14]));
+        // 2♣ 2♠ 2♥ 2♦ A♣: quads before the kicker
+        assert_scores([1, 14, 27, 40, 0], packed(7, [2, 2, 2, 2, 14]));
+        // The largest score fits the bitvector encoding: a royal flush
+        assert_scores([0, 12, 11, 10, 9], packed(8, [14, 13, 12, 11, 10]));
+    }
+
+    /// Assert that encoding `E` ranks `RANKED_HANDS` in order
+    fn ranks_hands_like_the_main_crate_in<E: for<'ctx> Encoding<'ctx>>() {
+        let id = |card: &str| card.parse::<cards::Card>().unwrap().0;
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let score = |hand: &[&str; 5]| hand_score::<E>(&ctx, &hand.map(|c| E::card(&ctx, id(c))));
+        for pair in ranked_hands::RANKED_HANDS.windows(2) {
+            let (lower, higher) = (&pair[0], &pair[1]);
+            let solver = Solver::new(&ctx);
+            solver.assert(&E::gt(&score(higher), &score(lower)).not());
+            assert_eq!(solver.check(), SatResult::Unsat, "{lower:?} should lose to {higher:?}");
+        }
+    }
+
+    #[test]
+    fn ranks_hands_like_the_main_crate() {
+        ranks_hands_like_the_main_crate_in::<IntEncoding>();
+        ranks_hands_like_the_main_crate_in::<BvEncoding>();
+    }
+
+    #[test]
+    fn fixed_cards_parse_pairs_and_reject_contradictions() {
+        let parse = |s: &str| s.parse::<FixedCards>();
+        assert_eq!(parse("0=12, 1=25"), Ok(FixedCards(vec![(0, 12), (1, 25)])));
+        assert_eq!(parse("51=0,51=0"), Ok(FixedCards(vec![(51, 0)])));
+
+        assert_eq!(parse("0=12,0=25"), Err(FixedError::Conflict { pos: 0, cards: (12, 25) }));
+        assert_eq!(
+            parse("0=12,7=12"),
+            Err(FixedError::Duplicate { card: 12, positions: (0, 7) })
+        );
+        assert_eq!(parse("52=0"), Err(FixedError::PositionOutOfRange(52)));
+        assert_eq!(parse("0=300"), Err(FixedError::CardOutOfRange(300)));
+        assert_eq!(parse("0:12"), Err(FixedError::Malformed("0:12".to_string())));
+        assert_eq!(parse("0=12,"), Err(FixedError::Malformed("".to_string())));
+    }
+
+    /// Deck variables with every position fixed to `ids`, except those in `free`
+    fn partly_fixed_deck<'ctx, E: Encoding<'ctx>>(
+        ctx: &'ctx Context,
+        solver: &Solver<'ctx>,
+        ids: [u8; 52],
+        free: &[usize],
+    ) -> Vec<E::Card> {
+        let deck = deck_variables::<E>(ctx, solver);
+        for (pos, &id) in ids.iter().enumerate().filter(|(pos, _)| !free.contains(pos)) {
+            solver.assert(&deck[pos]._eq(&E::card(ctx, id)));
+        }
+        deck
+    }
+
+    /// Constrain the dealer to win every cut of a deck whose positions are fixed
+    /// to `ids`, except those in `free`, and solve
+    fn solve_three_player_deck<E: for<'ctx> Encoding<'ctx>>(
+        ids: [u8; 52],
+        free: &[usize],
+    ) -> Option<Vec<u8>> {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let solver = Solver::new(&ctx);
+        let deck = partly_fixed_deck::<E>(&ctx, &solver, ids, free);
+        for cut in 0..52 {
+            assert_dealer_wins::<E>(&ctx, &solver, &deck, cut, 3);
+        }
+        match solver.check() {
+            SatResult::Sat => {
+                let model = solver.get_model().unwrap();
+                Some(deck.iter().map(|card| E::card_id(&model, card)).collect())
+            }
+            SatResult::Unsat => None,
+            SatResult::Unknown => panic!("the solver gave up"),
+        }
+    }
+
+    /// Assert that encoding `E` agrees with the main crate about `known_decks`
+    fn agrees_with_the_main_crate_on_known_decks_in<E: for<'ctx> Encoding<'ctx>>() {
+        let perfect = known_decks::three_player_perfect_deck();
+        assert_eq!(solve_three_player_deck::<E>(perfect, &[]), Some(perfect.to_vec()));
+        let swapped = known_decks::three_player_swapped_deck();
+        assert_eq!(solve_three_player_deck::<E>(swapped, &[]), None);
+
+        // With the bottom two cards free, only their winning order is a solution, and the
+        // main crate's `verify` reads the printed ids back as the perfect deck
+        let solved = solve_three_player_deck::<E>(swapped, &[0, 1]).unwrap();
+        assert_eq!(solved, perfect.to_vec());
+        let printed = card_ids::to_id_string(&solved);
+        let parsed: Vec<usize> = card_ids::parse_id_string(&printed).map(Result::unwrap).collect();
+        assert!(parsed.iter().zip(perfect).all(|(&id, expected)| id == usize::from(expected)));
+    }
+
+    #[test]
+    fn agrees_with_the_main_crate_on_known_decks() {
+        agrees_with_the_main_crate_on_known_decks_in::<IntEncoding>();
+        agrees_with_the_main_crate_on_known_decks_in::<BvEncoding>();
+    }
+
+    #[test]
+    fn incremental_solving_stops_at_the_first_lost_cut() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let solve = |ids: [u8; 52]| {
+            let solver = Solver::new(&ctx);
+            let deck = partly_fixed_deck::<IntEncoding>(&ctx, &solver, ids, &[]);
+            let mut checks = Vec::new();
+            let progress =
+                solve_incrementally::<IntEncoding>(&ctx, &solver, &deck, 3, |cut, result| {
+                    checks.push((cut, result));
+                });
+            (progress, checks)
+        };
+
+        let perfect = known_decks::three_player_perfect_deck();
+        let (progress, checks) = solve(perfect);
+        assert_eq!(progress.satisfiable, 52);
+        assert_eq!(progress.stopped, SatResult::Sat);
+        assert_eq!(progress.deck, Some(perfect.to_vec()));
+        assert_eq!(checks.len(), 52);
+
+        // The main crate has the swapped deck winning cut 0 and losing cut 1
+        let swapped = known_decks::three_player_swapped_deck();
+        let (progress, checks) = solve(swapped);
+        assert_eq!(progress.satisfiable, 1);
+        assert_eq!(progress.stopped, SatResult::Unsat);
+        assert_eq!(progress.deck, Some(swapped.to_vec()));
+        assert_eq!(checks, [(0, SatResult::Sat), (1, SatResult::Unsat)]);
+    }
+
+    #[test]
+    fn card_ids_mean_the_same_cards_as_the_main_crate() {
+        for id in 0..52 {
+            assert_eq!(card_to_string(id), cards::Card(id as u8).to_string(), "card {id}");
+        }
+    }
 }
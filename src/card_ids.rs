@@ -0,0 +1,38 @@
+//! The comma-separated card id format decks are exchanged in, e.g. "3,17,42,..."
+
+/// Join card ids with commas
+pub fn to_id_string(ids: &[u8]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Split a comma-separated list into card ids, or the entry that isn't an integer
+/// The ids aren't checked to be in range or distinct; that's up to the caller
+pub fn parse_id_string(s: &str) -> impl Iterator<Item = Result<usize, &str>> {
+    s.trim().split(',').map(|id| {
+        let id = id.trim();
+        id.parse().map_err(|_| id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_strings_roundtrip() {
+        let ids = [3, 17, 0, 51];
+        let s = to_id_string(&ids);
+        assert_eq!(s, "3,17,0,51");
+        let parsed: Vec<usize> = parse_id_string(&s).collect::<Result<_, _>>().unwrap();
+        assert_eq!(parsed, vec![3, 17, 0, 51]);
+    }
+
+    #[test]
+    fn bad_entries_are_reported() {
+        let parsed: Vec<_> = parse_id_string(" 1, x ,2 ").collect();
+        assert_eq!(parsed, vec![Ok(1), Err("x"), Ok(2)]);
+    }
+}
@@ -12,6 +12,11 @@ impl Card {
         Self(value + 13 * scale)
     }
 
+    /// Every card of a full deck, in id order: each suit's ace through king in turn
+    pub fn all() -> impl Iterator<Item = Card> {
+        (0..=MAX_CARD).map(Card)
+    }
+
     pub fn valid(&self) -> bool {
         self.0 <= MAX_CARD
     }
@@ -21,6 +26,31 @@ impl Card {
         let value = (self.0 % 13) + 1;
         (Value::new(value), suit.into())
     }
+
+    /// The value hands compare on: 2..=13 for two through king, and 14 for an ace
+    pub fn rank_value(&self) -> u8 {
+        match self.straight_low_value() {
+            1 => 14,
+            value => value,
+        }
+    }
+
+    /// The value with the ace playing low, as it does in the wheel (A-2-3-4-5): 1..=13
+    pub fn straight_low_value(&self) -> u8 {
+        self.into_inner().0.0
+    }
+
+    pub fn color(&self) -> Color {
+        match self.into_inner().1 {
+            Suit::Clubs | Suit::Spades => Color::Black,
+            Suit::Hearts | Suit::Diamonds => Color::Red,
+        }
+    }
+
+    /// Hearts and diamonds
+    pub fn is_red(&self) -> bool {
+        self.color() == Color::Red
+    }
 }
 
 impl Arbitrary for Card {
@@ -39,6 +69,68 @@ impl std::fmt::Display for Card {
     }
 }
 
+/// Why a string could not be parsed as a [`Card`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardParseError {
+    /// Cards are exactly two characters: a rank and a suit
+    WrongLength(String),
+    /// The rank character is not one of `23456789TJQKA`
+    BadValue(char),
+    /// The suit character is not one of `cdhs` (or `♣♦♥♠`)
+    BadSuit(char),
+}
+
+impl std::fmt::Display for CardParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardParseError::WrongLength(s) => {
+                write!(f, "expected a rank and a suit like \"As\", got \"{s}\"")
+            }
+            CardParseError::BadValue(c) => write!(f, "invalid card rank '{c}'"),
+            CardParseError::BadSuit(c) => write!(f, "invalid card suit '{c}'"),
+        }
+    }
+}
+
+impl std::error::Error for CardParseError {}
+
+/// Parses the conventional two-character notation, e.g. "As", "Td", "7h"
+impl std::str::FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(value), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(CardParseError::WrongLength(s.to_string()));
+        };
+
+        let value = value_from_char(value).ok_or(CardParseError::BadValue(value))?;
+
+        let suit = match suit {
+            'c' | 'C' | '♣' => Suit::Clubs,
+            's' | 'S' | '♠' => Suit::Spades,
+            'h' | 'H' | '♥' => Suit::Hearts,
+            'd' | 'D' | '♦' => Suit::Diamonds,
+            _ => return Err(CardParseError::BadSuit(suit)),
+        };
+
+        Ok(Card::new(Value::new(value), suit))
+    }
+}
+
+/// The value of a rank character from `A23456789TJQK`, ignoring case
+fn value_from_char(c: char) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        'A' => Some(1),
+        c @ '2'..='9' => Some(c as u8 - b'0'),
+        'T' => Some(10),
+        'J' => Some(11),
+        'Q' => Some(12),
+        'K' => Some(13),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Value(pub u8);
 
@@ -52,9 +144,36 @@ impl Value {
     }
 }
 
+/// Shows the rank character, one of `A23456789TJQK`
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match self.0 {
+            1 => write!(f, "A"),
+            10 => write!(f, "T"),
+            11 => write!(f, "J"),
+            12 => write!(f, "Q"),
+            13 => write!(f, "K"),
+            n => write!(f, "{}", n),
+        }
+    }
+}
+
+/// Parses a rank character as shown by `Display`, or the raw number 1-13
+impl std::str::FromStr for Value {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(n @ 1..=13) = s.parse::<u8>() {
+            return Ok(Value::new(n));
+        }
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => value_from_char(c)
+                .map(Value::new)
+                .ok_or(CardParseError::BadValue(c)),
+            (Some(c), Some(_)) => Err(CardParseError::BadValue(c)),
+            (None, _) => Err(CardParseError::WrongLength(s.to_string())),
+        }
     }
 }
 
@@ -75,6 +194,19 @@ pub enum Suit {
     Diamonds,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+}
+
+impl Suit {
+    /// The 13 cards of this suit, ace through king
+    pub fn cards(self) -> impl Iterator<Item = Card> {
+        (1..=13).map(move |value| Card::new(Value::new(value), self))
+    }
+}
+
 impl From<Suit> for u8 {
     fn from(suit: Suit) -> u8 {
         use Suit::*;
@@ -138,18 +270,115 @@ mod tests {
         assert_eq!(suit, Suit::Clubs)
     }
 
+    #[test]
+    fn hearts_and_diamonds_are_red() {
+        for card in Card::all() {
+            let (_, suit) = card.into_inner();
+            let red = matches!(suit, Suit::Hearts | Suit::Diamonds);
+            assert_eq!(card.is_red(), red, "{card}");
+            assert_eq!(card.color(), if red { Color::Red } else { Color::Black });
+        }
+    }
+
+    #[test]
+    fn rank_value_puts_aces_high_and_straight_low_value_puts_them_low() {
+        let ace = Card::new(Value::new(1), Suit::Hearts);
+        assert_eq!(ace.rank_value(), 14);
+        assert_eq!(ace.straight_low_value(), 1);
+        for n in 2..=13 {
+            let card = Card::new(Value::new(n), Suit::Spades);
+            assert_eq!(card.rank_value(), n);
+            assert_eq!(card.straight_low_value(), n);
+        }
+    }
+
+    #[test]
+    fn all_yields_each_card_once_and_suits_split_it() {
+        assert_eq!(Card::all().count(), 52);
+        assert!(Card::all().all(|card| card.valid()));
+
+        let mut by_suit = Vec::new();
+        for suit in [Suit::Clubs, Suit::Spades, Suit::Hearts, Suit::Diamonds] {
+            let cards: Vec<Card> = suit.cards().collect();
+            assert_eq!(cards.len(), 13);
+            assert!(cards.iter().all(|card| card.into_inner().1 == suit));
+            assert!(cards.windows(2).all(|pair| pair[0] < pair[1]));
+            by_suit.extend(cards);
+        }
+        assert_eq!(by_suit, Card::all().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_valid_cards() {
+        assert_eq!("As".parse(), Ok(Card::new(Value::new(1), Suit::Spades)));
+        assert_eq!("Td".parse(), Ok(Card::new(Value::new(10), Suit::Diamonds)));
+        assert_eq!("7h".parse(), Ok(Card::new(Value::new(7), Suit::Hearts)));
+        assert_eq!("2c".parse(), Ok(Card::new(Value::new(2), Suit::Clubs)));
+        assert_eq!("K♠".parse(), Ok(Card::new(Value::new(13), Suit::Spades)));
+    }
+
+    #[test]
+    fn parse_rank_is_case_insensitive() {
+        for (upper, lower) in [
+            ("Ah", "ah"),
+            ("Tc", "tc"),
+            ("Js", "js"),
+            ("Qd", "qd"),
+            ("Kh", "kh"),
+        ] {
+            assert_eq!(upper.parse::<Card>(), lower.parse::<Card>());
+            assert!(upper.parse::<Card>().is_ok());
+        }
+    }
+
+    #[test]
+    fn display_uses_rank_characters() {
+        assert_eq!(Card::new(Value::new(13), Suit::Spades).to_string(), "K♠");
+        assert_eq!(Card::new(Value::new(1), Suit::Hearts).to_string(), "A♥");
+        assert_eq!(Card::new(Value::new(10), Suit::Clubs).to_string(), "T♣");
+        assert_eq!(Card::new(Value::new(7), Suit::Diamonds).to_string(), "7♦");
+    }
+
+    #[test]
+    fn value_parses_its_display_and_numbers() {
+        for n in 1..=13 {
+            let value = Value::new(n);
+            assert_eq!(value.to_string().parse(), Ok(value));
+            assert_eq!(n.to_string().parse(), Ok(value));
+        }
+        assert_eq!("q".parse(), Ok(Value::new(12)));
+        assert_eq!("14".parse::<Value>(), Err(CardParseError::BadValue('1')));
+        assert_eq!("0".parse::<Value>(), Err(CardParseError::BadValue('0')));
+        assert_eq!("X".parse::<Value>(), Err(CardParseError::BadValue('X')));
+    }
+
+    #[test]
+    fn parse_malformed_cards() {
+        assert_eq!("Xz".parse::<Card>(), Err(CardParseError::BadValue('X')));
+        assert_eq!("Az".parse::<Card>(), Err(CardParseError::BadSuit('z')));
+        assert_eq!(
+            "10h".parse::<Card>(),
+            Err(CardParseError::WrongLength("10h".to_string()))
+        );
+        assert_eq!(
+            "A".parse::<Card>(),
+            Err(CardParseError::WrongLength("A".to_string()))
+        );
+        assert!("".parse::<Card>().is_err());
+    }
+
     proptest! {
         #[test]
         fn test_value_always_in_valid_range(value in any::<Value>()) {
             // Value should always be between 1 and 13 inclusive
             let inner = value.0;
-            prop_assert!(inner >= 1 && inner <= 13);
+            prop_assert!((1..=13).contains(&inner));
         }
 
         #[test]
         fn test_suit_roundtrip_conversion(suit in any::<Suit>()) {
             // Converting Suit -> u8 -> Suit should return the original
-            let as_u8: u8 = suit.clone().into();
+            let as_u8: u8 = suit.into();
             let back_to_suit = Suit::from(as_u8);
 
             // Compare by converting both to u8 since Suit doesn't derive PartialEq
@@ -178,5 +407,10 @@ mod tests {
             let card2 = Card::new(value, suit);
             prop_assert_eq!(card, card2);
         }
+
+        #[test]
+        fn test_card_display_parses_back(card in any::<Card>()) {
+            prop_assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+        }
     }
 }
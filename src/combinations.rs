@@ -0,0 +1,110 @@
+//! Enumerating the `K`-element subsets of `0..n`
+
+/// Every `K`-element subset of `0..n` as strictly increasing indices,
+/// in lexicographic order (the order `K` nested loops would visit them)
+/// There are C(n, K) of them, and none if `K > n`
+pub fn combinations<const K: usize>(n: usize) -> Combinations<K> {
+    Combinations::starting_at(n, std::array::from_fn(|i| i))
+}
+
+pub struct Combinations<const K: usize> {
+    n: usize,
+    // The combination to yield next; None once iteration is complete
+    next: Option<[usize; K]>,
+}
+
+impl<const K: usize> Combinations<K> {
+    /// Iterate from `first`, which must be strictly increasing and below `n`
+    pub fn starting_at(n: usize, first: [usize; K]) -> Self {
+        Self {
+            n,
+            next: (K <= n).then_some(first),
+        }
+    }
+}
+
+impl<const K: usize> Iterator for Combinations<K> {
+    type Item = [usize; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        // Advance the rightmost index that still has room, and reset the ones after it
+        let mut next = current;
+        let mut i = K;
+        self.next = loop {
+            if i == 0 {
+                break None;
+            }
+            i -= 1;
+            if next[i] < self.n - K + i {
+                next[i] += 1;
+                for j in (i + 1)..K {
+                    next[j] = next[j - 1] + 1;
+                }
+                break Some(next);
+            }
+        };
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        if k > n {
+            return 0;
+        }
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    fn check<const K: usize>(n: usize) {
+        let mut count = 0;
+        let mut prev: Option<[usize; K]> = None;
+        for combo in combinations::<K>(n) {
+            assert!(combo.windows(2).all(|w| w[0] < w[1]), "{combo:?}");
+            assert!(combo.iter().all(|&i| i < n), "{combo:?}");
+            assert!(
+                prev.is_none_or(|prev| prev < combo),
+                "{prev:?} then {combo:?}"
+            );
+            prev = Some(combo);
+            count += 1;
+        }
+        assert_eq!(count, binomial(n, K), "C({n}, {K})");
+    }
+
+    #[test]
+    fn counts_match_binomials() {
+        for n in 0..10 {
+            check::<0>(n);
+            check::<1>(n);
+            check::<2>(n);
+            check::<3>(n);
+            check::<5>(n);
+            check::<7>(n);
+        }
+        check::<5>(20);
+    }
+
+    #[test]
+    fn seven_choose_five_in_nested_loop_order() {
+        let combos: Vec<[usize; 5]> = combinations(7).collect();
+        assert_eq!(combos.len(), 21);
+        assert_eq!(combos[0], [0, 1, 2, 3, 4]);
+        assert_eq!(combos[1], [0, 1, 2, 3, 5]);
+        assert_eq!(combos[20], [2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn starting_at_resumes_iteration() {
+        let all: Vec<[usize; 3]> = combinations(6).collect();
+        for (i, &combo) in all.iter().enumerate() {
+            let rest: Vec<_> = Combinations::starting_at(6, combo).collect();
+            assert_eq!(rest, all[i..]);
+        }
+    }
+}
@@ -1,11 +1,251 @@
 use oorandom::Rand32;
 
+use crate::card_ids::{parse_id_string, to_id_string};
 use crate::cards::*;
 
+/// The cards of a deck, in dealing order from the end
+/// Stored inline, so copying a deck never allocates; only the first `len` cards are in it,
+/// which lets short decks and partly dealt decks share the type
+#[derive(Clone, Copy)]
+pub struct Deck {
+    cards: [Card; DECK_SIZE],
+    len: u8,
+}
+
+pub const DECK_SIZE: usize = 52;
+pub const SHORT_DECK_SIZE: usize = 36;
+
+impl Default for Deck {
+    /// An empty deck
+    fn default() -> Self {
+        Deck {
+            cards: [Card(0); DECK_SIZE],
+            len: 0,
+        }
+    }
+}
+
+/// Decks are equal when they hold the same cards in the same order,
+/// whatever is left in the slots past the end
+impl PartialEq for Deck {
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl Eq for Deck {}
+
+/// Hashes only the cards in the deck, to agree with `PartialEq`
+impl std::hash::Hash for Deck {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self[..].hash(state);
+    }
+}
+
+impl std::fmt::Debug for Deck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Deck").field(&&self[..]).finish()
+    }
+}
+
+impl std::ops::Deref for Deck {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.cards[..self.len as usize]
+    }
+}
+
+impl std::ops::DerefMut for Deck {
+    fn deref_mut(&mut self) -> &mut [Card] {
+        &mut self.cards[..self.len as usize]
+    }
+}
+
+/// Panics if there are more than 52 cards
+impl FromIterator<Card> for Deck {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut deck = Deck::default();
+        for card in iter {
+            deck.push(card);
+        }
+        deck
+    }
+}
+
+/// Why a string of card ids, or a displayed deck, could not be parsed as a [`Deck`]
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Deck(pub Vec<Card>);
+pub enum DeckParseError {
+    /// A deck has exactly 52 cards, or 36 for a short deck
+    WrongLength { expected: usize, got: usize },
+    /// An entry is not an integer
+    BadId(String),
+    /// A card id is outside 0..=51
+    OutOfRange(usize),
+    /// The same card appears twice
+    Duplicate(u8),
+    /// A short deck only has the cards from 6 up to Ace
+    NotInShortDeck(u8),
+    /// A displayed deck is wrapped in `[` and `]`
+    MissingBrackets,
+    /// An entry of a displayed deck is not a card like "K♠"
+    BadCard(CardParseError),
+}
+
+impl std::fmt::Display for DeckParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckParseError::WrongLength { expected, got } => {
+                write!(f, "expected {expected} cards, got {got}")
+            }
+            DeckParseError::BadId(s) => write!(f, "invalid card id \"{s}\""),
+            DeckParseError::OutOfRange(id) => write!(f, "card id {id} is not in 0..=51"),
+            DeckParseError::Duplicate(id) => write!(f, "card id {id} appears more than once"),
+            DeckParseError::NotInShortDeck(id) => {
+                write!(f, "card id {id} is below a six, so not in a short deck")
+            }
+            DeckParseError::MissingBrackets => write!(f, "expected a deck like \"[K♠, 2♣, ...]\""),
+            DeckParseError::BadCard(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for DeckParseError {}
+
+/// Why a [`Deck`] is not a permutation of the 52 cards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckError {
+    /// A deck has exactly 52 cards
+    WrongLength(usize),
+    /// A card id is outside 0..=51
+    InvalidCard(u8),
+    /// The same card appears twice
+    Duplicate(u8),
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckError::WrongLength(n) => write!(f, "expected 52 cards, got {n}"),
+            DeckError::InvalidCard(id) => write!(f, "card id {id} is not in 0..=51"),
+            DeckError::Duplicate(id) => write!(f, "card id {id} appears more than once"),
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
 
 impl Deck {
+    /// Build a deck from its 52 card ids, the inverse of [`Deck::as_card_ids`]
+    pub fn from_card_ids(ids: &[u8]) -> Result<Deck, DeckParseError> {
+        let cards = ids.iter().map(|&id| Self::card_from_id(id as usize));
+        Self::from_parsed_cards(cards, DECK_SIZE)
+    }
+
+    /// Parse a deck from 52 comma-separated card ids, e.g. "3,17,42,..."
+    /// This is the format the SMT solver prints its solutions in
+    pub fn from_id_string(s: &str) -> Result<Deck, DeckParseError> {
+        Self::from_card_ids(&Self::parse_card_ids(s)?)
+    }
+
+    /// Parse a 36-card short deck (see `new_short_deck_order`) from comma-separated card ids
+    pub fn from_short_deck_id_string(s: &str) -> Result<Deck, DeckParseError> {
+        let short_deck = Deck::new_short_deck_order();
+        let cards = Self::parse_card_ids(s)?.into_iter().map(|id| {
+            let card = Self::card_from_id(id as usize)?;
+            if short_deck.contains(&card) {
+                Ok(card)
+            } else {
+                Err(DeckParseError::NotInShortDeck(id))
+            }
+        });
+        Self::from_parsed_cards(cards, SHORT_DECK_SIZE)
+    }
+
+    /// The ids in a comma-separated list, which must each fit in a `u8`
+    fn parse_card_ids(s: &str) -> Result<Vec<u8>, DeckParseError> {
+        parse_id_string(s)
+            .map(|id| {
+                let id = id.map_err(|id| DeckParseError::BadId(id.to_string()))?;
+                u8::try_from(id).map_err(|_| DeckParseError::OutOfRange(id))
+            })
+            .collect()
+    }
+
+    fn card_from_id(id: usize) -> Result<Card, DeckParseError> {
+        if id > 51 {
+            return Err(DeckParseError::OutOfRange(id));
+        }
+        Ok(Card(id as u8))
+    }
+
+    /// Collect parsed cards into a deck, checking there are `expected` of them with no repeats
+    fn from_parsed_cards(
+        cards: impl Iterator<Item = Result<Card, DeckParseError>>,
+        expected: usize,
+    ) -> Result<Deck, DeckParseError> {
+        let mut seen = [false; 52];
+        let mut deck = Deck::default();
+        for card in cards {
+            let card = card?;
+            if seen[card.0 as usize] {
+                return Err(DeckParseError::Duplicate(card.0));
+            }
+            seen[card.0 as usize] = true;
+            // Every card is distinct and valid, so no more than a full deck can get here
+            deck.push(card);
+        }
+
+        if deck.len() != expected {
+            return Err(DeckParseError::WrongLength {
+                expected,
+                got: deck.len(),
+            });
+        }
+        Ok(deck)
+    }
+
+    /// Check that this is a permutation of all 52 cards, e.g. after a crossover
+    pub fn validate(&self) -> Result<(), DeckError> {
+        let mut seen = [false; 52];
+        for card in self.iter() {
+            if !card.valid() {
+                return Err(DeckError::InvalidCard(card.0));
+            }
+            if seen[card.0 as usize] {
+                return Err(DeckError::Duplicate(card.0));
+            }
+            seen[card.0 as usize] = true;
+        }
+
+        if self.len() != 52 {
+            return Err(DeckError::WrongLength(self.len()));
+        }
+        Ok(())
+    }
+
+    pub fn is_valid_permutation(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Each card's id, in deck order
+    pub fn as_card_ids(&self) -> Vec<u8> {
+        self.iter().map(|c| c.0).collect()
+    }
+
+    /// The lengths of the runs of consecutive same-color cards, from the top of the deck
+    /// A shuffled deck has many short runs; long ones are a sign of an unnatural order
+    pub fn color_runs(&self) -> Vec<usize> {
+        self.chunk_by(|a, b| a.color() == b.color())
+            .map(<[Card]>::len)
+            .collect()
+    }
+
+    /// Format as comma-separated card ids, the inverse of [`Deck::from_id_string`]
+    pub fn to_id_string(self) -> String {
+        to_id_string(&self.as_card_ids())
+    }
+
     pub fn apply_mutations(mut self, mutations: impl Iterator<Item = Mutation>) -> Self {
         for mutation in mutations {
             self = self.apply_mutation(mutation);
@@ -13,61 +253,96 @@ impl Deck {
         self
     }
 
-    pub fn swap(&mut self, a: usize, b: usize) {
-        self.0.swap(a, b);
-    }
-
     pub fn apply_mutation(mut self, mutation: Mutation) -> Self {
-        self.0.swap(mutation.0.0, mutation.0.1);
+        self.swap(mutation.0.0, mutation.0.1);
         self
     }
 
+    /// Move the top `pos` cards to the bottom
+    /// `pos` wraps around the deck, so cutting at `len()` (or any multiple of it) is a no-op
     pub fn cut(mut self, pos: usize) -> Self {
-        let mut taken = self.0.drain(0..pos).collect::<Vec<_>>();
-        self.0.append(&mut taken);
+        if !self.is_empty() {
+            let len = self.len();
+            self.rotate_left(pos % len);
+        }
         self
     }
 
+    /// Take the card off the end of the deck, the next one dealt
     pub fn draw(&mut self) -> Card {
-        self.0.pop().unwrap()
+        self.len = self.len.checked_sub(1).expect("drew from an empty deck");
+        self.cards[self.len as usize]
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+    /// Put a card on the end of the deck
+    /// Panics if the deck already has 52 cards
+    pub fn push(&mut self, card: Card) {
+        assert!(
+            (self.len as usize) < DECK_SIZE,
+            "a deck holds at most 52 cards"
+        );
+        self.cards[self.len as usize] = card;
+        self.len += 1;
     }
 
+    /// Every card, grouped by value from aces up, with the suits in id order within a value
     pub fn new_deck_order() -> Deck {
-        let mut v = vec![];
-        v.reserve(52);
+        let mut deck: Deck = Card::all().collect();
+        // Stable, so each value's cards keep their suit order
+        deck.sort_by_key(Card::straight_low_value);
+        deck
+    }
 
-        for value in 1..=13 {
+    /// New deck order after `n_riffles` riffle shuffles like a dealer's: each cuts the deck
+    /// where a binomial(52, 1/2) number of coin flips says, as hands split it near the middle,
+    /// then drops the packets back together in clumps (see `riffle`)
+    /// Seven riffles are about enough to make every order equally likely
+    pub fn riffle_shuffle(n_riffles: usize, rng: &mut Rand32) -> Deck {
+        (0..n_riffles).fold(Deck::new_deck_order(), |deck, _| {
+            let split = (0..deck.len())
+                .filter(|_| rng.rand_range(0..2) == 1)
+                .count();
+            riffle(&deck, split, rng)
+        })
+    }
+
+    /// The 36-card short-deck (6+) deck: every card from 6 up to Ace
+    pub fn new_short_deck_order() -> Deck {
+        let mut deck = Deck::default();
+
+        for value in std::iter::once(1).chain(6..=13) {
             for suit in 0..4 {
-                let card = Card::new(Value::new(value), suit.into());
-                v.push(card);
+                deck.push(Card::new(Value::new(value), suit.into()));
             }
         }
-        assert!(v.len() == 52);
+        assert!(deck.len() == SHORT_DECK_SIZE);
 
-        Self(v)
+        deck
     }
 
-    pub fn len(&self) -> usize {
-        self.0.len()
+    pub fn shuffle(self, rand: &mut Rand32) -> Deck {
+        self.fisher_yates(|range| rand.rand_range(range))
     }
 
-    pub fn shuffle(mut self, rand: &mut Rand32) -> Deck {
-        let n = self.0.len() as u32;
+    /// Shuffle with any `rand` generator, e.g. a seeded `StdRng`
+    pub fn shuffle_with<R: rand::RngCore>(self, rng: &mut R) -> Deck {
+        self.fisher_yates(|range| rand::Rng::random_range(rng, range))
+    }
+
+    /// Fisher-Yates shuffle, where `pick` chooses a random index in the given range
+    fn fisher_yates(mut self, mut pick: impl FnMut(std::ops::Range<u32>) -> u32) -> Deck {
+        let n = self.len() as u32;
 
         for i in 0..(n - 1) {
-            let j = rand.rand_range(i..n) as usize;
-            self.0.swap(i as usize, j as usize);
+            let j = pick(i..n) as usize;
+            self.swap(i as usize, j);
         }
         self
     }
 
     /// Two-point crossover: takes a segment from parent1 and fills remaining positions with parent2's cards
     pub fn crossover(parent1: &Deck, parent2: &Deck, rng: &mut Rand32) -> Deck {
-        let deck_size = parent1.0.len();
+        let deck_size = parent1.len();
 
         // Choose two random crossover points
         let point1 = rng.rand_range(0..deck_size as u32) as usize;
@@ -81,69 +356,156 @@ impl Deck {
         // Start with parent1's segment between the crossover points
         let mut child = vec![None; deck_size];
         for i in start..end {
-            child[i] = Some(parent1.0[i]);
+            child[i] = Some(parent1[i]);
         }
 
-        // Fill remaining positions with parent2's cards in order, skipping duplicates
-        let mut parent2_idx = 0;
-        for i in 0..deck_size {
-            if child[i].is_none() {
-                // Find next card from parent2 that's not already in child
-                while let Some(_) = child.iter().find(|&&c| c == Some(parent2.0[parent2_idx])) {
-                    parent2_idx += 1;
-                    if parent2_idx >= deck_size {
-                        break;
-                    }
-                }
-                if parent2_idx < deck_size {
-                    child[i] = Some(parent2.0[parent2_idx]);
-                    parent2_idx += 1;
+        Self::fill_in_order(child, parent2)
+    }
+
+    /// Fill the holes in `child` with parent2's remaining cards, in parent2's order
+    /// Parent2 has exactly as many cards missing from the child as there are holes,
+    /// so every hole is filled
+    fn fill_in_order(mut child: Vec<Option<Card>>, parent2: &Deck) -> Deck {
+        let mut used = [false; 52];
+        for card in child.iter().flatten() {
+            used[card.0 as usize] = true;
+        }
+        let mut remaining = parent2.iter().filter(|card| !used[card.0 as usize]);
+
+        for slot in child.iter_mut().filter(|slot| slot.is_none()) {
+            *slot = remaining.next().copied();
+        }
+
+        child.into_iter().map(|c| c.unwrap()).collect()
+    }
+
+    /// Partially-mapped crossover (PMX): copies a segment from parent1 and places each of
+    /// parent2's displaced segment cards by following the segment mapping, so most cards
+    /// keep the absolute position they had in one of the parents
+    pub fn pmx_crossover(parent1: &Deck, parent2: &Deck, rng: &mut Rand32) -> Deck {
+        let deck_size = parent1.len();
+
+        let point1 = rng.rand_range(0..deck_size as u32) as usize;
+        let point2 = rng.rand_range(0..deck_size as u32) as usize;
+        let (start, end) = if point1 < point2 {
+            (point1, point2)
+        } else {
+            (point2, point1)
+        };
+
+        // Position of each card in parent2, indexed by card id
+        let mut parent2_pos = [0usize; 52];
+        for (i, card) in parent2.iter().enumerate() {
+            parent2_pos[card.0 as usize] = i;
+        }
+
+        let mut child = vec![None; deck_size];
+        for (slot, card) in child[start..end].iter_mut().zip(&parent1[start..end]) {
+            *slot = Some(*card);
+        }
+
+        // Each parent2 segment card that parent1's segment displaced goes to the first
+        // position outside the segment reached by following parent1 -> parent2
+        for i in start..end {
+            let card = parent2[i];
+            if parent1[start..end].contains(&card) {
+                continue;
+            }
+            let mut pos = i;
+            while (start..end).contains(&pos) {
+                pos = parent2_pos[parent1[pos].0 as usize];
+            }
+            child[pos] = Some(card);
+        }
+
+        // Everything else comes straight from parent2
+        for (i, slot) in child.iter_mut().enumerate() {
+            if slot.is_none() {
+                *slot = Some(parent2[i]);
+            }
+        }
+
+        child.into_iter().map(|c| c.unwrap()).collect()
+    }
+
+    /// Cycle crossover (CX): splits positions into the cycles linking the two parents and
+    /// takes each whole cycle from one parent, alternating, so every card keeps the absolute
+    /// position it had in the parent it came from
+    pub fn cycle_crossover(parent1: &Deck, parent2: &Deck, rng: &mut Rand32) -> Deck {
+        let first_from_parent1 = rng.rand_range(0..2) == 0;
+        Self::cycle_crossover_from(parent1, parent2, first_from_parent1)
+    }
+
+    fn cycle_crossover_from(parent1: &Deck, parent2: &Deck, first_from_parent1: bool) -> Deck {
+        let deck_size = parent1.len();
+
+        // Position of each card in parent1, indexed by card id
+        let mut parent1_pos = [0usize; 52];
+        for (i, card) in parent1.iter().enumerate() {
+            parent1_pos[card.0 as usize] = i;
+        }
+
+        let mut child = vec![None; deck_size];
+        let mut from_parent1 = first_from_parent1;
+        for start in 0..deck_size {
+            if child[start].is_some() {
+                continue;
+            }
+            let source = if from_parent1 { parent1 } else { parent2 };
+            let mut pos = start;
+            loop {
+                child[pos] = Some(source[pos]);
+                pos = parent1_pos[parent2[pos].0 as usize];
+                if pos == start {
+                    break;
                 }
             }
+            from_parent1 = !from_parent1;
         }
 
-        Deck(child.into_iter().map(|c| c.unwrap()).collect())
+        child.into_iter().map(|c| c.unwrap()).collect()
     }
 
     /// Uniform crossover: each position randomly chosen from either parent
     /// This maintains valid decks by using order-based crossover
     pub fn uniform_crossover(parent1: &Deck, parent2: &Deck, rng: &mut Rand32) -> Deck {
-        let deck_size = parent1.0.len();
+        let deck_size = parent1.len();
         let mut child = vec![None; deck_size];
 
         // Randomly select positions to inherit from parent1
         for i in 0..deck_size {
             if rng.rand_range(0..2) == 0 {
-                child[i] = Some(parent1.0[i]);
+                child[i] = Some(parent1[i]);
             }
         }
 
-        // Fill remaining positions with parent2's cards in order, skipping duplicates
-        let mut parent2_idx = 0;
-        for i in 0..deck_size {
-            if child[i].is_none() {
-                // Find next card from parent2 that's not already in child
-                while let Some(_) = child.iter().find(|&&c| c == Some(parent2.0[parent2_idx])) {
-                    parent2_idx += 1;
-                    if parent2_idx >= deck_size {
-                        break;
-                    }
-                }
-                if parent2_idx < deck_size {
-                    child[i] = Some(parent2.0[parent2_idx]);
-                    parent2_idx += 1;
-                }
-            }
-        }
+        Self::fill_in_order(child, parent2)
+    }
+}
+
+/// Parses the `Display` form, e.g. "[K♠, 2♣, ...]", so decks round-trip through text
+impl std::str::FromStr for Deck {
+    type Err = DeckParseError;
 
-        Deck(child.into_iter().map(|c| c.unwrap()).collect())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or(DeckParseError::MissingBrackets)?;
+        Self::from_parsed_cards(
+            cards
+                .split(',')
+                .map(|card| card.trim().parse().map_err(DeckParseError::BadCard)),
+            DECK_SIZE,
+        )
     }
 }
 
 impl std::fmt::Display for Deck {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
-        for (i, card) in self.0.iter().enumerate() {
+        for (i, card) in self.iter().enumerate() {
             if i > 0 {
                 write!(f, ", ")?;
             }
@@ -153,6 +515,37 @@ impl std::fmt::Display for Deck {
     }
 }
 
+/// How much a deck looks like a thoroughly shuffled one, from 0 to 1
+/// Counts its rising sequences (runs of consecutive cards of new deck order that appear in
+/// order, the statistic riffle shuffles are analysed with): new deck order has one, each riffle
+/// can at most double them, and a random deck has about half as many as it has cards. Decks
+/// with one or with as many as cards (new deck order reversed) score 0, and a random deck ~1
+pub fn shuffle_plausibility(deck: &Deck) -> f32 {
+    let expected = (deck.len() as f32 + 1.0) / 2.0;
+    if expected <= 1.0 {
+        return 1.0;
+    }
+    let distance = (rising_sequences(deck) as f32 - expected).abs();
+    (1.0 - distance / (expected - 1.0)).max(0.0)
+}
+
+/// The runs of cards, taken in new deck order, that appear in increasing positions in `deck`
+fn rising_sequences(deck: &Deck) -> usize {
+    let mut positions = [None; DECK_SIZE];
+    for (pos, card) in deck.iter().enumerate() {
+        positions[card.0 as usize] = Some(pos);
+    }
+    let in_new_deck_order = Deck::new_deck_order();
+    let positions: Vec<usize> = in_new_deck_order
+        .iter()
+        .filter_map(|card| positions[card.0 as usize])
+        .collect();
+    1 + positions
+        .windows(2)
+        .filter(|pair| pair[1] < pair[0])
+        .count()
+}
+
 pub fn generate_mutations(rng: &mut Rand32) -> impl Iterator<Item = Mutation> {
     let num_mutations = rng.rand_range(1..4);
     let mut muts = vec![];
@@ -163,6 +556,15 @@ pub fn generate_mutations(rng: &mut Rand32) -> impl Iterator<Item = Mutation> {
 }
 
 pub fn generate_adaptive_mutations(rng: &mut Rand32, mutation_rate: f32) -> Vec<AdvancedMutation> {
+    generate_adaptive_mutations_with(rng, mutation_rate, MutationPolicy::Mixed)
+}
+
+/// `generate_adaptive_mutations`, drawing only the kinds of mutation `policy` allows
+pub fn generate_adaptive_mutations_with(
+    rng: &mut Rand32,
+    mutation_rate: f32,
+    policy: MutationPolicy,
+) -> Vec<AdvancedMutation> {
     // Number of mutations scales with mutation_rate
     let num_mutations = if mutation_rate > 0.2 {
         rng.rand_range(2..5) as usize
@@ -172,11 +574,116 @@ pub fn generate_adaptive_mutations(rng: &mut Rand32, mutation_rate: f32) -> Vec<
 
     let mut muts = vec![];
     for _ in 0..num_mutations {
-        muts.push(AdvancedMutation::generate(rng, mutation_rate));
+        muts.push(AdvancedMutation::generate_with(rng, mutation_rate, policy));
     }
     muts
 }
 
+/// A way of measuring how far apart two orderings of the same cards are
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DeckDistance {
+    /// Positions holding different cards, 0..=52
+    #[default]
+    Hamming,
+    /// Pairs of cards in opposite orders, 0..=1326: how many adjacent swaps turn one deck into
+    /// the other, so a deck shifted along by one card is close rather than entirely different
+    KendallTau,
+    /// The fewest swaps of any two cards that turn one deck into the other, 0..=51
+    Cayley,
+}
+
+impl DeckDistance {
+    /// The largest distance between two orderings of `len` cards
+    pub fn max(self, len: usize) -> usize {
+        match self {
+            DeckDistance::Hamming => len,
+            DeckDistance::KendallTau => len * len.saturating_sub(1) / 2,
+            DeckDistance::Cayley => len.saturating_sub(1),
+        }
+    }
+}
+
+/// How far apart `a` and `b`, orderings of the same cards, are under `metric`
+pub fn deck_distance(a: &Deck, b: &Deck, metric: DeckDistance) -> usize {
+    if metric == DeckDistance::Hamming {
+        return a.iter().zip(b.iter()).filter(|(a, b)| a != b).count();
+    }
+    // Where each of a's cards is in b: the permutation taking a to b
+    let mut positions_in_b = [0; DECK_SIZE];
+    for (pos, card) in b.iter().enumerate() {
+        positions_in_b[card.0 as usize] = pos;
+    }
+    let perm: Vec<usize> = a
+        .iter()
+        .map(|card| positions_in_b[card.0 as usize])
+        .collect();
+    match metric {
+        DeckDistance::KendallTau => (0..perm.len())
+            .map(|i| {
+                perm[i + 1..]
+                    .iter()
+                    .filter(|&&later| later < perm[i])
+                    .count()
+            })
+            .sum(),
+        DeckDistance::Cayley => {
+            // Each cycle of the permutation takes one swap fewer than its length
+            let mut seen = vec![false; perm.len()];
+            let mut cycles = 0;
+            for start in 0..perm.len() {
+                if !seen[start] {
+                    cycles += 1;
+                    let mut pos = start;
+                    while !seen[pos] {
+                        seen[pos] = true;
+                        pos = perm[pos];
+                    }
+                }
+            }
+            perm.len() - cycles
+        }
+        DeckDistance::Hamming => unreachable!(),
+    }
+}
+
+/// One of `Deck`'s ways of breeding a child deck from two parents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CrossoverOperator {
+    /// [`Deck::crossover`]
+    TwoPoint,
+    /// [`Deck::pmx_crossover`]
+    Pmx,
+    /// [`Deck::cycle_crossover`]
+    Cycle,
+    /// [`Deck::uniform_crossover`]
+    Uniform,
+}
+
+impl CrossoverOperator {
+    pub fn apply(self, parent1: &Deck, parent2: &Deck, rng: &mut Rand32) -> Deck {
+        match self {
+            CrossoverOperator::TwoPoint => Deck::crossover(parent1, parent2, rng),
+            CrossoverOperator::Pmx => Deck::pmx_crossover(parent1, parent2, rng),
+            CrossoverOperator::Cycle => Deck::cycle_crossover(parent1, parent2, rng),
+            CrossoverOperator::Uniform => Deck::uniform_crossover(parent1, parent2, rng),
+        }
+    }
+}
+
+/// Which kinds of [`AdvancedMutation`] may be generated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MutationPolicy {
+    /// Only small-neighbourhood moves: swaps, reversals and block swaps
+    GentleOnly,
+    /// Any of the abstract mutations, including rotations, scrambles and insertions
+    Aggressive,
+    /// Gentle moves at low mutation rates, and any abstract mutation above 0.2
+    #[default]
+    Mixed,
+    /// Only what a dealer could do at the table: riffle shuffles and cuts
+    Physical,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Mutation((usize, usize));
 
@@ -198,14 +705,28 @@ pub enum AdvancedMutation {
     Reversal(usize, usize),         // start, end
     Rotation(usize),                // cut position
     Scramble(usize, usize),         // start, end - shuffle this segment
+    Insertion(usize, usize),        // from, to - move one card, shifting the rest
+    Riffle(usize),                  // split - interleave the cards above and below it
+    MultiCut(usize),                // number of successive cuts at random positions
 }
 
 impl AdvancedMutation {
     pub fn generate(rng: &mut Rand32, mutation_rate: f32) -> Self {
+        Self::generate_with(rng, mutation_rate, MutationPolicy::Mixed)
+    }
+
+    /// `generate`, but only the kinds of mutation `policy` allows
+    pub fn generate_with(rng: &mut Rand32, mutation_rate: f32, policy: MutationPolicy) -> Self {
         // Higher mutation rate = more aggressive mutations
-        let mutation_type = if mutation_rate > 0.2 {
+        let aggressive = match policy {
+            MutationPolicy::GentleOnly => false,
+            MutationPolicy::Aggressive => true,
+            MutationPolicy::Mixed => mutation_rate > 0.2,
+            MutationPolicy::Physical => return Self::generate_physical(rng),
+        };
+        let mutation_type = if aggressive {
             // When stuck, use more aggressive mutations
-            rng.rand_range(0..5)
+            rng.rand_range(0..6)
         } else {
             // When progressing, favor simpler mutations (swap, reversal)
             match rng.rand_range(0..10) {
@@ -242,7 +763,7 @@ impl AdvancedMutation {
                 let pos = rng.rand_range(1..52) as usize;
                 AdvancedMutation::Rotation(pos)
             }
-            _ => {
+            4 => {
                 // Scramble - shuffle a segment
                 let a = rng.rand_range(0..52) as usize;
                 let b = rng.rand_range(0..52) as usize;
@@ -250,13 +771,28 @@ impl AdvancedMutation {
                 let end = a.max(b).min(start + 10); // Limit scramble size
                 AdvancedMutation::Scramble(start, end)
             }
+            _ => {
+                // Insertion - move a single card to a new position
+                let from = rng.rand_range(0..52) as usize;
+                let to = rng.rand_range(0..52) as usize;
+                AdvancedMutation::Insertion(from, to)
+            }
+        }
+    }
+
+    /// A riffle split within a few cards of the middle, or two to four cuts
+    fn generate_physical(rng: &mut Rand32) -> Self {
+        if rng.rand_range(0..2) == 0 {
+            AdvancedMutation::Riffle(rng.rand_range(20..33) as usize)
+        } else {
+            AdvancedMutation::MultiCut(rng.rand_range(2..5) as usize)
         }
     }
 
     pub fn apply(self, mut deck: Deck, rng: &mut Rand32) -> Deck {
         match self {
             AdvancedMutation::Swap(i, j) => {
-                deck.0.swap(i, j);
+                deck.swap(i, j);
                 deck
             }
             AdvancedMutation::BlockSwap(start1, start2, len) => {
@@ -265,13 +801,13 @@ impl AdvancedMutation {
                 }
                 // Swap blocks by using a temporary buffer
                 for i in 0..len {
-                    deck.0.swap(start1 + i, start2 + i);
+                    deck.swap(start1 + i, start2 + i);
                 }
                 deck
             }
             AdvancedMutation::Reversal(start, end) => {
                 if start < end && end <= 52 {
-                    deck.0[start..end].reverse();
+                    deck[start..end].reverse();
                 }
                 deck
             }
@@ -281,34 +817,532 @@ impl AdvancedMutation {
                     // Fisher-Yates shuffle on the segment
                     for i in start..end {
                         let j = rng.rand_range(i as u32..end as u32) as usize;
-                        deck.0.swap(i, j);
+                        deck.swap(i, j);
                     }
                 }
                 deck
             }
+            AdvancedMutation::Insertion(from, to) => {
+                // Shift the cards between `from` and `to` one place towards `from`
+                if from < to && to < 52 {
+                    deck[from..=to].rotate_left(1);
+                } else if to < from && from < 52 {
+                    deck[to..=from].rotate_right(1);
+                }
+                deck
+            }
+            AdvancedMutation::Riffle(split) => riffle(&deck, split, rng),
+            AdvancedMutation::MultiCut(cuts) => (0..cuts).fold(deck, |deck, _| {
+                deck.cut(rng.rand_range(1..deck.len().max(2) as u32) as usize)
+            }),
         }
     }
 }
 
+/// Split `deck` after its first `split` cards and drop the two packets back together the way
+/// hands do: each card falls from a packet with probability proportional to the cards left in
+/// it (the Gilbert-Shannon-Reeds model), so cards fall in clumps and each packet keeps its order
+fn riffle(deck: &Deck, split: usize, rng: &mut Rand32) -> Deck {
+    let (mut left, mut right) = deck.split_at(split.min(deck.len()));
+    let mut riffled = Deck::default();
+    while !left.is_empty() || !right.is_empty() {
+        let remaining = (left.len() + right.len()) as u32;
+        let packet = if (rng.rand_range(0..remaining) as usize) < left.len() {
+            &mut left
+        } else {
+            &mut right
+        };
+        riffled.push(packet[0]);
+        *packet = &packet[1..];
+    }
+    riffled
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use proptest::prelude::*;
+    #[test]
+    fn drawn_cards_do_not_affect_eq_or_hash() {
+        use std::collections::HashSet;
+        let mut drawn = Deck::new_deck_order();
+        drawn.draw();
+        let mut pushed: Deck = Deck::new_deck_order().iter().take(51).copied().collect();
+        assert_eq!(drawn, pushed);
+        let set: HashSet<Deck> = [drawn, pushed].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        pushed.push(Card(0));
+        assert_ne!(drawn, pushed);
+    }
+
     #[test]
     fn cut_0_does_nothing() {
         let start = Deck::new_deck_order();
-        let c = start.clone().cut(0);
+        let c = start.cut(0);
         assert_eq!(start, c);
     }
 
+    #[test]
+    fn new_deck_order_groups_values_from_aces_up() {
+        let deck = Deck::new_deck_order();
+        assert!(deck.is_valid_permutation());
+        let first: Vec<String> = deck[..5].iter().map(Card::to_string).collect();
+        assert_eq!(first, ["A♣", "A♠", "A♥", "A♦", "2♣"]);
+        assert_eq!(deck[51].to_string(), "K♦");
+    }
+
+    #[test]
+    fn new_deck_order_alternates_pairs_of_colors() {
+        // Each value goes clubs, spades, hearts, diamonds: two black then two red
+        let deck = Deck::new_deck_order();
+        assert_eq!(deck.color_runs(), vec![2; 26]);
+        let colors: Vec<Color> = deck[..4].iter().map(Card::color).collect();
+        assert_eq!(colors, [Color::Black, Color::Black, Color::Red, Color::Red]);
+    }
+
+    #[test]
+    fn color_runs_cover_the_deck() {
+        let mut rng = Rand32::new(7);
+        let deck = Deck::new_deck_order().shuffle(&mut rng);
+        assert_eq!(deck.color_runs().iter().sum::<usize>(), DECK_SIZE);
+        let suit_order: Deck = Card::all().collect();
+        assert_eq!(suit_order.color_runs(), [26, 26]);
+        assert!(Deck::default().color_runs().is_empty());
+    }
+
+    #[test]
+    fn new_deck_order_is_an_implausible_shuffle() {
+        let deck = Deck::new_deck_order();
+        assert_eq!(rising_sequences(&deck), 1);
+        assert_eq!(shuffle_plausibility(&deck), 0.0);
+        let mut reversed = deck;
+        reversed.reverse();
+        assert_eq!(shuffle_plausibility(&reversed), 0.0);
+    }
+
+    #[test]
+    fn random_shuffles_are_plausible() {
+        let mut rng = Rand32::new(3);
+        for _ in 0..100 {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let plausibility = shuffle_plausibility(&deck);
+            assert!(plausibility > 0.7, "{deck:?} scored {plausibility}");
+        }
+    }
+
+    #[test]
+    fn a_riffle_at_most_doubles_rising_sequences() {
+        // Perfectly interleave the two halves, then again
+        let riffle = |deck: &Deck| -> Deck {
+            let (top, bottom) = deck.split_at(26);
+            top.iter().zip(bottom).flat_map(|(a, b)| [*a, *b]).collect()
+        };
+        let once = riffle(&Deck::new_deck_order());
+        assert_eq!(rising_sequences(&once), 2);
+        assert_eq!(rising_sequences(&riffle(&once)), 4);
+        assert!(shuffle_plausibility(&riffle(&once)) < 0.2);
+    }
+
+    #[test]
+    fn riffles_and_cuts_keep_new_deck_order_in_two_rising_sequences() {
+        // Either keeps each packet in order, so it can never look like more than two
+        let mut rng = Rand32::new(9);
+        for mutation in [AdvancedMutation::Riffle(26), AdvancedMutation::MultiCut(3)] {
+            let deck = mutation.clone().apply(Deck::new_deck_order(), &mut rng);
+            assert!(deck.is_valid_permutation());
+            assert!(rising_sequences(&deck) <= 2, "{mutation:?} gave {deck:?}");
+        }
+    }
+
+    #[test]
+    fn deck_distances_on_four_cards() {
+        let deck = |ids: [u8; 4]| -> Deck { ids.into_iter().map(Card).collect() };
+        let start = deck([0, 1, 2, 3]);
+        let distances = |other: Deck| {
+            [
+                DeckDistance::Hamming,
+                DeckDistance::KendallTau,
+                DeckDistance::Cayley,
+            ]
+            .map(|metric| deck_distance(&start, &other, metric))
+        };
+        assert_eq!(distances(start), [0, 0, 0]);
+        // One adjacent swap
+        assert_eq!(distances(deck([1, 0, 2, 3])), [2, 1, 1]);
+        // Two disjoint adjacent swaps
+        assert_eq!(distances(deck([1, 0, 3, 2])), [4, 2, 2]);
+        // A cut: every card moves, but only one is out of order with the rest
+        assert_eq!(distances(deck([1, 2, 3, 0])), [4, 3, 3]);
+        // Reversed: every pair is out of order, but two swaps put it back
+        assert_eq!(distances(deck([3, 2, 1, 0])), [4, 6, 2]);
+        // Swapping the ends
+        assert_eq!(distances(deck([3, 1, 2, 0])), [2, 5, 1]);
+    }
+
+    #[test]
+    fn deck_distances_are_symmetric_and_bounded() {
+        let mut rng = Rand32::new(13);
+        let a = Deck::new_deck_order().shuffle(&mut rng);
+        let b = Deck::new_deck_order().shuffle(&mut rng);
+        for metric in [
+            DeckDistance::Hamming,
+            DeckDistance::KendallTau,
+            DeckDistance::Cayley,
+        ] {
+            assert_eq!(deck_distance(&a, &b, metric), deck_distance(&b, &a, metric));
+            assert!(deck_distance(&a, &b, metric) <= metric.max(DECK_SIZE));
+        }
+        let mut reversed = a;
+        reversed.reverse();
+        assert_eq!(deck_distance(&a, &reversed, DeckDistance::KendallTau), 1326);
+    }
+
+    #[test]
+    fn riffle_shuffles_double_rising_sequences_until_the_deck_is_mixed() {
+        let mut rng = Rand32::new(21);
+        assert_eq!(Deck::riffle_shuffle(0, &mut rng), Deck::new_deck_order());
+        for n_riffles in 1..=3 {
+            let deck = Deck::riffle_shuffle(n_riffles, &mut rng);
+            assert!(deck.is_valid_permutation());
+            assert!(
+                rising_sequences(&deck) <= 1 << n_riffles,
+                "{n_riffles}: {deck:?}"
+            );
+        }
+        let mixed = Deck::riffle_shuffle(10, &mut rng);
+        assert!(shuffle_plausibility(&mixed) > 0.7, "{mixed:?}");
+    }
+
+    #[test]
+    fn cut_len_does_nothing() {
+        let start = Deck::new_deck_order();
+        assert_eq!(start.cut(start.len()), start);
+        let short = Deck::new_short_deck_order();
+        assert_eq!(short.cut(short.len()), short);
+        assert_eq!(Deck::default().cut(7), Deck::default());
+    }
+
+    #[test]
+    fn from_id_string_valid_permutation() {
+        let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(4));
+        assert_eq!(Deck::from_id_string(&deck.to_id_string()), Ok(deck));
+    }
+
+    #[test]
+    fn from_card_ids_inverts_as_card_ids() {
+        let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(5));
+        assert_eq!(Deck::from_card_ids(&deck.as_card_ids()), Ok(deck));
+        assert_eq!(
+            Deck::from_card_ids(&[0, 1, 2]),
+            Err(DeckParseError::WrongLength {
+                expected: DECK_SIZE,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn from_id_string_rejects_duplicate() {
+        let mut deck = Deck::new_deck_order();
+        deck[10] = deck[20];
+        let id = deck[20].0;
+        assert_eq!(
+            Deck::from_id_string(&deck.to_id_string()),
+            Err(DeckParseError::Duplicate(id))
+        );
+    }
+
+    #[test]
+    fn from_id_string_rejects_out_of_range() {
+        let mut deck = Deck::new_deck_order();
+        deck[0] = Card(52);
+        assert_eq!(
+            Deck::from_id_string(&deck.to_id_string()),
+            Err(DeckParseError::OutOfRange(52))
+        );
+    }
+
+    #[test]
+    fn from_id_string_rejects_short_deck() {
+        assert_eq!(
+            Deck::from_id_string("0,1,2"),
+            Err(DeckParseError::WrongLength {
+                expected: DECK_SIZE,
+                got: 3
+            })
+        );
+        assert_eq!(
+            Deck::from_id_string("0,x,2"),
+            Err(DeckParseError::BadId("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn short_deck_id_strings_roundtrip() {
+        let deck = Deck::new_short_deck_order().shuffle(&mut Rand32::new(36));
+        assert_eq!(
+            Deck::from_short_deck_id_string(&deck.to_id_string()),
+            Ok(deck)
+        );
+        let mut ids = deck.as_card_ids();
+        ids.pop();
+        assert_eq!(
+            Deck::from_short_deck_id_string(&to_id_string(&ids)),
+            Err(DeckParseError::WrongLength {
+                expected: SHORT_DECK_SIZE,
+                got: SHORT_DECK_SIZE - 1
+            })
+        );
+        let two_of_clubs = Card::new(Value::new(2), Suit::Clubs).0;
+        ids.push(two_of_clubs);
+        assert_eq!(
+            Deck::from_short_deck_id_string(&to_id_string(&ids)),
+            Err(DeckParseError::NotInShortDeck(two_of_clubs))
+        );
+    }
+
+    #[test]
+    fn short_deck_has_no_low_cards() {
+        let deck = Deck::new_short_deck_order();
+        assert_eq!(deck.len(), SHORT_DECK_SIZE);
+        for card in deck.iter() {
+            let value = card.into_inner().0.0;
+            assert!(value == 1 || value >= 6);
+        }
+        let mut ids = deck.as_card_ids();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), SHORT_DECK_SIZE);
+    }
+
+    #[test]
+    fn validate_accepts_a_shuffled_deck() {
+        let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(9));
+        assert_eq!(deck.validate(), Ok(()));
+        assert!(deck.is_valid_permutation());
+    }
+
+    #[test]
+    fn validate_rejects_a_duplicate() {
+        let mut deck = Deck::new_deck_order();
+        deck[10] = deck[3];
+        assert_eq!(deck.validate(), Err(DeckError::Duplicate(deck[3].0)));
+        assert!(!deck.is_valid_permutation());
+    }
+
+    #[test]
+    fn validate_rejects_a_short_deck() {
+        let deck = Deck::new_short_deck_order();
+        assert_eq!(
+            deck.validate(),
+            Err(DeckError::WrongLength(SHORT_DECK_SIZE))
+        );
+
+        let mut deck = Deck::new_deck_order();
+        deck[51] = Card(52);
+        assert_eq!(deck.validate(), Err(DeckError::InvalidCard(52)));
+    }
+
+    #[test]
+    fn pmx_of_identical_parents_is_that_parent() {
+        let parent = Deck::new_deck_order().shuffle(&mut Rand32::new(7));
+        for seed in 0..20 {
+            let child = Deck::pmx_crossover(&parent, &parent, &mut Rand32::new(seed));
+            assert_eq!(child, parent);
+        }
+    }
+
+    #[test]
+    fn cycle_crossover_small_example() {
+        let deck = |ids: &[u8]| ids.iter().map(|&id| Card(id)).collect::<Deck>();
+        let parent1 = deck(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let parent2 = deck(&[7, 4, 1, 0, 2, 5, 3, 6]);
+
+        // Cycles are {0, 7, 6, 3}, {1, 4, 2}, and {5}
+        assert_eq!(
+            Deck::cycle_crossover_from(&parent1, &parent2, true),
+            deck(&[0, 4, 1, 3, 2, 5, 6, 7])
+        );
+        assert_eq!(
+            Deck::cycle_crossover_from(&parent1, &parent2, false),
+            deck(&[7, 1, 2, 0, 4, 5, 3, 6])
+        );
+    }
+
+    #[test]
+    fn insertion_moves_one_card() {
+        let deck: Deck = Card::all().collect();
+        let mut rng = Rand32::new(0);
+
+        let moved = AdvancedMutation::Insertion(2, 5).apply(deck, &mut rng);
+        let expected: Vec<u8> = [0, 1, 3, 4, 5, 2].into_iter().chain(6..52).collect();
+        assert_eq!(moved.as_card_ids(), expected);
+
+        let moved = AdvancedMutation::Insertion(5, 2).apply(deck, &mut rng);
+        let expected: Vec<u8> = [0, 1, 5, 2, 3, 4].into_iter().chain(6..52).collect();
+        assert_eq!(moved.as_card_ids(), expected);
+    }
+
+    #[test]
+    fn shuffle_with_seeded_std_rng_is_reproducible() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let first = Deck::new_deck_order().shuffle_with(&mut StdRng::seed_from_u64(5));
+        let second = Deck::new_deck_order().shuffle_with(&mut StdRng::seed_from_u64(5));
+        let other = Deck::new_deck_order().shuffle_with(&mut StdRng::seed_from_u64(6));
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(first.is_valid_permutation());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_displayed_decks() {
+        let shown = Deck::new_deck_order().to_string();
+        assert_eq!(
+            shown.trim_start_matches('[').parse::<Deck>(),
+            Err(DeckParseError::MissingBrackets)
+        );
+        assert_eq!(
+            "[A♠, K♥]".parse::<Deck>(),
+            Err(DeckParseError::WrongLength {
+                expected: DECK_SIZE,
+                got: 2
+            })
+        );
+        assert_eq!(
+            "[A♠, A♠]".parse::<Deck>(),
+            Err(DeckParseError::Duplicate(
+                Card::new(Value::new(1), Suit::Spades).0
+            ))
+        );
+        assert_eq!(
+            "[A♠, 1♥]".parse::<Deck>(),
+            Err(DeckParseError::BadCard(CardParseError::BadValue('1')))
+        );
+    }
+
+    #[test]
+    fn gentle_policy_never_disrupts_the_deck() {
+        let mut rng = Rand32::new(6);
+        for rate in [0.0, 0.2, 0.3, 1.0] {
+            for _ in 0..500 {
+                let mutation =
+                    AdvancedMutation::generate_with(&mut rng, rate, MutationPolicy::GentleOnly);
+                assert!(
+                    !matches!(
+                        mutation,
+                        AdvancedMutation::Scramble(..)
+                            | AdvancedMutation::Rotation(_)
+                            | AdvancedMutation::Insertion(..)
+                    ),
+                    "{mutation:?}"
+                );
+            }
+        }
+        // Aggressive draws every kind even at a low rate
+        let aggressive: Vec<_> = (0..500)
+            .map(|_| AdvancedMutation::generate_with(&mut rng, 0.0, MutationPolicy::Aggressive))
+            .collect();
+        assert!(
+            aggressive
+                .iter()
+                .any(|m| matches!(m, AdvancedMutation::Scramble(..)))
+        );
+    }
+
     proptest! {
+        #[test]
+        fn test_id_string_roundtrip(seed: u64) {
+            let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(seed));
+            prop_assert_eq!(Deck::from_id_string(&deck.to_id_string()), Ok(deck));
+        }
+
+        #[test]
+        fn test_display_parse_roundtrip(seed: u64) {
+            let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(seed));
+            prop_assert_eq!(deck.to_string().parse::<Deck>(), Ok(deck));
+        }
+
         #[test]
         fn test_cut_twice_roundtrip(cut_pos in 0usize..52) {
             let deck = Deck::new_deck_order();
-            let d1 = deck.clone().cut(cut_pos);
+            let d1 = deck.cut(cut_pos);
             let d2 = d1.cut(52 - cut_pos);
             assert_eq!(deck, d2);
 
         }
+
+        #[test]
+        fn test_cut_wraps_around(seed: u64, cut_pos: usize) {
+            let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(seed));
+            let cut = deck.cut(cut_pos);
+            prop_assert!(cut.is_valid_permutation());
+            prop_assert_eq!(cut, deck.cut(cut_pos % 52));
+        }
+
+        #[test]
+        fn test_crossover_is_a_permutation(seed1: u64, seed2: u64, seed3: u64) {
+            let parent1 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed1));
+            let parent2 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed2));
+            let mut rng = Rand32::new(seed3);
+            for _ in 0..16 {
+                let child = Deck::crossover(&parent1, &parent2, &mut rng);
+                assert!(child.is_valid_permutation());
+                let child = Deck::uniform_crossover(&parent1, &parent2, &mut rng);
+                assert!(child.is_valid_permutation());
+            }
+        }
+
+        #[test]
+        fn test_insertion_is_a_permutation(seed: u64, from in 0usize..52, to in 0usize..52) {
+            let deck = Deck::new_deck_order().shuffle(&mut Rand32::new(seed));
+            let moved = AdvancedMutation::Insertion(from, to).apply(deck, &mut Rand32::new(seed));
+            assert!(moved.is_valid_permutation());
+            assert_eq!(moved[to], deck[from]);
+        }
+
+        #[test]
+        fn test_generated_mutations_are_permutations(seed: u64) {
+            let mut rng = Rand32::new(seed);
+            let mut deck = Deck::new_deck_order();
+            for _ in 0..32 {
+                deck = AdvancedMutation::generate(&mut rng, 0.3).apply(deck, &mut rng);
+            }
+            assert!(deck.is_valid_permutation());
+        }
+
+        #[test]
+        fn test_physical_mutations_are_permutations(seed: u64, split in 0usize..=52) {
+            let mut rng = Rand32::new(seed);
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let riffled = AdvancedMutation::Riffle(split).apply(deck, &mut rng);
+            prop_assert!(riffled.is_valid_permutation());
+            let mut deck = riffled;
+            for _ in 0..16 {
+                deck = AdvancedMutation::generate_with(&mut rng, 0.0, MutationPolicy::Physical)
+                    .apply(deck, &mut rng);
+            }
+            prop_assert!(deck.is_valid_permutation());
+        }
+
+        #[test]
+        fn test_pmx_crossover_is_a_permutation(seed1: u64, seed2: u64, seed3: u64) {
+            let parent1 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed1));
+            let parent2 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed2));
+            let child = Deck::pmx_crossover(&parent1, &parent2, &mut Rand32::new(seed3));
+            assert!(child.is_valid_permutation());
+        }
+
+        #[test]
+        fn test_cycle_crossover_is_a_permutation(seed1: u64, seed2: u64, seed3: u64) {
+            let parent1 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed1));
+            let parent2 = Deck::new_deck_order().shuffle(&mut Rand32::new(seed2));
+            let child = Deck::cycle_crossover(&parent1, &parent2, &mut Rand32::new(seed3));
+            assert!(child.is_valid_permutation());
+            for i in 0..52 {
+                assert!(child[i] == parent1[i] || child[i] == parent2[i]);
+            }
+        }
     }
 }
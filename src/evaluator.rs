@@ -0,0 +1,455 @@
+//! A "two plus two" style hand evaluator: a state machine walked one card at a time
+//! Every partial hand of up to six cards is a state with a transition for each of the 52 cards,
+//! and the transition out of a six-card state is the finished hand's rank. That's about 32M
+//! `u32`s, and no hand bytes or per-hand scores have to be stored
+
+use crate::cards::Card;
+use crate::hands::{HAND_SIZE, Hand, score_best_five};
+use crate::precompute::{TableEntry, TableError};
+use bytes::{Buf, BufMut, Bytes};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// Evaluator files start with this magic, then the format version, the number of transitions,
+/// and the number of ranks, all big-endian
+pub const EVALUATOR_MAGIC: [u8; 8] = *b"PKREVALS";
+const EVALUATOR_VERSION: u16 = 1;
+const HEADER_SIZE: usize = EVALUATOR_MAGIC.len() + 2 + 4 + 4;
+
+/// Where `build-evaluator` writes the evaluator unless told otherwise
+pub const DEFAULT_EVALUATOR_PATH: &str = "evaluator";
+
+/// The suit of a card whose suit can no longer make a flush, so only its rank matters
+const ANY_SUIT: u8 = 4;
+
+/// A card in a partial hand: its value less one (0 for an ace up to 12 for a king) and its
+/// suit, or `ANY_SUIT`
+type PartialCard = (u8, u8);
+
+pub struct Evaluator {
+    /// Row `state` holds the state each card leads to, or from a six-card state the rank
+    transitions: Box<[u32]>,
+    /// The score of each rank, best last; rank 0 is a hand that can't be dealt
+    ranks: Box<[TableEntry]>,
+}
+
+impl Evaluator {
+    /// The rank of a 7-card hand, in any order: hands with higher ranks win
+    pub fn rank7(&self, cards: &[Card; 7]) -> u32 {
+        cards.iter().fold(0, |state, card| {
+            self.transitions[state as usize * 52 + card.0 as usize]
+        })
+    }
+
+    /// `ScoreTable::score`, through the state machine
+    pub fn score(&self, hand: &Hand) -> TableEntry {
+        self.ranks[self.rank7(&hand.0) as usize]
+    }
+
+    /// Build the state machine, scoring each distinct finished hand once
+    /// Takes a while: there are about 600k states, and each six-card state ends 52 hands
+    pub fn build() -> Self {
+        let mut states: Vec<Vec<PartialCard>> = vec![Vec::new()];
+        let mut ids: HashMap<u64, u32> = HashMap::from([(state_key(&[]), 0)]);
+        let mut transitions: Vec<u32> = Vec::new();
+        // Finished hands are first numbered in the order they're seen, then sorted into ranks
+        let mut seen: HashMap<u32, u32> = HashMap::new();
+        let mut entries = vec![TableEntry::default()];
+
+        // States are appended in order of size, so this is a breadth-first walk
+        let mut next = 0;
+        while next < states.len() {
+            let state = states[next].clone();
+            for card in 0..52u8 {
+                let mut cards = state.clone();
+                cards.push((card % 13, card / 13));
+                canonicalize(&mut cards);
+                let target = if !is_possible(&cards) {
+                    // No real hand gets here, so don't spend a state on it
+                    0
+                } else if cards.len() == HAND_SIZE {
+                    match materialize(&cards) {
+                        Some(hand) => {
+                            let entry = score_best_five(&hand);
+                            *seen.entry(entry.pack()).or_insert_with(|| {
+                                entries.push(entry);
+                                entries.len() as u32 - 1
+                            })
+                        }
+                        None => 0,
+                    }
+                } else {
+                    *ids.entry(state_key(&cards)).or_insert_with(|| {
+                        states.push(cards);
+                        states.len() as u32 - 1
+                    })
+                };
+                transitions.push(target);
+            }
+            next += 1;
+        }
+
+        // Renumber the finished hands so that better hands have higher ranks
+        let mut order: Vec<u32> = (1..entries.len() as u32).collect();
+        order.sort_by_key(|&id| entries[id as usize]);
+        let mut rank_of = vec![0; entries.len()];
+        for (rank, &id) in order.iter().enumerate() {
+            rank_of[id as usize] = rank as u32 + 1;
+        }
+        for (state, row) in states.iter().zip(transitions.chunks_mut(52)) {
+            if state.len() == HAND_SIZE - 1 {
+                row.iter_mut()
+                    .for_each(|target| *target = rank_of[*target as usize]);
+            }
+        }
+        let ranks = std::iter::once(TableEntry::default())
+            .chain(order.iter().map(|&id| entries[id as usize]))
+            .collect();
+
+        Evaluator {
+            transitions: transitions.into_boxed_slice(),
+            ranks,
+        }
+    }
+
+    /// Serialize the transitions and ranks, so `read` can load them without rebuilding
+    pub fn write(&self, mut output: impl Write) -> std::io::Result<()> {
+        let size = HEADER_SIZE + 4 * (self.transitions.len() + self.ranks.len());
+        let mut bytes = Vec::with_capacity(size);
+        bytes.put_slice(&EVALUATOR_MAGIC);
+        bytes.put_u16(EVALUATOR_VERSION);
+        bytes.put_u32(self.transitions.len() as u32);
+        bytes.put_u32(self.ranks.len() as u32);
+        for &target in self.transitions.iter() {
+            bytes.put_u32(target);
+        }
+        for entry in self.ranks.iter() {
+            bytes.put_u32(entry.pack());
+        }
+        output.write_all(&bytes)
+    }
+
+    /// Load an evaluator written by `write`
+    pub fn read(mut input: impl Read) -> Result<Self, TableError> {
+        let mut v = Vec::new();
+        input.read_to_end(&mut v)?;
+        Self::parse(v)
+    }
+
+    /// `read`, from the whole file already in memory
+    pub fn parse(v: Vec<u8>) -> Result<Self, TableError> {
+        if v.len() < HEADER_SIZE {
+            return Err(TableError::Truncated {
+                expected: HEADER_SIZE,
+                actual: v.len(),
+            });
+        }
+        if !v.starts_with(&EVALUATOR_MAGIC) {
+            return Err(TableError::BadMagic);
+        }
+
+        let mut bs = Bytes::from_owner(v);
+        bs.advance(EVALUATOR_MAGIC.len());
+        let version = bs.get_u16();
+        if version != EVALUATOR_VERSION {
+            return Err(TableError::WrongVersion(version));
+        }
+        let num_transitions = bs.get_u32() as usize;
+        let num_ranks = bs.get_u32() as usize;
+        let expected = HEADER_SIZE + 4 * (num_transitions + num_ranks);
+        let actual = HEADER_SIZE + bs.len();
+        if actual < expected {
+            return Err(TableError::Truncated { expected, actual });
+        }
+        if actual > expected {
+            return Err(TableError::TrailingData { expected, actual });
+        }
+
+        let transitions = (0..num_transitions).map(|_| bs.get_u32()).collect();
+        let ranks = (0..num_ranks)
+            .map(|_| TableEntry::unpack(bs.get_u32()))
+            .collect();
+        Ok(Evaluator { transitions, ranks })
+    }
+}
+
+/// `Evaluator::read` from the file at `path`
+pub fn load_evaluator_file(path: &Path) -> Result<Evaluator, TableError> {
+    Evaluator::read(std::fs::File::open(path)?)
+}
+
+/// Forget the suits that can't make a flush any more and sort, so that partial hands
+/// that finish the same way share a state
+fn canonicalize(cards: &mut [PartialCard]) {
+    let remaining = HAND_SIZE - cards.len();
+    let mut counts = [0; 5];
+    for &(_, suit) in cards.iter() {
+        counts[suit as usize] += 1;
+    }
+    for card in cards.iter_mut() {
+        if card.1 != ANY_SUIT && counts[card.1 as usize] + remaining < 5 {
+            card.1 = ANY_SUIT;
+        }
+    }
+    cards.sort_unstable();
+}
+
+/// Whether some real hand canonicalizes to these cards: no card twice, and at most four of a rank
+fn is_possible(cards: &[PartialCard]) -> bool {
+    let mut ranks = [0; 13];
+    cards
+        .iter()
+        .for_each(|&(rank, _)| ranks[rank as usize] += 1);
+    let repeated = cards
+        .windows(2)
+        .any(|pair| pair[0] == pair[1] && pair[0].1 != ANY_SUIT);
+    ranks.iter().all(|&count| count <= 4) && !repeated
+}
+
+/// Pack a canonical partial hand into a key, 7 bits per card
+fn state_key(cards: &[PartialCard]) -> u64 {
+    cards.iter().fold(0, |key, &(rank, suit)| {
+        (key << 7) | (rank * 5 + suit + 1) as u64
+    })
+}
+
+/// Real cards for a canonical 7-card hand: any-suit cards get distinct suits spread so that
+/// none of them makes a flush. None if no real hand canonicalizes to these cards
+fn materialize(cards: &[PartialCard]) -> Option<[Card; 7]> {
+    let mut taken = [[false; 4]; 13];
+    let mut suit_counts = [0; 4];
+    for &(rank, suit) in cards.iter().filter(|card| card.1 != ANY_SUIT) {
+        if std::mem::replace(&mut taken[rank as usize][suit as usize], true) {
+            return None;
+        }
+        suit_counts[suit as usize] += 1;
+    }
+    // At most one suit has a flush, and its cards are the ones that kept their suit
+    let flush_suit = (0..4).find(|&suit| suit_counts[suit] >= 5);
+
+    let mut hand = [Card::default(); 7];
+    for (slot, &(rank, suit)) in hand.iter_mut().zip(cards) {
+        let suit = if suit == ANY_SUIT {
+            let suit = (0..4)
+                .filter(|&suit| Some(suit) != flush_suit && !taken[rank as usize][suit])
+                .min_by_key(|&suit| suit_counts[suit])?;
+            taken[rank as usize][suit] = true;
+            suit_counts[suit] += 1;
+            suit as u8
+        } else {
+            suit
+        };
+        *slot = Card(rank + 13 * suit);
+    }
+    debug_assert!(
+        (0..4).all(|suit| Some(suit) == flush_suit || suit_counts[suit] < 5),
+        "{cards:?} made a flush"
+    );
+    Some(hand)
+}
+
+/// Time `Evaluator::score` against `ScoreTable::score` on `samples` random hands,
+/// and check that they agree
+pub fn compare_throughput(
+    evaluator: &Evaluator,
+    table: &crate::precompute::ScoreTable,
+    samples: usize,
+    seed: u64,
+) {
+    let mut rng = oorandom::Rand32::new(seed);
+    let hands: Vec<Hand> = (0..samples)
+        .map(|_| {
+            let deck = crate::deck::Deck::new_deck_order().shuffle(&mut rng);
+            let mut cards: [Card; 7] = std::array::from_fn(|i| deck[i]);
+            cards.sort();
+            Hand(cards)
+        })
+        .collect();
+
+    let time = |name: &str, score: &dyn Fn(&Hand) -> TableEntry| {
+        let started = Instant::now();
+        let scores: Vec<TableEntry> = hands
+            .iter()
+            .map(|hand| std::hint::black_box(score(hand)))
+            .collect();
+        let elapsed = started.elapsed();
+        println!(
+            "{name:<10} {:>8.1}M hands/s  ({samples} hands in {:.2}s)",
+            samples as f64 / elapsed.as_secs_f64() / 1e6,
+            elapsed.as_secs_f64()
+        );
+        scores
+    };
+    let evaluated = time("evaluator", &|hand| evaluator.score(hand));
+    let looked_up = time("table", &|hand| table.score(hand));
+    for ((hand, evaluated), looked_up) in hands.iter().zip(evaluated).zip(looked_up) {
+        assert_eq!(
+            evaluated, looked_up,
+            "the evaluator and the table disagree on {hand:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn partial(cards: &[&str]) -> Vec<PartialCard> {
+        cards
+            .iter()
+            .map(|card| {
+                let card: Card = card.parse().unwrap();
+                (card.0 % 13, card.0 / 13)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn suits_that_cant_flush_are_forgotten() {
+        // Two hearts and two more cards to come can't make a flush
+        let mut cards = partial(&["Ah", "Kh", "Qs", "Js", "Ts"]);
+        canonicalize(&mut cards);
+        let spades = u8::from(crate::cards::Suit::Spades);
+        let expected = [
+            (0, ANY_SUIT),
+            (9, spades),
+            (10, spades),
+            (11, spades),
+            (12, ANY_SUIT),
+        ];
+        assert_eq!(cards, expected);
+
+        // Hands that differ only in suits that can't flush share a state
+        let mut other = partial(&["Ad", "Kc", "Qs", "Js", "Ts"]);
+        canonicalize(&mut other);
+        assert_eq!(state_key(&cards), state_key(&other));
+    }
+
+    #[test]
+    fn state_keys_are_order_independent() {
+        let mut a = partial(&["2c", "9d", "Ks"]);
+        let mut b = partial(&["Ks", "2c", "9d"]);
+        canonicalize(&mut a);
+        canonicalize(&mut b);
+        assert_eq!(state_key(&a), state_key(&b));
+        assert_ne!(state_key(&a[..2]), state_key(&a));
+    }
+
+    #[test]
+    fn materialized_hands_score_like_the_originals() {
+        let mut rng = oorandom::Rand32::new(58);
+        for _ in 0..2000 {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let cards: [Card; 7] = std::array::from_fn(|i| deck[i]);
+            let mut canonical: Vec<PartialCard> = cards
+                .iter()
+                .map(|card| (card.0 % 13, card.0 / 13))
+                .collect();
+            canonicalize(&mut canonical);
+            let hand = materialize(&canonical).unwrap();
+
+            let mut distinct = hand.to_vec();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), HAND_SIZE, "{hand:?}");
+            assert_eq!(score_best_five(&hand), score_best_five(&cards), "{cards:?}");
+        }
+    }
+
+    #[test]
+    fn impossible_hands_do_not_materialize() {
+        // Five aces
+        let mut cards = vec![(0, ANY_SUIT); 5];
+        cards.extend(partial(&["2c", "3d"]));
+        canonicalize(&mut cards);
+        assert_eq!(materialize(&cards), None);
+
+        // The same card twice
+        let mut cards = partial(&["Ah", "Ah", "Kh", "Qh", "Jh", "2c", "3d"]);
+        canonicalize(&mut cards);
+        assert_eq!(materialize(&cards), None);
+    }
+
+    #[test]
+    fn only_dealable_partial_hands_are_possible() {
+        let mut cards = partial(&["Ah", "Kh", "Qh", "2c"]);
+        canonicalize(&mut cards);
+        assert!(is_possible(&cards));
+
+        let mut cards = partial(&["Ah", "Ah", "Kh", "Qh"]);
+        canonicalize(&mut cards);
+        assert!(!is_possible(&cards));
+
+        let mut cards = vec![(0, ANY_SUIT); 5];
+        canonicalize(&mut cards);
+        assert!(!is_possible(&cards));
+    }
+
+    #[test]
+    fn write_then_read_roundtrips() {
+        let evaluator = Evaluator {
+            transitions: vec![1, 2, 0, 3].into_boxed_slice(),
+            ranks: vec![TableEntry::default(), TableEntry::unpack(0x1e_edcb9)].into_boxed_slice(),
+        };
+        let mut bytes = Vec::new();
+        evaluator.write(&mut bytes).unwrap();
+        let read = Evaluator::read(bytes.as_slice()).unwrap();
+        assert_eq!(read.transitions, evaluator.transitions);
+        assert_eq!(read.ranks, evaluator.ranks);
+
+        assert!(matches!(
+            Evaluator::read(&bytes[..bytes.len() - 1]),
+            Err(TableError::Truncated { .. })
+        ));
+        bytes[0] = b'X';
+        assert!(matches!(
+            Evaluator::read(bytes.as_slice()),
+            Err(TableError::BadMagic)
+        ));
+    }
+
+    #[test]
+    fn evaluator_files_load_as_score_tables() {
+        // Every card leads back to the start, so every hand finishes on rank 0
+        let entry = TableEntry::unpack(0x1e_edcb9);
+        let evaluator = Evaluator {
+            transitions: vec![0; 52].into_boxed_slice(),
+            ranks: vec![entry].into_boxed_slice(),
+        };
+        let mut bytes = Vec::new();
+        evaluator.write(&mut bytes).unwrap();
+
+        let table = crate::precompute::load_table(bytes.as_slice()).unwrap();
+        let mut rng = oorandom::Rand32::new(58);
+        for _ in 0..100 {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let mut cards: [Card; 7] = std::array::from_fn(|i| deck[i]);
+            cards.sort();
+            assert_eq!(table.score(&Hand(cards)), entry);
+        }
+    }
+
+    #[test]
+    #[ignore = "builds the full state machine; run with --release"]
+    fn built_evaluator_agrees_with_scoring() {
+        let evaluator = Evaluator::build();
+        let mut rng = oorandom::Rand32::new(7);
+        let mut previous: Option<([Card; 7], u32)> = None;
+        for _ in 0..200_000 {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let cards: [Card; 7] = std::array::from_fn(|i| deck[i]);
+            let rank = evaluator.rank7(&cards);
+            let entry = score_best_five(&cards);
+            assert_eq!(evaluator.ranks[rank as usize], entry, "{cards:?}");
+
+            // Ranks order hands the way their scores do
+            if let Some((other, other_rank)) = previous {
+                assert_eq!(rank.cmp(&other_rank), entry.cmp(&score_best_five(&other)));
+            }
+            previous = Some((cards, rank));
+        }
+    }
+}
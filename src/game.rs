@@ -1,116 +1,928 @@
+use crate::cards::*;
+use crate::combinations::combinations;
 use crate::deck::*;
 use crate::hands::*;
 use crate::precompute::*;
 
-const MAX_WINS: usize = 52;
+/// Cards a round of Hold'em uses: two hole cards per player, five on the board, and three burns
+pub fn cards_per_round(num_players: usize) -> usize {
+    2 * num_players + 5 + 3
+}
 
-pub fn max_wins(real: bool) -> usize {
-    if real { 52 - 10 } else { 52 }
+/// Why a round can't be dealt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DealError {
+    /// A round needs at least the dealer
+    NoPlayers,
+    /// The deck has fewer cards than a round needs
+    NotEnoughCards {
+        num_players: usize,
+        needed: usize,
+        available: usize,
+    },
+    /// Too few community cards for two hole cards to make a five-card hand with
+    BoardTooSmall { board_size: usize },
 }
 
-pub fn num_wins(num_players: usize, deck: &Deck, table: &ScoreTable, real: bool) -> usize {
-    if !real {
-        num_wins_total(num_players, deck, table)
+impl std::fmt::Display for DealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DealError::NoPlayers => write!(f, "a round needs at least one player"),
+            DealError::NotEnoughCards {
+                num_players,
+                needed,
+                available,
+            } => write!(
+                f,
+                "{num_players} players need {needed} cards but only {available} are dealable"
+            ),
+            DealError::BoardTooSmall { board_size } => write!(
+                f,
+                "a {board_size}-card board is too small, it needs at least {MIN_BOARD_SIZE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DealError {}
+
+/// Check up front that a deck of `deck_size` cards can deal a round to `num_players` the way
+/// `config` deals. Dealing itself doesn't check, and panics when it runs out of cards
+pub fn check_player_count(
+    num_players: usize,
+    deck_size: usize,
+    config: DealConfig,
+) -> Result<(), DealError> {
+    if num_players == 0 {
+        return Err(DealError::NoPlayers);
+    }
+    let needed = config.cards_per_round(num_players);
+    if needed > deck_size {
+        return Err(DealError::NotEnoughCards {
+            num_players,
+            needed,
+            available: deck_size,
+        });
+    }
+    Ok(())
+}
+
+/// The cut positions a deck is scored at
+/// Realistic cuts leave at least 5 cards in each pile. A cut only rotates the deck, so
+/// every position deals a complete round no matter how many players there are
+pub fn cut_positions(real: bool) -> std::ops::Range<usize> {
+    if real { 5..DECK_SIZE - 5 } else { 0..DECK_SIZE }
+}
+
+/// The most games the dealer can win: one per scored cut position,
+/// or none when the deck can't deal a round to this many players
+pub fn max_wins(num_players: usize, real: bool) -> usize {
+    if cards_per_round(num_players) > DECK_SIZE {
+        0
     } else {
-        num_realistic_wins(num_players, deck, table)
+        cut_positions(real).len()
+    }
+}
+
+pub fn num_wins(num_players: usize, deck: &Deck, table: &ScoreTable, real: bool) -> usize {
+    num_wins_with(num_players, deck, table, real, DealConfig::default())
+}
+
+/// `num_wins`, dealing each cut with the given procedure
+pub fn num_wins_with(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    config: DealConfig,
+) -> usize {
+    winning_positions_with(num_players, deck, table, real, config).count_ones() as usize
+}
+
+/// `num_wins`, counting the cuts `target_player` wins instead of the dealer
+pub fn num_wins_for(
+    num_players: usize,
+    target_player: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+) -> usize {
+    winning_positions_for(num_players, target_player, deck, table, real).count_ones() as usize
+}
+
+/// The cut positions the dealer wins at, as a mask: bit i is set iff the dealer wins at cut i
+/// Masks from different decks can be and-ed, xor-ed, etc. to compare which positions flip
+pub fn winning_positions(num_players: usize, deck: &Deck, table: &ScoreTable, real: bool) -> u64 {
+    winning_positions_with(num_players, deck, table, real, DealConfig::default())
+}
+
+/// How many of `decks` the dealer wins at each of the cut positions `real` scores
+/// Positions that stay low across many good decks are structurally hard to win
+/// Each cut is dealt with `config`
+pub fn position_win_frequencies(
+    num_players: usize,
+    table: &ScoreTable,
+    decks: &[Deck],
+    real: bool,
+    config: DealConfig,
+) -> [usize; DECK_SIZE] {
+    let mut counts = [0; DECK_SIZE];
+    for deck in decks {
+        let mask = winning_positions_with(num_players, deck, table, real, config);
+        for (cut_pos, count) in counts.iter_mut().enumerate() {
+            *count += (mask >> cut_pos & 1) as usize;
+        }
+    }
+    counts
+}
+
+/// How many of the cut positions `real` scores the dealer wins with each hand category,
+/// indexed by the category's `u8` value (so index 0 is always 0): whether a deck wins on
+/// flushes, straights, ... Each cut is dealt with `config`
+pub fn win_category_histogram(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    config: DealConfig,
+) -> [usize; 10] {
+    let mask = winning_positions_with(num_players, deck, table, real, config);
+    let mut counts = [0; 10];
+    for cut_pos in cut_positions(real).filter(|cut_pos| mask >> cut_pos & 1 == 1) {
+        let game = deal_a_round_with(num_players, deck.cut(cut_pos), config);
+        let dealer = game.players_score(0, table);
+        counts[u8::from(dealer.category()) as usize] += 1;
     }
+    counts
 }
 
-pub fn num_wins_total(num_players: usize, deck: &Deck, table: &ScoreTable) -> usize {
-    (0..52)
-        .filter(|cut_pos| dealer_wins_game(num_players, deck.clone().cut(*cut_pos), table))
-        .count()
+/// `winning_positions`, dealing each cut with the given procedure
+pub fn winning_positions_with(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    config: DealConfig,
+) -> u64 {
+    cut_positions(real)
+        .filter(|cut_pos| dealer_wins_game_with(num_players, deck.cut(*cut_pos), table, config))
+        .fold(0, |mask, cut_pos| mask | (1 << cut_pos))
 }
 
-pub fn num_realistic_wins(num_players: usize, deck: &Deck, table: &ScoreTable) -> usize {
-    (5..47)
-        .filter(|cut_pos| dealer_wins_game(num_players, deck.clone().cut(*cut_pos), table))
-        .count()
+/// `winning_positions`, for the cuts `target_player` wins outright instead of the dealer
+pub fn winning_positions_for(
+    num_players: usize,
+    target_player: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+) -> u64 {
+    let opponents = all_opponents(num_players, target_player);
+    let model = OpponentModel::Showdown;
+    let config = DealConfig::default();
+    winning_positions_vs(
+        num_players,
+        target_player,
+        &opponents,
+        deck,
+        table,
+        real,
+        model,
+        config,
+    )
+}
+
+/// The cut positions `target_player` beats every one of `opponents` that `model` keeps in
+/// The other seats are still dealt cards, but have folded and don't need to be beaten
+/// Each cut is dealt with `config`
+#[allow(clippy::too_many_arguments)]
+pub fn winning_positions_vs(
+    num_players: usize,
+    target_player: usize,
+    opponents: &[usize],
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    model: OpponentModel,
+    config: DealConfig,
+) -> u64 {
+    cut_positions(real)
+        .filter(|cut_pos| {
+            let cut_deck = deck.cut(*cut_pos);
+            player_wins_game_vs(
+                num_players,
+                target_player,
+                opponents,
+                cut_deck,
+                table,
+                model,
+                config,
+            )
+        })
+        .fold(0, |mask, cut_pos| mask | (1 << cut_pos))
+}
+
+/// Count the cuts `real` scores that the dealer wins when only the `active` opponents stay in
+/// the hand. With no active opponents the dealer wins every cut
+pub fn num_wins_vs(
+    num_players: usize,
+    active: &[usize],
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+) -> usize {
+    let (model, config) = (OpponentModel::Showdown, DealConfig::default());
+    winning_positions_vs(num_players, 0, active, deck, table, real, model, config).count_ones()
+        as usize
+}
+
+/// Every player but `target_player`: the ones it must beat to win outright
+pub fn all_opponents(num_players: usize, target_player: usize) -> Vec<usize> {
+    (0..num_players)
+        .filter(|&idx| idx != target_player)
+        .collect()
+}
+
+/// Update a deck's `winning_positions` mask after the cards at two positions are swapped
+/// `deck` is the deck after the swap and `prev_mask` its mask before, and `wins_game` says
+/// whether a cut deck (dealt with `config`) is a win. Only the cuts that deal one of the
+/// swapped positions (as any card, burns included) can change, so only those are replayed,
+/// which is much cheaper than a full recompute for local search
+pub fn num_wins_delta(
+    num_players: usize,
+    deck: &Deck,
+    swapped: (usize, usize),
+    prev_mask: u64,
+    real: bool,
+    config: DealConfig,
+    wins_game: impl Fn(Deck) -> bool,
+) -> u64 {
+    let dealt = config.cards_per_round(num_players);
+    let len = deck.len();
+    // Cards are drawn from the end, so a cut deals the `dealt` positions just before it
+    let deals = |pos: usize, cut_pos: usize| (cut_pos + len - pos - 1) % len < dealt;
+    cut_positions(real)
+        .filter(|&cut_pos| deals(swapped.0, cut_pos) || deals(swapped.1, cut_pos))
+        .fold(prev_mask, |mask, cut_pos| {
+            if wins_game(deck.cut(cut_pos)) {
+                mask | (1 << cut_pos)
+            } else {
+                mask & !(1 << cut_pos)
+            }
+        })
+}
+
+/// Count dealer wins over every cut of a 36-card short deck, under short-deck rules
+pub fn num_wins_short_deck(num_players: usize, deck: &Deck, table: &ScoreTable) -> usize {
+    let config = DealConfig::default();
+    winning_positions_short_deck(num_players, deck, table, config).count_ones() as usize
+}
+
+/// The cuts of a 36-card short deck the dealer wins at under short-deck rules, as a mask like
+/// `winning_positions`'s over every cut position. Each cut is dealt with `config`'s burns
+pub fn winning_positions_short_deck(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    config: DealConfig,
+) -> u64 {
+    assert_eq!(deck.len(), SHORT_DECK_SIZE);
+    let config = config.with_variant(GameVariant::ShortDeck);
+    (0..SHORT_DECK_SIZE)
+        .filter(|cut_pos| dealer_wins_game_with(num_players, deck.cut(*cut_pos), table, config))
+        .fold(0, |mask, cut_pos| mask | (1 << cut_pos))
 }
 
 pub fn dealer_wins_game(num_players: usize, deck: Deck, table: &ScoreTable) -> bool {
-    deal_a_round(num_players, deck).dealer_wins(table)
+    dealer_wins_game_with(num_players, deck, table, DealConfig::default())
+}
+
+pub fn dealer_wins_game_with(
+    num_players: usize,
+    deck: Deck,
+    table: &ScoreTable,
+    config: DealConfig,
+) -> bool {
+    player_wins_game_with(num_players, 0, deck, table, config)
+}
+
+/// Whether `target_player` beats every other player outright; a tie at the top is nobody's win
+pub fn player_wins_game_with(
+    num_players: usize,
+    target_player: usize,
+    deck: Deck,
+    table: &ScoreTable,
+    config: DealConfig,
+) -> bool {
+    deal_a_round_with(num_players, deck, config).player_wins_outright(target_player, table)
+}
+
+/// Whether `target_player` beats every one of `opponents` that `model` keeps in the hand,
+/// ignoring the other seats. If they all fold, the target wins without a showdown
+pub fn player_wins_game_vs(
+    num_players: usize,
+    target_player: usize,
+    opponents: &[usize],
+    deck: Deck,
+    table: &ScoreTable,
+    model: OpponentModel,
+    config: DealConfig,
+) -> bool {
+    let game = deal_a_round_with(num_players, deck, config);
+    game.player_beats(target_player, game.staying(opponents, model), table)
 }
 
+/// What one win is worth in a hybrid score, far more than any total margin
+pub const WIN_WEIGHT: f64 = 100_000.0;
+
 /// Hybrid scoring function that combines win count with margin of victory
 /// Returns: (num_wins * WIN_WEIGHT) + total_margin
 /// This provides a smooth gradient for optimization while prioritizing wins
 pub fn hybrid_score(num_players: usize, deck: &Deck, table: &ScoreTable, real: bool) -> f64 {
-    const WIN_WEIGHT: f64 = 100_000.0; // One win is worth 100k points
+    hybrid_score_for(num_players, 0, deck, table, real)
+}
 
-    let positions: Vec<usize> = if real {
-        (5..47).collect()
-    } else {
-        (0..52).collect()
-    };
+/// `hybrid_score`, counting wins and margins for `target_player` instead of the dealer
+pub fn hybrid_score_for(
+    num_players: usize,
+    target_player: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+) -> f64 {
+    let opponents = all_opponents(num_players, target_player);
+    let model = OpponentModel::Showdown;
+    let config = DealConfig::default();
+    hybrid_score_vs(
+        num_players,
+        target_player,
+        &opponents,
+        deck,
+        table,
+        real,
+        model,
+        config,
+    )
+}
+
+/// `hybrid_score_for`, where `target_player` only has to beat the `opponents` `model` keeps in,
+/// dealing each cut with `config`
+#[allow(clippy::too_many_arguments)]
+pub fn hybrid_score_vs(
+    num_players: usize,
+    target_player: usize,
+    opponents: &[usize],
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    model: OpponentModel,
+    config: DealConfig,
+) -> f64 {
+    let (num_wins, total_margin) = wins_and_margin_vs(
+        num_players,
+        target_player,
+        opponents,
+        deck,
+        table,
+        real,
+        model,
+        config,
+    );
+
+    // Hybrid score: heavily weight wins, but use margins as tiebreaker/gradient
+    (num_wins as f64) * WIN_WEIGHT + total_margin
+}
+
+/// The two parts `hybrid_score_vs` combines: the wins, and the total margin over every
+/// scored cut position (positive where `target_player` wins)
+#[allow(clippy::too_many_arguments)]
+pub fn wins_and_margin_vs(
+    num_players: usize,
+    target_player: usize,
+    opponents: &[usize],
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    model: OpponentModel,
+    config: DealConfig,
+) -> (usize, f64) {
+    const MARGIN_SCALE: f64 = 4096.0; // to_score packs 3 kicker nibbles below the old rank*256 + hi scale
+
+    // Margins are positive where the target player wins
+    let margins: Vec<i32> = cut_positions(real)
+        .map(|cut_pos| {
+            position_margin_vs(
+                num_players,
+                target_player,
+                opponents,
+                deck,
+                cut_pos,
+                table,
+                model,
+                config,
+            )
+        })
+        .collect();
+    let num_wins = margins.iter().filter(|&&margin| margin > 0).count();
+    let total_margin: f64 = margins
+        .iter()
+        .map(|&margin| margin as f64 / MARGIN_SCALE)
+        .sum();
+    (num_wins, total_margin)
+}
+
+/// The signed `position_margin_for` `target_player` at every cut position, in `cut_positions`
+/// order. Positive entries are wins; small negative ones are narrow losses one swap might flip
+/// Each cut is dealt with `config`
+pub fn margin_profile(
+    num_players: usize,
+    target_player: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    config: DealConfig,
+) -> Vec<i32> {
+    let opponents = all_opponents(num_players, target_player);
+    let model = OpponentModel::Showdown;
+    cut_positions(real)
+        .map(|cut_pos| {
+            position_margin_vs(
+                num_players,
+                target_player,
+                &opponents,
+                deck,
+                cut_pos,
+                table,
+                model,
+                config,
+            )
+        })
+        .collect()
+}
+
+/// Get just the margin component for a single cut position
+pub fn position_margin(num_players: usize, deck: &Deck, cut_pos: usize, table: &ScoreTable) -> i32 {
+    position_margin_for(num_players, 0, deck, cut_pos, table)
+}
+
+/// The cut position that gives the dealer the biggest `position_margin`, and that margin
+/// Only the positions `real` scores are considered, and ties go to the earliest one
+pub fn best_cut_for_dealer(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+) -> (usize, i32) {
+    cut_positions(real)
+        .map(|cut_pos| (cut_pos, position_margin(num_players, deck, cut_pos, table)))
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .expect("a deck has at least one cut position")
+}
+
+/// How far `target_player`'s hand is ahead of the best other hand at a cut position
+pub fn position_margin_for(
+    num_players: usize,
+    target_player: usize,
+    deck: &Deck,
+    cut_pos: usize,
+    table: &ScoreTable,
+) -> i32 {
+    let opponents = all_opponents(num_players, target_player);
+    let (model, config) = (OpponentModel::Showdown, DealConfig::default());
+    position_margin_vs(
+        num_players,
+        target_player,
+        &opponents,
+        deck,
+        cut_pos,
+        table,
+        model,
+        config,
+    )
+}
+
+/// How far `target_player`'s hand is ahead of the best of the `opponents` `model` keeps in,
+/// at a cut position dealt with `config`
+#[allow(clippy::too_many_arguments)]
+pub fn position_margin_vs(
+    num_players: usize,
+    target_player: usize,
+    opponents: &[usize],
+    deck: &Deck,
+    cut_pos: usize,
+    table: &ScoreTable,
+    model: OpponentModel,
+    config: DealConfig,
+) -> i32 {
+    let cut_deck = deck.cut(cut_pos);
+    let game = deal_a_round_with(num_players, cut_deck, config);
+
+    let target_score = game.players_score(target_player, table);
+    let best_opponent_score = game
+        .staying(opponents, model)
+        .map(|idx| game.margin_score(&game.players_score(idx, table)))
+        .max();
+
+    match best_opponent_score {
+        Some(best) => game.margin_score(&target_score) - best,
+        // With no opponents (or none left in) the target wins by its whole score
+        None => target_score.to_score(),
+    }
+}
 
-    let mut num_wins = 0;
-    let mut total_margin = 0.0;
+/// What happens at one cut position, for seeing where a deck leaks wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionSummary {
+    pub cut_pos: usize,
+    pub dealer: TableEntry,
+    /// The five cards the dealer's hand is made with
+    pub dealer_cards: [Card; 5],
+    /// `None` when the dealer plays alone
+    pub best_opponent: Option<HandCategory>,
+    /// See `position_margin`
+    pub margin: i32,
+    pub dealer_wins: bool,
+    /// The dealer ties for the best hand, so the pot is split; never a win
+    pub splits_pot: bool,
+}
 
-    for cut_pos in positions {
-        let cut_deck = deck.clone().cut(cut_pos);
-        let game = deal_a_round(num_players, cut_deck);
+pub fn position_summary(
+    num_players: usize,
+    deck: &Deck,
+    cut_pos: usize,
+    table: &ScoreTable,
+) -> PositionSummary {
+    let game = deal_a_round(num_players, deck.cut(cut_pos));
+    let scored = |idx| table.scored(game.table_hand(idx).expect("deal_a_round deals Hold'em"));
+    let dealer = scored(0);
+    let best_opponent = (1..num_players).map(scored).max();
+    let split = game.split_pot_winners(table);
 
-        // Get player 0's score
-        let p0_score = game.players_score(0, table);
+    PositionSummary {
+        cut_pos,
+        dealer: dealer.entry,
+        dealer_cards: dealer.hand.best_five().0,
+        best_opponent: best_opponent.map(|opponent| opponent.entry.category()),
+        margin: position_margin(num_players, deck, cut_pos, table),
+        dealer_wins: game.dealer_wins_outright(table),
+        splits_pot: split.len() > 1 && split.contains(&0),
+    }
+}
 
-        // Get best opponent's score
-        let best_opponent_score = (1..num_players)
-            .map(|idx| game.players_score(idx, table))
-            .max()
-            .unwrap();
+/// Dealer's equity at a single cut position against the dealt opponents
+/// Holds every player's hole cards fixed and enumerates every board that could
+/// come from the unseen cards, returning the fraction the dealer wins outright.
+///
+/// This is exhaustive and expensive: C(52 - 2n, 5) boards, each scored n times
+/// (1,712,304 boards for 2 players, 1,086,008 for 6), so keep it off the search path.
+pub fn position_equity(num_players: usize, deck: &Deck, cut_pos: usize, table: &ScoreTable) -> f64 {
+    let mut game = deal_a_round(num_players, deck.cut(cut_pos));
+    let unseen = game.unseen_cards();
+    game.board_equity(&unseen, table)
+}
 
-        // Calculate margin (positive if player 0 wins)
-        let margin = p0_score.to_score() - best_opponent_score.to_score();
+/// Classic preflop all-in equity between two Hold'em hands, independent of any deck order
+/// Enumerates every board from the other 48 cards (C(48, 5) = 1,712,304 of them) and returns
+/// the fractions of boards that `p0` wins, ties, and loses
+pub fn heads_up_equity(p0: Player, p1: Player, table: &ScoreTable) -> (f64, f64, f64) {
+    let mut game = Game {
+        players: vec![p0.into(), p1.into()],
+        common: Common::default(),
+        variant: GameVariant::Holdem,
+    };
+    let unseen = game.unseen_cards();
+    game.heads_up_board_equity(&unseen, table)
+}
 
-        if margin > 0 {
-            num_wins += 1;
+/// Which poker game a round is dealt and scored as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GameVariant {
+    /// Texas Hold'em: two hole cards, best five of seven
+    #[default]
+    Holdem,
+    /// Omaha: four hole cards, exactly two of them plus exactly three community cards
+    Omaha,
+    /// Short-deck (6+) Hold'em: 36 cards, A-6-7-8-9 is a straight, a flush beats a full house
+    ShortDeck,
+    /// A-5 lowball Hold'em: the lowest hand wins, ignoring straights and flushes
+    Lowball,
+}
+
+impl GameVariant {
+    /// The hole cards each player is dealt
+    pub fn hole_cards(self) -> usize {
+        match self {
+            GameVariant::Omaha => 4,
+            GameVariant::Holdem | GameVariant::ShortDeck | GameVariant::Lowball => 2,
         }
+    }
+}
 
-        total_margin += margin as f64;
+/// How opponents decide whether to play their hole cards to showdown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpponentModel {
+    /// Every opponent plays every hand to showdown
+    #[default]
+    Showdown,
+    /// An opponent folds unpaired hole cards whose highest card's `rank_value` is below this,
+    /// e.g. 10 folds 9-7 but plays T-2 and 2-2
+    FoldsBelow(u8),
+}
+
+impl OpponentModel {
+    /// Whether an opponent holding `hole` stays in to showdown
+    pub fn plays(&self, hole: &[Card]) -> bool {
+        match *self {
+            OpponentModel::Showdown => true,
+            OpponentModel::FoldsBelow(rank) => {
+                let paired = hole.iter().enumerate().any(|(i, a)| {
+                    hole[i + 1..]
+                        .iter()
+                        .any(|b| a.rank_value() == b.rank_value())
+                });
+                paired || hole.iter().any(|card| card.rank_value() >= rank)
+            }
+        }
     }
+}
 
-    // Hybrid score: heavily weight wins, but use margins as tiebreaker/gradient
-    (num_wins as f64) * WIN_WEIGHT + total_margin
+/// Which game is dealt, and how the dealer deals the board: which burn cards are discarded,
+/// and how many community cards
+/// The default is the standard procedure for Hold'em: one burn before each of the flop, turn,
+/// and river, and a five-card board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DealConfig {
+    pub variant: GameVariant,
+    pub burn_before_flop: bool,
+    pub burn_before_turn: bool,
+    pub burn_before_river: bool,
+    /// Community cards dealt: 3 skips the turn and river, more than 5 extends the river
+    /// Set through `with_board_size`, which keeps it at least `MIN_BOARD_SIZE`
+    board_size: usize,
 }
 
-/// Get just the margin component for a single cut position
-pub fn position_margin(num_players: usize, deck: &Deck, cut_pos: usize, table: &ScoreTable) -> i32 {
-    let cut_deck = deck.clone().cut(cut_pos);
-    let game = deal_a_round(num_players, cut_deck);
+/// The smallest board a round can be dealt with: two hole cards and the flop make five
+pub const MIN_BOARD_SIZE: usize = 3;
 
-    let p0_score = game.players_score(0, table);
-    let best_opponent_score = (1..num_players)
-        .map(|idx| game.players_score(idx, table))
-        .max()
-        .unwrap();
+impl DealConfig {
+    /// This procedure, dealing a `board_size`-card board
+    pub fn with_board_size(self, board_size: usize) -> Result<Self, DealError> {
+        if board_size < MIN_BOARD_SIZE {
+            return Err(DealError::BoardTooSmall { board_size });
+        }
+        Ok(Self { board_size, ..self })
+    }
+
+    /// This procedure, dealing a round of `variant`
+    pub fn with_variant(self, variant: GameVariant) -> Self {
+        Self { variant, ..self }
+    }
+
+    /// Cards a round for `num_players` uses: the hole cards, the board, and the burns
+    pub fn cards_per_round(&self, num_players: usize) -> usize {
+        let burns = [
+            self.burn_before_flop,
+            self.burn_before_turn,
+            self.burn_before_river,
+        ];
+        let burns = match self.board_size {
+            3 => &burns[..1],
+            4 => &burns[..2],
+            _ => &burns[..],
+        };
+        self.variant.hole_cards() * num_players
+            + self.board_size
+            + burns.iter().filter(|&&burn| burn).count()
+    }
 
-    p0_score.to_score() - best_opponent_score.to_score()
+    /// Deal the board straight off the deck, with no burns
+    pub fn no_burns() -> Self {
+        Self {
+            variant: GameVariant::Holdem,
+            burn_before_flop: false,
+            burn_before_turn: false,
+            burn_before_river: false,
+            board_size: BOARD_SIZE,
+        }
+    }
 }
 
-struct Game {
-    players: Vec<Player>,
+impl Default for DealConfig {
+    fn default() -> Self {
+        Self {
+            variant: GameVariant::Holdem,
+            burn_before_flop: true,
+            burn_before_turn: true,
+            burn_before_river: true,
+            board_size: BOARD_SIZE,
+        }
+    }
+}
+
+pub struct Game {
+    players: Vec<HoleCards>,
     common: Common,
+    variant: GameVariant,
 }
 
 impl Game {
-    pub fn dealer_wins(&self, table: &ScoreTable) -> bool {
-        self.winning_player(table) == 0
+    /// Does the dealer take the whole pot?
+    /// A chopped pot (any opponent with an equal hand) is not a win
+    pub fn dealer_wins_outright(&self, table: &ScoreTable) -> bool {
+        self.player_wins_outright(0, table)
+    }
+
+    /// Whether player `target` has a strictly better hand than every other player
+    pub fn player_wins_outright(&self, target: usize, table: &ScoreTable) -> bool {
+        let others = (0..self.players.len()).filter(|&idx| idx != target);
+        self.player_beats(target, others, table)
+    }
+
+    /// Whether player `target` has a strictly better hand than each of `opponents`
+    pub fn player_beats(
+        &self,
+        target: usize,
+        opponents: impl IntoIterator<Item = usize>,
+        table: &ScoreTable,
+    ) -> bool {
+        let target_score = self.players_score(target, table);
+        opponents.into_iter().all(|idx| {
+            self.compare(&self.players_score(idx, table), &target_score)
+                .is_lt()
+        })
+    }
+
+    /// The seats of `opponents` that `model` keeps in the hand
+    pub fn staying<'a>(
+        &'a self,
+        opponents: &'a [usize],
+        model: OpponentModel,
+    ) -> impl Iterator<Item = usize> + 'a {
+        opponents
+            .iter()
+            .copied()
+            .filter(move |&idx| model.plays(self.hole_cards(idx)))
+    }
+
+    /// The hole cards dealt to seat `idx`
+    fn hole_cards(&self, idx: usize) -> &[Card] {
+        match &self.players[idx] {
+            HoleCards::Holdem(p) => &p.0,
+            HoleCards::Omaha(p) => &p.0,
+        }
+    }
+
+    /// Seat `idx`'s 7-card hand, when it is scored straight from the table:
+    /// Hold'em with a full board, where the table's order is the game's
+    fn table_hand(&self, idx: usize) -> Option<Hand> {
+        match &self.players[idx] {
+            HoleCards::Holdem(p)
+                if self.variant == GameVariant::Holdem && self.common.0.len() == BOARD_SIZE =>
+            {
+                Some(self.holdem_hand(p))
+            }
+            _ => None,
+        }
+    }
+
+    /// Every seat with its score, best hand first
+    /// Tied seats are adjacent, in seat order, so split pots can be read off the front
+    pub fn showdown(&self, table: &ScoreTable) -> Vec<(usize, TableEntry)> {
+        let mut order: Vec<_> = self.players_scores(table).into_iter().enumerate().collect();
+        order.sort_by(|(_, a), (_, b)| self.compare(b, a));
+        order
+    }
+
+    /// Every seat tied for the best hand, in seat order
+    /// More than one seat means the pot is split
+    pub fn split_pot_winners(&self, table: &ScoreTable) -> Vec<usize> {
+        let showdown = self.showdown(table);
+        let best = showdown[0].1;
+        showdown
+            .into_iter()
+            .take_while(|(_, score)| self.compare(score, &best).is_eq())
+            .map(|(seat, _)| seat)
+            .collect()
+    }
+
+    /// Compare two scores under this game's hand ordering
+    fn compare(&self, a: &TableEntry, b: &TableEntry) -> std::cmp::Ordering {
+        match self.variant {
+            GameVariant::ShortDeck => a.cmp_short_deck(b),
+            GameVariant::Lowball => a.cmp_lowball(b),
+            GameVariant::Holdem | GameVariant::Omaha => a.cmp(b),
+        }
     }
 
-    pub fn winning_player(&self, table: &ScoreTable) -> usize {
-        let winner = (0..self.players.len())
-            .max_by_key(|idx| self.players_score(*idx, table))
-            .unwrap();
-        winner
+    /// `to_score`, negated for lowball so the better hand always has the higher number
+    fn margin_score(&self, score: &TableEntry) -> i32 {
+        match self.variant {
+            GameVariant::Lowball => -score.to_score(),
+            _ => score.to_score(),
+        }
+    }
+
+    /// Every seat's `players_score`, in seat order
+    /// Table-scored hands are looked up as one batch
+    pub fn players_scores(&self, table: &ScoreTable) -> Vec<TableEntry> {
+        let hands: Option<Vec<Hand>> = (0..self.players.len())
+            .map(|idx| self.table_hand(idx))
+            .collect();
+        match hands {
+            Some(hands) => table.score_many(&hands).collect(),
+            None => (0..self.players.len())
+                .map(|idx| self.players_score(idx, table))
+                .collect(),
+        }
     }
 
     pub fn players_score(&self, idx: usize, table: &ScoreTable) -> TableEntry {
-        let p = &self.players[idx];
+        match &self.players[idx] {
+            // Short-deck rules differ from the precomputed table, so score directly
+            HoleCards::Holdem(p) if self.variant == GameVariant::ShortDeck => best_five_of(
+                &self.holdem_cards(p),
+                score_five_cards_short,
+                TableEntry::cmp_short_deck,
+            ),
+            // The precomputed table only holds high hands
+            HoleCards::Holdem(p) if self.variant == GameVariant::Lowball => best_five_of(
+                &self.holdem_cards(p),
+                score_five_cards_lowball,
+                TableEntry::cmp_lowball,
+            ),
+            // The precomputed table only holds 7-card hands
+            HoleCards::Holdem(p) if self.common.0.len() != BOARD_SIZE => {
+                score_best_five(&self.holdem_cards(p))
+            }
+            // With 7-card precomputation, we directly look up the score
+            // for the player's 2 hole cards + 5 community cards
+            HoleCards::Holdem(p) => table.score(&self.holdem_hand(p)),
+            HoleCards::Omaha(p) => p.score(&self.common),
+        }
+    }
+
+    /// Every card of a full deck not held by some player
+    fn unseen_cards(&self) -> Vec<Card> {
+        let held: Vec<Card> = self
+            .players
+            .iter()
+            .flat_map(|hole| match hole {
+                HoleCards::Holdem(p) => p.0.to_vec(),
+                HoleCards::Omaha(p) => p.0.to_vec(),
+            })
+            .collect();
+        Deck::new_deck_order()
+            .iter()
+            .copied()
+            .filter(|c| !held.contains(c))
+            .collect()
+    }
+
+    /// Replace the board with every 5-card combination of `unseen`,
+    /// returning the fraction of boards the dealer wins outright
+    fn board_equity(&mut self, unseen: &[Card], table: &ScoreTable) -> f64 {
+        let mut boards = 0usize;
+        let mut wins = 0usize;
+        for idx in combinations::<BOARD_SIZE>(unseen.len()) {
+            self.common.0.clear();
+            self.common.0.extend(idx.map(|i| unseen[i]));
+            boards += 1;
+            if self.dealer_wins_outright(table) {
+                wins += 1;
+            }
+        }
+        wins as f64 / boards as f64
+    }
 
-        // With 7-card precomputation, we directly look up the score
-        // for the player's 2 hole cards + 5 community cards
-        let mut cards = [
+    /// Replace the board with every 5-card combination of `unseen`, returning the
+    /// fractions of boards that seat 0 wins, ties, and loses against seat 1
+    fn heads_up_board_equity(&mut self, unseen: &[Card], table: &ScoreTable) -> (f64, f64, f64) {
+        let mut boards = 0usize;
+        let (mut wins, mut ties) = (0usize, 0usize);
+        for idx in combinations::<BOARD_SIZE>(unseen.len()) {
+            self.common.0.clear();
+            self.common.0.extend(idx.map(|i| unseen[i]));
+            boards += 1;
+            let hands = [0, 1].map(|seat| self.table_hand(seat).expect("heads-up Hold'em"));
+            match table.compare(&hands[0], &hands[1]) {
+                std::cmp::Ordering::Greater => wins += 1,
+                std::cmp::Ordering::Equal => ties += 1,
+                std::cmp::Ordering::Less => {}
+            }
+        }
+        let losses = boards - wins - ties;
+        let fraction = |n: usize| n as f64 / boards as f64;
+        (fraction(wins), fraction(ties), fraction(losses))
+    }
+
+    /// A Hold'em player's hole cards and the board, for boards of any size
+    fn holdem_cards(&self, p: &Player) -> Vec<Card> {
+        p.0.iter().chain(&self.common.0).copied().collect()
+    }
+
+    /// The sorted 7-card hand (hole cards + community cards) of a Hold'em player
+    fn holdem_hand(&self, p: &Player) -> Hand {
+        Hand::from_cards([
             p.0[0],
             p.0[1],
             self.common.0[0],
@@ -118,38 +930,156 @@ impl Game {
             self.common.0[2],
             self.common.0[3],
             self.common.0[4],
-        ];
-        cards.sort();
-        let hand = Hand(cards);
-        table.score(&hand)
+        ])
+        .expect("a dealt game never repeats a card")
     }
 }
 
-pub fn deal_a_round(num_players: usize, mut deck: Deck) -> Game {
-    let mut players = vec![Player::default(); num_players];
-    let mut common = Common::default();
-    for hand_idx in 0..2 {
-        for p in 0..num_players {
-            let card = deck.draw();
-            players[p].0[hand_idx] = card;
+pub fn deal_a_round(num_players: usize, deck: Deck) -> Game {
+    deal_a_round_variant(num_players, deck, GameVariant::Holdem)
+}
+
+/// Deal a round of the given variant: hole cards round-robin, then the board
+pub fn deal_a_round_variant(num_players: usize, deck: Deck, variant: GameVariant) -> Game {
+    deal_a_round_with(
+        num_players,
+        deck,
+        DealConfig::default().with_variant(variant),
+    )
+}
+
+/// Deal a round of the variant `config` says, burning cards as it says
+pub fn deal_a_round_with(num_players: usize, deck: Deck, config: DealConfig) -> Game {
+    deal_recording(num_players, deck, config, |_, _| {})
+}
+
+/// Where a dealt card went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealDestination {
+    /// The `card`th hole card of seat `player`
+    Hole {
+        player: usize,
+        card: usize,
+    },
+    Burn,
+    /// The `n`th community card
+    Board(usize),
+}
+
+impl std::fmt::Display for DealDestination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DealDestination::Hole { player, card } => write!(f, "player {player} hole {card}"),
+            DealDestination::Burn => write!(f, "burn"),
+            DealDestination::Board(n) => write!(f, "board {n}"),
+        }
+    }
+}
+
+/// Every card a round used, in the order it was dealt, and where it went
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DealTrace(pub Vec<(Card, DealDestination)>);
+
+/// One card per line, e.g. "7♣ -> burn"
+impl std::fmt::Display for DealTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (card, destination) in &self.0 {
+            writeln!(f, "{card} -> {destination}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `deal_a_round`, also recording where each card went
+pub fn deal_a_round_traced(num_players: usize, deck: Deck) -> (Game, DealTrace) {
+    deal_a_round_traced_with(num_players, deck, DealConfig::default())
+}
+
+/// `deal_a_round_with`, also recording where each card went
+pub fn deal_a_round_traced_with(
+    num_players: usize,
+    deck: Deck,
+    config: DealConfig,
+) -> (Game, DealTrace) {
+    let mut trace = DealTrace::default();
+    let game = deal_recording(num_players, deck, config, |card, destination| {
+        trace.0.push((card, destination))
+    });
+    (game, trace)
+}
+
+/// Deal a round, telling `record` about every card as it comes off the deck
+fn deal_recording(
+    num_players: usize,
+    mut deck: Deck,
+    config: DealConfig,
+    mut record: impl FnMut(Card, DealDestination),
+) -> Game {
+    let mut draw = |destination| {
+        let card = deck.draw();
+        record(card, destination);
+        card
+    };
+    let variant = config.variant;
+    let players = match variant {
+        GameVariant::Holdem | GameVariant::ShortDeck | GameVariant::Lowball => {
+            let mut players = vec![Player::default(); num_players];
+            for hand_idx in 0..2 {
+                for (player, p) in players.iter_mut().enumerate() {
+                    p.0[hand_idx] = draw(DealDestination::Hole {
+                        player,
+                        card: hand_idx,
+                    });
+                }
+            }
+            players.into_iter().map(HoleCards::from).collect()
+        }
+        GameVariant::Omaha => {
+            let mut players = vec![OmahaPlayer::default(); num_players];
+            for hand_idx in 0..4 {
+                for (player, p) in players.iter_mut().enumerate() {
+                    p.0[hand_idx] = draw(DealDestination::Hole {
+                        player,
+                        card: hand_idx,
+                    });
+                }
+            }
+            players.into_iter().map(HoleCards::from).collect()
+        }
+    };
+    let mut common = Common(Vec::with_capacity(config.board_size));
+    if config.burn_before_flop {
+        draw(DealDestination::Burn);
+    }
+    for _ in 0..config.board_size.min(3) {
+        common.0.push(draw(DealDestination::Board(common.0.len())));
+    }
+    if config.board_size > 3 {
+        if config.burn_before_turn {
+            draw(DealDestination::Burn);
+        }
+        common.0.push(draw(DealDestination::Board(common.0.len())));
+    }
+    if config.board_size > 4 {
+        if config.burn_before_river {
+            draw(DealDestination::Burn);
+        }
+        // Any cards past the river are dealt straight after it
+        for _ in 4..config.board_size {
+            common.0.push(draw(DealDestination::Board(common.0.len())));
         }
     }
-    let _burn = deck.draw();
-    common.0[0] = deck.draw();
-    common.0[1] = deck.draw();
-    common.0[2] = deck.draw();
-    let _burn = deck.draw();
-    common.0[3] = deck.draw();
-    let _burn = deck.draw();
-    common.0[4] = deck.draw();
 
-    Game { players, common }
+    Game {
+        players,
+        common,
+        variant,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cards::*;
     use crate::precompute::load_table;
     use std::fs::File;
 
@@ -184,12 +1114,13 @@ mod tests {
         let player1 = Player([card_kh, card_6d]);
 
         // Create common cards
-        let common = Common([card_4c, card_3d, card_7s, card_5c, card_js]);
+        let common = Common(vec![card_4c, card_3d, card_7s, card_5c, card_js]);
 
         // Create game
         let game = Game {
-            players: vec![player0, player1],
+            players: vec![player0.into(), player1.into()],
             common,
+            variant: GameVariant::Holdem,
         };
 
         // Load the precomputed score table
@@ -213,12 +1144,12 @@ mod tests {
         assert_eq!(p0_score.rank, 2, "Player 0 should have a pair");
         assert_eq!(p1_score.rank, 5, "Player 1 should have a straight");
         assert!(
-            !game.dealer_wins(&table),
+            !game.dealer_wins_outright(&table),
             "Player 0 (dealer) should NOT win this hand"
         );
         assert_eq!(
-            game.winning_player(&table),
-            1,
+            game.split_pot_winners(&table),
+            vec![1],
             "Player 1 should be the winning player"
         );
     }
@@ -255,12 +1186,13 @@ mod tests {
         let player1 = Player([card_ac, card_3d]);
 
         // Create common cards
-        let common = Common([card_7h, card_5c, card_10c, card_8c, card_8h]);
+        let common = Common(vec![card_7h, card_5c, card_10c, card_8c, card_8h]);
 
         // Create game
         let game = Game {
-            players: vec![player0, player1],
+            players: vec![player0.into(), player1.into()],
             common,
+            variant: GameVariant::Holdem,
         };
 
         // Load the precomputed score table
@@ -284,11 +1216,10 @@ mod tests {
         use crate::hands::Hand;
 
         // Player 0 full 7-card hand: Q♦, 4♠ (hole) + 7♥, 5♣, 10♣, 8♣, 8♥ (community)
-        let mut p0_full = [
+        let p0_hand = Hand::from_cards([
             card_qd, card_4s, card_7h, card_5c, card_10c, card_8c, card_8h,
-        ];
-        p0_full.sort();
-        let p0_hand = Hand(p0_full);
+        ])
+        .unwrap();
         let p0_manual = p0_hand.score();
         println!(
             "Player 0 manual 7-card: rank={}, hi={}",
@@ -317,12 +1248,12 @@ mod tests {
         assert_eq!(p1_score.hi, 14, "Player 1's high card should be Ace (14)");
         assert_eq!(p0_score.hi, 12, "Player 0's high card should be Queen (12)");
         assert!(
-            !game.dealer_wins(&table),
+            !game.dealer_wins_outright(&table),
             "Player 0 (dealer) should NOT win this hand"
         );
         assert_eq!(
-            game.winning_player(&table),
-            1,
+            game.split_pot_winners(&table),
+            vec![1],
             "Player 1 should be the winning player"
         );
     }
@@ -351,11 +1282,10 @@ mod tests {
 
         // Check Player 1's full 7-card hand: K♥, 6♦ (hole) + 4♣, 3♦, 7♠, 5♣, J♠ (community)
         // This should find the 3-4-5-6-7 straight
-        let mut p1_full = [
+        let p1_hand = Hand::from_cards([
             card_kh, card_6d, card_4c, card_3d, card_7s, card_5c, card_js,
-        ];
-        p1_full.sort(); // Hands must be sorted
-        let p1_hand = Hand(p1_full);
+        ])
+        .unwrap();
         let p1_score = p1_hand.score();
         println!(
             "Player 1 7-card (has 3-4-5-6-7 straight): rank={}, hi={}",
@@ -364,11 +1294,10 @@ mod tests {
 
         // Check Player 0's full 7-card hand: 8♥, 7♦ (hole) + 4♣, 3♦, 7♠, 5♣, J♠ (community)
         // This should find the pair of 7s
-        let mut p0_full = [
+        let p0_hand = Hand::from_cards([
             card_8h, card_7d, card_4c, card_3d, card_7s, card_5c, card_js,
-        ];
-        p0_full.sort(); // Hands must be sorted
-        let p0_hand = Hand(p0_full);
+        ])
+        .unwrap();
         let p0_score = p0_hand.score();
         println!(
             "Player 0 7-card (has pair of 7s): rank={}, hi={}",
@@ -383,4 +1312,953 @@ mod tests {
             "Player 1's straight should beat Player 0's pair"
         );
     }
+
+    #[test]
+    fn test_board_straight_is_not_an_outright_win() {
+        // Community: 5♣ 6♦ 7♠ 8♥ 9♣ - a straight that no hole card can improve
+        // Player 0: 2♥ 3♦
+        // Player 1: 2♠ 3♣
+        // Both players play the board, so the pot is chopped
+        let common = Common(vec![
+            Card::new(Value::new(5), Suit::Clubs),
+            Card::new(Value::new(6), Suit::Diamonds),
+            Card::new(Value::new(7), Suit::Spades),
+            Card::new(Value::new(8), Suit::Hearts),
+            Card::new(Value::new(9), Suit::Clubs),
+        ]);
+        let game = Game {
+            players: vec![
+                Player([
+                    Card::new(Value::new(2), Suit::Hearts),
+                    Card::new(Value::new(3), Suit::Diamonds),
+                ])
+                .into(),
+                Player([
+                    Card::new(Value::new(2), Suit::Spades),
+                    Card::new(Value::new(3), Suit::Clubs),
+                ])
+                .into(),
+            ],
+            common,
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        assert_eq!(game.players_score(0, &table).rank, 5);
+        assert!(game.players_score(0, &table) == game.players_score(1, &table));
+        // A chopped pot is not an outright win for the dealer
+        assert!(!game.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_showdown_orders_every_seat() {
+        // Community: 2♣ 7♦ 9♠ J♥ K♣
+        // Player 0: 3♦ 4♥ - King high
+        // Player 1: K♦ 5♠ - a pair of Kings
+        // Player 2: 3♠ 4♣ - the same King high as player 0
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let game = Game {
+            players: vec![
+                Player([card(3, Suit::Diamonds), card(4, Suit::Hearts)]).into(),
+                Player([card(13, Suit::Diamonds), card(5, Suit::Spades)]).into(),
+                Player([card(3, Suit::Spades), card(4, Suit::Clubs)]).into(),
+            ],
+            common: Common(vec![
+                card(2, Suit::Clubs),
+                card(7, Suit::Diamonds),
+                card(9, Suit::Spades),
+                card(11, Suit::Hearts),
+                card(13, Suit::Clubs),
+            ]),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        let showdown = game.showdown(&table);
+        let seats: Vec<usize> = showdown.iter().map(|(seat, _)| *seat).collect();
+        assert_eq!(seats, vec![1, 0, 2]);
+        assert_eq!(showdown[0].1.category(), HandCategory::Pair);
+        assert_eq!(showdown[1].1.category(), HandCategory::HighCard);
+        assert!(showdown[1].1 == showdown[2].1);
+        assert_eq!(game.split_pot_winners(&table), vec![1]);
+    }
+
+    #[test]
+    fn test_board_straight_splits_the_pot_between_everyone() {
+        // Community: 10♣ J♦ Q♠ K♥ A♣ - Broadway, which no hole card can beat
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let game = Game {
+            players: vec![
+                Player([card(2, Suit::Hearts), card(3, Suit::Diamonds)]).into(),
+                Player([card(4, Suit::Spades), card(5, Suit::Clubs)]).into(),
+                Player([card(9, Suit::Diamonds), card(2, Suit::Spades)]).into(),
+            ],
+            common: Common(vec![
+                card(10, Suit::Clubs),
+                card(11, Suit::Diamonds),
+                card(12, Suit::Spades),
+                card(13, Suit::Hearts),
+                card(1, Suit::Clubs),
+            ]),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        assert_eq!(game.split_pot_winners(&table), vec![0, 1, 2]);
+        assert!(!game.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_board_straight_flush_ties_everyone() {
+        // Community: 5♥ 6♥ 7♥ 8♥ 9♥. Player 1's 4♥ only makes a lower straight flush, and
+        // player 2's A♥ and K♥ a lower flush, so every seat plays the board
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let game = Game {
+            players: vec![
+                Player([card(9, Suit::Spades), card(9, Suit::Clubs)]).into(),
+                Player([card(4, Suit::Hearts), card(3, Suit::Hearts)]).into(),
+                Player([card(1, Suit::Hearts), card(13, Suit::Hearts)]).into(),
+            ],
+            common: Common((5..=9).map(|value| card(value, Suit::Hearts)).collect()),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        let dealer = game.players_score(0, &table);
+        assert_eq!(dealer.category(), HandCategory::StraightFlush);
+        for seat in 1..3 {
+            assert_eq!(game.players_score(seat, &table), dealer);
+        }
+        assert_eq!(game.split_pot_winners(&table), vec![0, 1, 2]);
+        assert!(!game.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_opponent_model_folds_weak_unpaired_hole_cards() {
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let folds = OpponentModel::FoldsBelow(10);
+        assert!(!folds.plays(&[card(9, Suit::Spades), card(7, Suit::Hearts)]));
+        assert!(folds.plays(&[card(10, Suit::Spades), card(2, Suit::Hearts)]));
+        assert!(folds.plays(&[card(1, Suit::Clubs), card(3, Suit::Hearts)]));
+        assert!(folds.plays(&[card(2, Suit::Clubs), card(2, Suit::Hearts)]));
+        let omaha = [2, 5, 7, 9].map(|value| card(value, Suit::Diamonds));
+        assert!(!folds.plays(&omaha));
+        assert!(OpponentModel::Showdown.plays(&omaha));
+    }
+
+    #[test]
+    fn test_folding_opponent_flips_a_loss_to_a_win() {
+        // Cut 0 deals the dealer A♠ K♦ and the opponent 7♣ 2♦, with a board of 7♥ 9♠ J♣ 4♦ 3♥:
+        // the opponent's sevens win a showdown, but 7-2 is folded by an opponent wanting a ten
+        let card = |value, suit| Some(Card::new(Value::new(value), suit));
+        let dealt = [
+            card(1, Suit::Spades),
+            card(7, Suit::Clubs),
+            card(13, Suit::Diamonds),
+            card(2, Suit::Diamonds),
+            None,
+            card(7, Suit::Hearts),
+            card(9, Suit::Spades),
+            card(11, Suit::Clubs),
+            None,
+            card(4, Suit::Diamonds),
+            None,
+            card(3, Suit::Hearts),
+        ];
+        let mut deck = Deck::new_deck_order();
+        for (i, card) in dealt.into_iter().enumerate() {
+            if let Some(card) = card {
+                let from = deck.iter().position(|&c| c == card).unwrap();
+                deck.swap(from, DECK_SIZE - 1 - i);
+            }
+        }
+        let table = ScoreTable::direct();
+        let folds = OpponentModel::FoldsBelow(10);
+        let config = DealConfig::default();
+
+        let game = deal_a_round(2, deck);
+        assert_eq!(game.players_score(1, &table).category(), HandCategory::Pair);
+        assert_eq!(game.staying(&[1], folds).count(), 0);
+        assert!(!dealer_wins_game(2, deck, &table));
+        let everyone = OpponentModel::Showdown;
+        assert!(!player_wins_game_vs(
+            2,
+            0,
+            &[1],
+            deck,
+            &table,
+            everyone,
+            config
+        ));
+        assert!(player_wins_game_vs(2, 0, &[1], deck, &table, folds, config));
+        // With nobody left to beat, the dealer wins by its whole hand
+        let margin = position_margin_vs(2, 0, &[1], &deck, 0, &table, folds, config);
+        assert_eq!(margin, game.players_score(0, &table).to_score());
+
+        // Folds only ever remove opponents, so no position flips the other way
+        let showdown = winning_positions(2, &deck, &table, false);
+        let folding = winning_positions_vs(2, 0, &[1], &deck, &table, false, folds, config);
+        assert_eq!(showdown & 1, 0);
+        assert_eq!(folding & 1, 1);
+        assert_eq!(folding & showdown, showdown);
+    }
+
+    #[test]
+    fn test_showdown_opponents_count_the_same_wins() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(102);
+        for num_players in [2, 4] {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let opponents = all_opponents(num_players, 0);
+            let model = OpponentModel::Showdown;
+            for config in [DealConfig::default(), DealConfig::no_burns()] {
+                assert_eq!(
+                    winning_positions_vs(
+                        num_players,
+                        0,
+                        &opponents,
+                        &deck,
+                        &table,
+                        true,
+                        model,
+                        config,
+                    ),
+                    winning_positions_with(num_players, &deck, &table, true, config)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_deal_omaha_round() {
+        let num_players = 3;
+        let deck = Deck::new_deck_order();
+        let game = deal_a_round_variant(num_players, deck, GameVariant::Omaha);
+
+        // Cards come off the end of the deck: four rounds of hole cards, then the board
+        let dealt: Vec<Card> = deck.iter().rev().copied().collect();
+        for (p, hole) in game.players.iter().enumerate() {
+            let HoleCards::Omaha(hole) = hole else {
+                panic!("expected Omaha hole cards");
+            };
+            for round in 0..4 {
+                assert_eq!(hole.0[round], dealt[round * num_players + p]);
+            }
+        }
+        let board_start = 4 * num_players;
+        assert_eq!(game.common.0[0], dealt[board_start + 1]);
+        assert_eq!(game.common.0[3], dealt[board_start + 5]);
+        assert_eq!(game.common.0[4], dealt[board_start + 7]);
+
+        // Omaha scoring bypasses the table
+        let table = ScoreTable::direct();
+        for p in 0..num_players {
+            let HoleCards::Omaha(hole) = &game.players[p] else {
+                unreachable!()
+            };
+            assert_eq!(game.players_score(p, &table), hole.score(&game.common));
+        }
+    }
+
+    #[test]
+    fn test_board_equity_counts_winning_boards() {
+        // Dealer holds A♠ A♥, opponent holds 7♣ 2♦
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let mut game = Game {
+            players: vec![
+                Player([card(1, Suit::Spades), card(1, Suit::Hearts)]).into(),
+                Player([card(7, Suit::Clubs), card(2, Suit::Diamonds)]).into(),
+            ],
+            common: Common::default(),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        // A board from these six cards always gives the dealer at least aces up
+        let safe = [
+            card(9, Suit::Spades),
+            card(10, Suit::Hearts),
+            card(11, Suit::Clubs),
+            card(13, Suit::Diamonds),
+            card(4, Suit::Spades),
+            card(5, Suit::Hearts),
+        ];
+        assert_eq!(game.board_equity(&safe, &table), 1.0);
+
+        // With only 7s and 2s left, every board fills the opponent up
+        let bad = [
+            card(7, Suit::Spades),
+            card(7, Suit::Hearts),
+            card(7, Suit::Diamonds),
+            card(2, Suit::Spades),
+            card(2, Suit::Hearts),
+        ];
+        assert_eq!(game.board_equity(&bad, &table), 0.0);
+
+        // Mixed: count the winning boards by hand
+        let mixed = [
+            card(7, Suit::Spades),
+            card(7, Suit::Hearts),
+            card(9, Suit::Spades),
+            card(10, Suit::Hearts),
+            card(11, Suit::Clubs),
+            card(13, Suit::Diamonds),
+        ];
+        // Of the 6 boards, the opponent makes trips exactly when both 7s come (4 boards)
+        let equity = game.board_equity(&mixed, &table);
+        assert!((equity - 2.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_heads_up_board_equity_counts_wins_ties_and_losses() {
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let mut game = Game {
+            players: vec![
+                Player([card(1, Suit::Spades), card(1, Suit::Hearts)]).into(),
+                Player([card(13, Suit::Clubs), card(13, Suit::Diamonds)]).into(),
+            ],
+            common: Common::default(),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        // A straight on the board plays for both hands
+        let straight = [
+            card(2, Suit::Spades),
+            card(3, Suit::Hearts),
+            card(4, Suit::Clubs),
+            card(5, Suit::Diamonds),
+            card(6, Suit::Spades),
+        ];
+        assert_eq!(
+            game.heads_up_board_equity(&straight, &table),
+            (0.0, 1.0, 0.0)
+        );
+
+        // Of the 6 boards, the 5 with the last king give kings a set
+        let mixed = [
+            card(13, Suit::Spades),
+            card(2, Suit::Hearts),
+            card(4, Suit::Clubs),
+            card(6, Suit::Diamonds),
+            card(8, Suit::Spades),
+            card(9, Suit::Hearts),
+        ];
+        let (win, tie, loss) = game.heads_up_board_equity(&mixed, &table);
+        assert!((win - 1.0 / 6.0).abs() < 1e-9, "{win}");
+        assert_eq!(tie, 0.0);
+        assert!((loss - 5.0 / 6.0).abs() < 1e-9, "{loss}");
+    }
+
+    #[test]
+    #[ignore = "Scores all 1.7M boards, which takes about a minute in debug builds"]
+    fn test_heads_up_equity_aces_dominate_kings() {
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let aces = Player([card(1, Suit::Spades), card(1, Suit::Hearts)]);
+        let kings = Player([card(13, Suit::Clubs), card(13, Suit::Diamonds)]);
+        let (win, tie, loss) = heads_up_equity(aces, kings, &ScoreTable::direct());
+        assert!((win + tie + loss - 1.0).abs() < 1e-9);
+        // The textbook figure is about 82% for aces
+        assert!((0.79..0.84).contains(&win), "{win}");
+        assert!(tie < 0.01, "{tie}");
+        assert!(win > 4.0 * loss, "{win} vs {loss}");
+    }
+
+    #[test]
+    fn test_unseen_cards_excludes_hole_cards() {
+        let game = deal_a_round(3, Deck::new_deck_order());
+        let unseen = game.unseen_cards();
+        assert_eq!(unseen.len(), 52 - 6);
+        for hole in &game.players {
+            let HoleCards::Holdem(p) = hole else {
+                unreachable!()
+            };
+            assert!(p.0.iter().all(|c| !unseen.contains(c)));
+        }
+    }
+
+    #[test]
+    fn test_winning_positions_popcount_is_num_wins() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(9));
+        for real in [false, true] {
+            let mask = winning_positions(3, &deck, &table, real);
+            assert_eq!(mask.count_ones() as usize, num_wins(3, &deck, &table, real));
+            for cut_pos in 0..52 {
+                let wins = dealer_wins_game(3, deck.cut(cut_pos), &table);
+                let in_range = cut_positions(real).contains(&cut_pos);
+                assert_eq!(mask & (1 << cut_pos) != 0, wins && in_range);
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_wins_by_player_count() {
+        for num_players in [2, 6, 9] {
+            // Even 9 players only use 26 cards, so every cut position deals a full round
+            assert!(cards_per_round(num_players) <= 52);
+            assert_eq!(max_wins(num_players, false), 52);
+            assert_eq!(max_wins(num_players, true), 42);
+        }
+        assert_eq!(cards_per_round(9), 26);
+        // 23 players need 54 cards
+        assert_eq!(max_wins(23, false), 0);
+    }
+
+    #[test]
+    fn test_too_many_players_is_an_error() {
+        assert_eq!(check_player_count(2, 52, DealConfig::default()), Ok(()));
+        assert_eq!(check_player_count(22, 52, DealConfig::default()), Ok(()));
+        let err = check_player_count(23, 52, DealConfig::default()).unwrap_err();
+        assert_eq!(
+            err,
+            DealError::NotEnoughCards {
+                num_players: 23,
+                needed: 54,
+                available: 52
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "23 players need 54 cards but only 52 are dealable"
+        );
+        assert_eq!(
+            check_player_count(0, 52, DealConfig::default()),
+            Err(DealError::NoPlayers)
+        );
+
+        // Omaha deals four hole cards each, so 11 players need 52 cards and 12 need 56
+        let omaha = DealConfig::default().with_variant(GameVariant::Omaha);
+        assert_eq!(check_player_count(11, 52, omaha), Ok(()));
+        assert!(check_player_count(12, 52, omaha).is_err());
+    }
+
+    #[test]
+    fn test_every_cut_deals_a_full_round() {
+        let deck = Deck::new_deck_order();
+        for num_players in [2, 6, 9] {
+            for cut_pos in cut_positions(false) {
+                let game = deal_a_round(num_players, deck.cut(cut_pos));
+                assert_eq!(game.players.len(), num_players);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_burns_changes_the_board() {
+        let num_players = 2;
+        let deck = Deck::new_deck_order();
+        let dealt: Vec<Card> = deck.iter().rev().copied().collect();
+        let board_start = 2 * num_players;
+
+        let standard = deal_a_round(num_players, deck);
+        let no_burns = deal_a_round_with(num_players, deck, DealConfig::no_burns());
+
+        // Hole cards are dealt before any burn
+        for p in 0..num_players {
+            let (HoleCards::Holdem(a), HoleCards::Holdem(b)) =
+                (&standard.players[p], &no_burns.players[p])
+            else {
+                unreachable!()
+            };
+            assert_eq!(a.0, b.0);
+        }
+
+        // Without burns, the board is the next five cards in order
+        assert_eq!(no_burns.common.0, dealt[board_start..board_start + 5]);
+        // With burns, the first card after the hole cards never reaches the board
+        assert_eq!(standard.common.0[0], dealt[board_start + 1]);
+        assert!(!standard.common.0.contains(&dealt[board_start]));
+        assert_ne!(standard.common.0, no_burns.common.0);
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house_at_showdown() {
+        // Community: 6♥ 8♥ J♥ K♣ K♦
+        // Player 0: A♥ 9♥ - ace-high flush
+        // Player 1: K♥ J♣ - Kings full of Jacks
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let common = Common(vec![
+            card(6, Suit::Hearts),
+            card(8, Suit::Hearts),
+            card(11, Suit::Hearts),
+            card(13, Suit::Clubs),
+            card(13, Suit::Diamonds),
+        ]);
+        let players = vec![
+            Player([card(1, Suit::Hearts), card(9, Suit::Hearts)]).into(),
+            Player([card(13, Suit::Hearts), card(11, Suit::Clubs)]).into(),
+        ];
+        let table = ScoreTable::direct();
+
+        let holdem = Game {
+            players: players.clone(),
+            common: common.clone(),
+            variant: GameVariant::Holdem,
+        };
+        assert_eq!(holdem.split_pot_winners(&table), vec![1]);
+
+        let short = Game {
+            players,
+            common,
+            variant: GameVariant::ShortDeck,
+        };
+        assert_eq!(
+            short.players_score(0, &table).category(),
+            HandCategory::Flush
+        );
+        assert_eq!(
+            short.players_score(1, &table).category(),
+            HandCategory::FullHouse
+        );
+        assert_eq!(short.split_pot_winners(&table), vec![0]);
+        assert!(short.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_position_summary_matches_winning_positions() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(7));
+        for num_players in [2, 4] {
+            let mask = winning_positions(num_players, &deck, &table, false);
+            for cut_pos in 0..DECK_SIZE {
+                let summary = position_summary(num_players, &deck, cut_pos, &table);
+                assert_eq!(summary.cut_pos, cut_pos);
+                assert_eq!(summary.dealer_wins, mask & (1 << cut_pos) != 0);
+                // The dealer wins exactly when they beat the best opponent
+                assert_eq!(summary.dealer_wins, summary.margin > 0);
+                // and split the pot exactly when they tie with it
+                assert_eq!(summary.splits_pot, summary.margin == 0);
+                assert!(
+                    summary.dealer_wins || Some(summary.dealer.category()) <= summary.best_opponent
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_position_summary_without_opponents() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(8));
+        for cut_pos in 0..DECK_SIZE {
+            let summary = position_summary(1, &deck, cut_pos, &table);
+            assert_eq!(summary.best_opponent, None);
+            // Alone, the dealer wins by their whole score, like `verify -n 1`
+            assert!(summary.dealer_wins);
+            assert!(!summary.splits_pot);
+            assert!(summary.margin > 0);
+        }
+    }
+
+    #[test]
+    fn test_position_win_frequencies_sum_to_total_wins() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(11);
+        let decks: Vec<Deck> = (0..6)
+            .map(|_| Deck::new_deck_order().shuffle(&mut rng))
+            .collect();
+        let config = DealConfig::default();
+        for num_players in [2, 5] {
+            let counts = position_win_frequencies(num_players, &table, &decks, false, config);
+            let total: usize = decks
+                .iter()
+                .map(|deck| num_wins(num_players, deck, &table, false))
+                .sum();
+            assert_eq!(counts.iter().sum::<usize>(), total);
+            assert!(counts.iter().all(|&count| count <= decks.len()));
+        }
+        assert_eq!(
+            position_win_frequencies(2, &table, &[], false, config),
+            [0; DECK_SIZE]
+        );
+
+        // Only the scored cuts are counted
+        let counts = position_win_frequencies(2, &table, &decks, true, config);
+        let total: usize = decks
+            .iter()
+            .map(|deck| num_wins(2, deck, &table, true))
+            .sum();
+        assert_eq!(counts.iter().sum::<usize>(), total);
+    }
+
+    #[test]
+    fn test_win_category_histogram_sums_to_num_wins() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(12);
+        for num_players in [2, 3, 6] {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let config = DealConfig::default();
+            let counts = win_category_histogram(num_players, &deck, &table, false, config);
+            assert_eq!(
+                counts.iter().sum::<usize>(),
+                num_wins(num_players, &deck, &table, false)
+            );
+            assert_eq!(counts[0], 0);
+        }
+        // The dealer wins every cut of the perfect deck with something
+        let perfect = Deck::from_card_ids(&crate::known_decks::three_player_perfect_deck());
+        let perfect = perfect.unwrap();
+        let counts = win_category_histogram(3, &perfect, &table, false, DealConfig::default());
+        assert_eq!(counts.iter().sum::<usize>(), 52);
+        let counts = win_category_histogram(3, &perfect, &table, true, DealConfig::default());
+        assert_eq!(counts.iter().sum::<usize>(), max_wins(3, true));
+        // Cuts are dealt the way they're counted
+        let no_burns = DealConfig::no_burns();
+        let counts = win_category_histogram(3, &perfect, &table, false, no_burns);
+        let wins = num_wins_with(3, &perfect, &table, false, no_burns);
+        assert_eq!(counts.iter().sum::<usize>(), wins);
+        assert!(wins < 52);
+    }
+
+    #[test]
+    fn test_known_decks_win_as_the_smt_solver_expects() {
+        use crate::known_decks::*;
+        let table = ScoreTable::direct();
+        let perfect = Deck::from_card_ids(&three_player_perfect_deck()).unwrap();
+        assert_eq!(num_wins(3, &perfect, &table, false), 52);
+        let swapped = Deck::from_card_ids(&three_player_swapped_deck()).unwrap();
+        let losses = !winning_positions(3, &swapped, &table, false) & ((1 << 52) - 1);
+        assert_eq!(losses, 1 << 1 | 1 << 2 | 1 << 14 | 1 << 15);
+    }
+
+    #[test]
+    fn test_best_cut_for_dealer_matches_a_full_scan() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order();
+        for (num_players, real) in [(1, false), (2, false), (3, false), (6, false), (2, true)] {
+            let margins: Vec<(usize, i32)> = cut_positions(real)
+                .map(|cut_pos| {
+                    (
+                        cut_pos,
+                        position_margin(num_players, &deck, cut_pos, &table),
+                    )
+                })
+                .collect();
+            let best = margins.iter().map(|&(_, m)| m).max().unwrap();
+            let first_best = margins.iter().find(|&&(_, m)| m == best).unwrap().0;
+            assert_eq!(
+                best_cut_for_dealer(num_players, &deck, &table, real),
+                (first_best, best)
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_wins_delta_matches_full_recompute() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(21);
+        let omaha = DealConfig::default().with_variant(GameVariant::Omaha);
+        let deals = [
+            (2, false, DealConfig::default()),
+            (6, false, DealConfig::default()),
+            (3, true, DealConfig::default()),
+            (4, false, omaha),
+        ];
+        for (num_players, real, config) in deals {
+            let mut deck = Deck::new_deck_order().shuffle(&mut rng);
+            let mut mask = winning_positions_with(num_players, &deck, &table, real, config);
+            for _ in 0..20 {
+                let a = rng.rand_range(0..52) as usize;
+                let b = rng.rand_range(0..52) as usize;
+                deck.swap(a, b);
+                mask = num_wins_delta(num_players, &deck, (a, b), mask, real, config, |cut_deck| {
+                    dealer_wins_game_with(num_players, cut_deck, &table, config)
+                });
+                let full = winning_positions_with(num_players, &deck, &table, real, config);
+                assert_eq!(mask, full);
+            }
+        }
+    }
+
+    #[test]
+    fn test_margin_profile_positive_entries_are_wins() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(13);
+        for (num_players, real) in [(2, false), (4, true)] {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let config = DealConfig::default();
+            let margins = margin_profile(num_players, 0, &deck, &table, real, config);
+            assert_eq!(margins.len(), cut_positions(real).len());
+            assert_eq!(
+                margins.iter().filter(|&&margin| margin > 0).count(),
+                num_wins(num_players, &deck, &table, real)
+            );
+        }
+    }
+
+    #[test]
+    fn test_wins_over_all_players_cover_every_untied_cut() {
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(31);
+        for num_players in [2, 3, 6] {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let wins: Vec<usize> = (0..num_players)
+                .map(|target| num_wins_for(num_players, target, &deck, &table, false))
+                .collect();
+            assert_eq!(wins[0], num_wins(num_players, &deck, &table, false));
+
+            // A cut with a tie at the top has no outright winner
+            let ties = cut_positions(false)
+                .filter(|&cut_pos| {
+                    let game = deal_a_round(num_players, deck.cut(cut_pos));
+                    let scores: Vec<_> = (0..num_players)
+                        .map(|idx| game.players_score(idx, &table))
+                        .collect();
+                    let best = scores.iter().max().unwrap();
+                    scores.iter().filter(|&score| score == best).count() > 1
+                })
+                .count();
+            assert_eq!(
+                wins.iter().sum::<usize>() + ties,
+                cut_positions(false).len(),
+                "{num_players} players"
+            );
+
+            for (target, &target_wins) in wins.iter().enumerate() {
+                let config = DealConfig::default();
+                let margins = margin_profile(num_players, target, &deck, &table, false, config);
+                assert_eq!(
+                    margins.iter().filter(|&&margin| margin > 0).count(),
+                    target_wins
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dealer_always_wins_with_no_active_opponents() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(41));
+        assert_eq!(
+            num_wins_vs(4, &[], &deck, &table, false),
+            cut_positions(false).len()
+        );
+        assert_eq!(
+            num_wins_vs(4, &[], &deck, &table, true),
+            cut_positions(true).len()
+        );
+    }
+
+    #[test]
+    fn test_fewer_active_opponents_never_lose_wins() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(43));
+        let everyone = winning_positions(4, &deck, &table, false);
+        let wins = num_wins_vs(4, &[1, 2, 3], &deck, &table, false);
+        assert_eq!(wins, everyone.count_ones() as usize);
+
+        // A cut won against everyone is still won when seat 2 folds
+        let model = OpponentModel::Showdown;
+        let config = DealConfig::default();
+        let some = winning_positions_vs(4, 0, &[1, 3], &deck, &table, false, model, config);
+        assert_eq!(some & everyone, everyone);
+    }
+
+    #[test]
+    fn test_flop_only_showdown() {
+        // Board: K♣ 7♦ 2♥
+        // Player 0: K♠ Q♦ - pair of kings
+        // Player 1: 7♠ 7♥ - trip sevens
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let game = Game {
+            players: vec![
+                Player([card(13, Suit::Spades), card(12, Suit::Diamonds)]).into(),
+                Player([card(7, Suit::Spades), card(7, Suit::Hearts)]).into(),
+            ],
+            common: Common(vec![
+                card(13, Suit::Clubs),
+                card(7, Suit::Diamonds),
+                card(2, Suit::Hearts),
+            ]),
+            variant: GameVariant::Holdem,
+        };
+        let table = ScoreTable::direct();
+
+        let dealer = game.players_score(0, &table);
+        assert_eq!(dealer.category(), HandCategory::Pair);
+        assert_eq!(dealer.kickers, [13, 13, 12, 7, 2]);
+        assert_eq!(
+            game.players_score(1, &table).category(),
+            HandCategory::ThreeOfAKind
+        );
+        assert_eq!(game.split_pot_winners(&table), vec![1]);
+        assert!(!game.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_deal_trace_accounts_for_every_card() {
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(12));
+        let flop_only = DealConfig::default().with_board_size(3).unwrap();
+        let deals = [
+            (3, DealConfig::default()),
+            (2, DealConfig::no_burns()),
+            (4, flop_only),
+            (2, DealConfig::default().with_variant(GameVariant::Omaha)),
+        ];
+        for (num_players, config) in deals {
+            let (game, trace) = deal_a_round_traced_with(num_players, deck, config);
+            let plain = deal_a_round_with(num_players, deck, config);
+            assert_eq!(game.common.0, plain.common.0);
+
+            // The trace is exactly the cards taken off the end of the deck
+            let dealt: Vec<Card> = deck.iter().rev().take(trace.0.len()).copied().collect();
+            assert_eq!(
+                trace.0.iter().map(|&(card, _)| card).collect::<Vec<_>>(),
+                dealt
+            );
+            let hole_cards = config.variant.hole_cards();
+            let burns = trace
+                .0
+                .iter()
+                .filter(|(_, d)| *d == DealDestination::Burn)
+                .count();
+            assert_eq!(
+                trace.0.len(),
+                hole_cards * num_players + burns + config.board_size
+            );
+            assert_eq!(trace.0.len(), config.cards_per_round(num_players));
+
+            let board: Vec<Card> = trace
+                .0
+                .iter()
+                .filter_map(|&(card, d)| matches!(d, DealDestination::Board(_)).then_some(card))
+                .collect();
+            assert_eq!(board, game.common.0);
+            for &(card, destination) in &trace.0 {
+                match destination {
+                    DealDestination::Hole { player, card: i } => match game.players[player] {
+                        HoleCards::Holdem(p) => assert_eq!(p.0[i], card),
+                        HoleCards::Omaha(p) => assert_eq!(p.0[i], card),
+                    },
+                    DealDestination::Board(n) => assert_eq!(game.common.0[n], card),
+                    DealDestination::Burn => {}
+                }
+            }
+        }
+
+        let (_, trace) = deal_a_round_traced(2, deck);
+        assert_eq!(trace.0.len(), cards_per_round(2));
+        assert_eq!(trace.0[4].1, DealDestination::Burn);
+        assert!(
+            trace
+                .to_string()
+                .starts_with(&format!("{} -> player 0 hole 0\n", trace.0[0].0))
+        );
+    }
+
+    #[test]
+    fn test_deal_custom_board_sizes() {
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(8));
+        let mut dealt = deck.to_vec();
+        dealt.reverse();
+        let board_start = 2 * 2;
+
+        let flop_only = DealConfig::default().with_board_size(3).unwrap();
+        let game = deal_a_round_with(2, deck, flop_only);
+        assert_eq!(game.common.0, dealt[board_start + 1..board_start + 4]);
+
+        // Short of the river, each seat's best five of its six cards
+        let turn = DealConfig::default().with_board_size(4).unwrap();
+        let game = deal_a_round_with(2, deck, turn);
+        let table = ScoreTable::direct();
+        for p in 0..2 {
+            let HoleCards::Holdem(hole) = &game.players[p] else {
+                unreachable!()
+            };
+            let cards = game.holdem_cards(hole);
+            assert_eq!(cards.len(), 6);
+            assert_eq!(game.players_score(p, &table), score_best_five(&cards));
+        }
+
+        // Extra cards come straight after the river
+        let seven_cards = DealConfig::default().with_board_size(7).unwrap();
+        let game = deal_a_round_with(2, deck, seven_cards);
+        assert_eq!(game.common.0.len(), 7);
+        assert_eq!(game.common.0[4], dealt[board_start + 7]);
+        assert_eq!(game.common.0[6], dealt[board_start + 9]);
+
+        let standard = deal_a_round(2, deck);
+        assert_eq!(game.common.0[..5], standard.common.0[..]);
+        for p in 0..2 {
+            assert!(game.players_score(p, &table) >= standard.players_score(p, &table));
+        }
+
+        // Two hole cards and fewer than three board cards can't make a hand
+        for board_size in 0..MIN_BOARD_SIZE {
+            let config = DealConfig::no_burns().with_board_size(board_size);
+            assert_eq!(config, Err(DealError::BoardTooSmall { board_size }));
+        }
+    }
+
+    #[test]
+    fn test_lowball_inverts_the_winner() {
+        // Community: A♣ 2♦ 3♥ K♠ K♣
+        // Player 0: K♦ K♥ - quad kings for high, a terrible low
+        // Player 1: 4♦ 5♠ - the wheel: just a straight for high, but the nut low
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let common = Common(vec![
+            card(1, Suit::Clubs),
+            card(2, Suit::Diamonds),
+            card(3, Suit::Hearts),
+            card(13, Suit::Spades),
+            card(13, Suit::Clubs),
+        ]);
+        let players = vec![
+            Player([card(13, Suit::Diamonds), card(13, Suit::Hearts)]).into(),
+            Player([card(4, Suit::Diamonds), card(5, Suit::Spades)]).into(),
+        ];
+        let table = ScoreTable::direct();
+
+        let high = Game {
+            players: players.clone(),
+            common: common.clone(),
+            variant: GameVariant::Holdem,
+        };
+        assert_eq!(high.split_pot_winners(&table), vec![0]);
+        assert!(high.dealer_wins_outright(&table));
+
+        let low = Game {
+            players,
+            common,
+            variant: GameVariant::Lowball,
+        };
+        assert_eq!(low.split_pot_winners(&table), vec![1]);
+        assert_eq!(low.players_score(1, &table).kickers, [5, 4, 3, 2, 1]);
+        // Player 0 can't avoid pairing a king
+        let dealer = low.players_score(0, &table);
+        assert_eq!(dealer.category(), HandCategory::Pair);
+        assert_eq!(dealer.kickers, [13, 13, 3, 2, 1]);
+        assert!(!low.dealer_wins_outright(&table));
+    }
+
+    #[test]
+    fn test_lowball_margins_are_positive_where_the_low_hand_wins() {
+        let table = ScoreTable::direct();
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(29));
+        let lowball = DealConfig::default().with_variant(GameVariant::Lowball);
+        let (model, opponents) = (OpponentModel::Showdown, all_opponents(3, 0));
+        let (wins, _) = wins_and_margin_vs(3, 0, &opponents, &deck, &table, false, model, lowball);
+        assert_eq!(wins, num_wins_with(3, &deck, &table, false, lowball));
+        assert_ne!(
+            winning_positions_with(3, &deck, &table, false, lowball),
+            winning_positions_with(3, &deck, &table, false, DealConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_short_deck_deals_every_cut() {
+        let deck = Deck::new_short_deck_order().shuffle(&mut oorandom::Rand32::new(36));
+        let short_deck = deck.to_vec();
+        for cut_pos in 0..SHORT_DECK_SIZE {
+            let config = DealConfig::default().with_variant(GameVariant::ShortDeck);
+            let (_, trace) = deal_a_round_traced_with(4, deck.cut(cut_pos), config);
+            let mut dealt: Vec<Card> = trace.0.iter().map(|&(card, _)| card).collect();
+            let from_short_deck = dealt.iter().all(|card| short_deck.contains(card));
+            assert!(from_short_deck, "cut {cut_pos}");
+            dealt.sort();
+            dealt.dedup();
+            assert_eq!(dealt.len(), cards_per_round(4), "cut {cut_pos}");
+        }
+        assert!(num_wins_short_deck(4, &deck, &ScoreTable::direct()) > 0);
+    }
 }
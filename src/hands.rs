@@ -1,5 +1,7 @@
 use crate::cards::*;
-use crate::precompute::Entry;
+use crate::combinations::combinations;
+use crate::precompute::{Entry, TableEntry};
+use std::sync::OnceLock;
 
 pub const HAND_SIZE: usize = 7;
 pub const ALL_HANDS: usize = 133_784_560;
@@ -7,8 +9,82 @@ pub const ALL_HANDS: usize = 133_784_560;
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Hand(pub [Card; 7]);
 
+/// Why seven cards do not make a [`Hand`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandError {
+    /// The same card appears twice
+    Duplicate(Card),
+}
+
+impl std::fmt::Display for HandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::Duplicate(card) => write!(f, "{card} appears more than once"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+/// The category of a poker hand, ordered from worst to best
+/// The discriminants are the `rank` bytes stored in the precomputed table
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard = 1,
+    Pair = 2,
+    TwoPair = 3,
+    ThreeOfAKind = 4,
+    Straight = 5,
+    Flush = 6,
+    FullHouse = 7,
+    FourOfAKind = 8,
+    StraightFlush = 9,
+}
+
+impl From<HandCategory> for u8 {
+    fn from(category: HandCategory) -> u8 {
+        category as u8
+    }
+}
+
+impl From<u8> for HandCategory {
+    fn from(rank: u8) -> Self {
+        use HandCategory::*;
+        match rank {
+            1 => HighCard,
+            2 => Pair,
+            3 => TwoPair,
+            4 => ThreeOfAKind,
+            5 => Straight,
+            6 => Flush,
+            7 => FullHouse,
+            8 => FourOfAKind,
+            9 => StraightFlush,
+            _ => panic!("Invalid hand rank {rank}"),
+        }
+    }
+}
+
+impl std::fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HandCategory::HighCard => "High Card",
+            HandCategory::Pair => "Pair",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::ThreeOfAKind => "Three of a Kind",
+            HandCategory::Straight => "Straight",
+            HandCategory::Flush => "Flush",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::FourOfAKind => "Four of a Kind",
+            HandCategory::StraightFlush => "Straight Flush",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Calculate binomial coefficient C(n, k)
-fn binomial(n: usize, k: usize) -> usize {
+const fn binomial(n: usize, k: usize) -> usize {
     if k > n {
         return 0;
     }
@@ -17,111 +93,318 @@ fn binomial(n: usize, k: usize) -> usize {
     }
 
     let mut result = 1;
-    for i in 0..k {
+    let mut i = 0;
+    while i < k {
         result = result * (n - i) / (i + 1);
+        i += 1;
     }
     result
 }
 
+/// BINOMIALS[n][k] = C(n, k) for every card id n, used to rank hands
+const BINOMIALS: [[usize; HAND_SIZE + 1]; 52] = {
+    let mut table = [[0; HAND_SIZE + 1]; 52];
+    let mut n = 0;
+    while n < 52 {
+        let mut k = 0;
+        while k <= HAND_SIZE {
+            table[n][k] = binomial(n, k);
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+};
+
 impl Hand {
+    /// Build a hand from seven distinct cards in any order
+    /// The cards are sorted, since table lookups need the canonical order
+    pub fn from_cards(mut cards: [Card; 7]) -> Result<Hand, HandError> {
+        cards.sort();
+        match cards.windows(2).find(|pair| pair[0] == pair[1]) {
+            Some(pair) => Err(HandError::Duplicate(pair[0])),
+            None => Ok(Hand(cards)),
+        }
+    }
+
+    /// The colexicographic rank of this hand among all 7-card hands
+    /// This is a bijection from sorted hands onto 0..ALL_HANDS, so it can index a flat table
+    /// The cards must be sorted in increasing order
+    pub fn colex_index(&self) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, card)| BINOMIALS[card.0 as usize][i + 1])
+            .sum()
+    }
+
+    /// The hand whose `colex_index` is `index`, which must be below `ALL_HANDS`
+    pub fn from_colex_index(mut index: usize) -> Hand {
+        let mut cards = [Card(0); HAND_SIZE];
+        let mut card = 52;
+        for (i, slot) in cards.iter_mut().enumerate().rev() {
+            // The highest remaining card whose block of hands starts at or before `index`
+            card -= 1;
+            while BINOMIALS[card][i + 1] > index {
+                card -= 1;
+            }
+            index -= BINOMIALS[card][i + 1];
+            *slot = Card(card as u8);
+        }
+        Hand(cards)
+    }
+
+    /// The hand after this one in `colex_index` order, if there is one
+    fn next_colex(mut self) -> Option<Hand> {
+        // Bump the lowest card that has room, and reset the ones below it
+        let i = (0..HAND_SIZE).find(|&i| {
+            let limit = self.0.get(i + 1).map_or(52, |c| c.0);
+            self.0[i].0 + 1 < limit
+        })?;
+        self.0[i].0 += 1;
+        for (j, card) in self.0[..i].iter_mut().enumerate() {
+            *card = Card(j as u8);
+        }
+        Some(self)
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         // SAFETY: Cards are a newtype of u8
         unsafe { std::mem::transmute(self.0.as_slice()) }
     }
 
+    /// The five cards making the best hand, e.g. to show "made with K♠ K♥ 7♦ 7♣ A♠",
+    /// and their score, which matches `score`
+    pub fn best_five(&self) -> ([Card; 5], TableEntry) {
+        best_five_cards_of(&self.0, lookup_five, TableEntry::cmp)
+    }
+
     /// Compute the rank of this hand (higher is better)
-    /// Also return the rank of the high card
+    /// Also return the rank of the high card and the kickers used to break ties
     /// This _will not_ be called in a hot loop, and will be used to precompute a lookup table
     ///
     /// For 7-card hands, we find the best 5-card poker hand within the 7 cards
+    /// Each 5-card hand is looked up in `five_card_scores` rather than scored again
     pub fn score(self) -> Entry {
         // For 7 cards, we need to check all C(7,5) = 21 possible 5-card combinations
-        // and find the best one
-        let mut best_rank = 0u8;
-        let mut best_hi = 0u8;
-
-        // Iterate through all 5-card combinations from the 7 cards
-        for i in 0..7 {
-            for j in (i + 1)..7 {
-                for k in (j + 1)..7 {
-                    for l in (k + 1)..7 {
-                        for m in (l + 1)..7 {
-                            let five_card_hand = [
-                                self.0[i],
-                                self.0[j],
-                                self.0[k],
-                                self.0[l],
-                                self.0[m],
-                            ];
-
-                            let (rank, hi) = score_five_cards(five_card_hand);
-
-                            // Update best if this hand is better
-                            if rank > best_rank || (rank == best_rank && hi > best_hi) {
-                                best_rank = rank;
-                                best_hi = hi;
-                            }
-                        }
-                    }
+        // and find the best one, stopping early once no other combination can beat it
+        let mut best = TableEntry::default();
+        for idx in combinations::<5>(HAND_SIZE) {
+            let score = lookup_five(idx.map(|i| self.0[i]));
+            if score > best {
+                best = score;
+                if self.cannot_beat(&best) {
+                    break;
                 }
             }
         }
 
         Entry {
             hand: self,
-            rank: best_rank,
-            hi: best_hi,
+            rank: best.rank,
+            hi: best.hi,
+            kickers: best.kickers,
+        }
+    }
+
+    /// Whether no five of these cards score better than `best`, a score some five of them make
+    /// Only checked for straight flushes and quads, the hands rare and strong enough to settle
+    /// the hand early; anything weaker is left to the full search
+    fn cannot_beat(&self, best: &TableEntry) -> bool {
+        let mut suit_counts = [0u8; 4];
+        for card in self.0 {
+            suit_counts[u8::from(card.into_inner().1) as usize] += 1;
+        }
+        // Seven cards hold at most one suit with five of them
+        let flush_suit = suit_counts.iter().position(|&count| count >= 5);
+        match best.category() {
+            // Only a higher straight flush wins, and that needs a higher card of the same suit
+            HandCategory::StraightFlush => flush_suit.is_some_and(|suit| {
+                self.0.iter().all(|card| {
+                    u8::from(card.into_inner().1) as usize != suit || card.rank_value() <= best.hi
+                })
+            }),
+            // Seven cards can't hold two sets of quads, so without a possible straight flush
+            // the only way up is a higher kicker
+            HandCategory::FourOfAKind if flush_suit.is_none() => {
+                let quads = best.kickers[0];
+                let top_kicker = self
+                    .0
+                    .iter()
+                    .map(|card| card.rank_value())
+                    .filter(|&value| value != quads)
+                    .max();
+                top_kicker == Some(best.kickers[4])
+            }
+            _ => false,
         }
     }
 }
 
-/// Score a 5-card poker hand
-/// Returns (rank, high_card)
-fn score_five_cards(cards: [Card; 5]) -> (u8, u8) {
-    // Extract values and suits from all cards
-    let mut values = [0u8; 5];
-    let mut suits = [0u8; 5];
+/// The best 5-card score among any number (at least five) of cards,
+/// where `cmp` says which score is better
+pub fn best_five_of(
+    cards: &[Card],
+    score_five: fn([Card; 5]) -> TableEntry,
+    cmp: fn(&TableEntry, &TableEntry) -> std::cmp::Ordering,
+) -> TableEntry {
+    best_five_cards_of(cards, score_five, cmp).1
+}
 
-    for i in 0..5 {
-        let (value, suit) = cards[i].into_inner();
-        values[i] = value.0;
-        suits[i] = suit.into();
+/// `best_five_of`, also returning the five cards that made the best hand
+/// Combinations are tried in lexicographic order, like nested loops over the indices,
+/// and the first of several equally good ones is kept
+pub fn best_five_cards_of(
+    cards: &[Card],
+    score_five: fn([Card; 5]) -> TableEntry,
+    cmp: fn(&TableEntry, &TableEntry) -> std::cmp::Ordering,
+) -> ([Card; 5], TableEntry) {
+    let n = cards.len();
+    assert!(n >= 5, "need at least five cards to make a hand, got {n}");
+    let mut best: Option<([Card; 5], TableEntry)> = None;
+    for idx in combinations::<5>(n) {
+        let five = idx.map(|i| cards[i]);
+        let score = score_five(five);
+        if best.is_none_or(|(_, b)| cmp(&score, &b).is_gt()) {
+            best = Some((five, score));
+        }
     }
+    best.unwrap()
+}
+
+/// The best high-hand score among any number (at least five) of cards
+/// Unlike `ScoreTable::score`, this works for any board size
+pub fn score_best_five(cards: &[Card]) -> TableEntry {
+    best_five_of(cards, lookup_five, TableEntry::cmp)
+}
+
+/// `score_five_cards`, read from the `five_card_scores` table
+fn lookup_five(cards: [Card; 5]) -> TableEntry {
+    five_card_scores()[five_card_index(cards)]
+}
+
+/// Number of 5-card hands, C(52, 5)
+const FIVE_CARD_HANDS: usize = binomial(52, 5);
+
+/// The colexicographic rank of a 5-card hand, in any order, among all 5-card hands
+fn five_card_index(mut cards: [Card; 5]) -> usize {
+    cards.sort_unstable();
+    cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| BINOMIALS[card.0 as usize][i + 1])
+        .sum()
+}
 
-    // Sort values for easier analysis
+/// `score_five_cards` for every 5-card hand, indexed by `five_card_index`
+/// Built on first use (2,598,960 evaluations), after which scoring a 7-card hand
+/// is 21 lookups instead of 21 evaluations
+fn five_card_scores() -> &'static [TableEntry] {
+    static SCORES: OnceLock<Box<[TableEntry]>> = OnceLock::new();
+    SCORES.get_or_init(|| {
+        let mut scores = vec![TableEntry::default(); FIVE_CARD_HANDS].into_boxed_slice();
+        for idx in combinations::<5>(52) {
+            let cards = idx.map(|i| Card(i as u8));
+            scores[five_card_index(cards)] = score_five_cards(cards);
+        }
+        scores
+    })
+}
+
+/// Score a 5-card short-deck (6+) hand, where the deck has no 2s through 5s
+/// The categories are the same as `score_five_cards`, but A-6-7-8-9 is also a straight (the lowest)
+/// Compare the results with `TableEntry::cmp_short_deck`, where a flush beats a full house
+pub fn score_five_cards_short(cards: [Card; 5]) -> TableEntry {
+    let mut values = cards.map(|c| c.into_inner().0.0);
     values.sort_unstable();
 
-    // Find high card (last after sorting)
-    // Special case: Ace (value 1) should be treated as 14 (highest) for high card
-    let high_card = if values[0] == 1 {
-        // If we have an Ace, it's the high card (treat as 14)
-        // unless we have a 5-high straight (A-2-3-4-5)
-        if values == [1, 2, 3, 4, 5] {
-            // 5-high straight, high card is 5
-            5
+    if values == [1, 6, 7, 8, 9] {
+        let suit = cards[0].into_inner().1;
+        let is_flush = cards.iter().all(|c| c.into_inner().1 == suit);
+        let category = if is_flush {
+            HandCategory::StraightFlush
         } else {
-            // Ace is high
-            14
-        }
-    } else {
-        // No Ace, high card is last element
-        values[4]
+            HandCategory::Straight
+        };
+        return TableEntry {
+            rank: category.into(),
+            hi: 9,
+            kickers: [9, 8, 7, 6, 5],
+        };
+    }
+
+    score_five_cards(cards)
+}
+
+/// Score a 5-card hand for A-5 lowball (as in Razz)
+/// Straights and flushes don't count and aces are low, so the best hand is 5-4-3-2-A.
+/// The category only reflects pairs, trips, and quads, and the kickers order aces as 1
+/// Compare the results with `TableEntry::cmp_lowball`, where the lower hand wins
+pub fn score_five_cards_lowball(cards: [Card; 5]) -> TableEntry {
+    let mut counts = [0u8; 14];
+    for card in cards {
+        counts[card.into_inner().0.0 as usize] += 1;
+    }
+
+    // Bigger groups first, then higher values, as for high hands
+    let mut groups: Vec<(u8, u8)> = (1..=13u8)
+        .filter(|&v| counts[v as usize] > 0)
+        .map(|v| (counts[v as usize], v))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+
+    let category = match (groups[0].0, groups.get(1).map_or(0, |g| g.0)) {
+        (4, _) => HandCategory::FourOfAKind,
+        (3, 2) => HandCategory::FullHouse,
+        (3, _) => HandCategory::ThreeOfAKind,
+        (2, 2) => HandCategory::TwoPair,
+        (2, _) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
     };
 
+    let mut kickers = [0u8; 5];
+    let mut idx = 0;
+    for (count, value) in groups {
+        for _ in 0..count {
+            kickers[idx] = value;
+            idx += 1;
+        }
+    }
+
+    TableEntry {
+        rank: category.into(),
+        hi: kickers.iter().copied().max().unwrap(),
+        kickers,
+    }
+}
+
+/// Score a 5-card poker hand
+/// Returns the rank, the high card, and the five card values in comparison order
+/// (grouped by count descending, then by value descending, with aces high except in the wheel)
+pub fn score_five_cards(cards: [Card; 5]) -> TableEntry {
+    // Sort values for easier analysis, ace high
+    let mut values = cards.map(|card| card.rank_value());
+    values.sort_unstable();
+    let suits = cards.map(|card| u8::from(card.into_inner().1));
+
     // Check for flush (all same suit)
     let is_flush = suits.iter().all(|&s| s == suits[0]);
 
-    // Check for straight
-    let is_straight = if values == [1, 10, 11, 12, 13] {
-        // Ace-high straight (royal)
-        true
+    // Check for straight, with the ace high (up to A-K-Q-J-T) or low (the wheel, A-2-3-4-5)
+    let mut low_values = cards.map(|card| card.straight_low_value());
+    low_values.sort_unstable();
+    let consecutive = |values: &[u8; 5]| values.windows(2).all(|w| w[1] == w[0] + 1);
+    let straight_high = if consecutive(&values) {
+        Some(values[4])
+    } else if consecutive(&low_values) {
+        Some(low_values[4])
     } else {
-        // Regular straight: consecutive values
-        values[1] == values[0] + 1
-            && values[2] == values[1] + 1
-            && values[3] == values[2] + 1
-            && values[4] == values[3] + 1
+        None
     };
+    let is_straight = straight_high.is_some();
+
+    // The high card is the top of a straight, so the wheel is 5-high; otherwise the largest value
+    let high_card = straight_high.unwrap_or(values[4]);
 
     // Count frequency of each unique value
     // First, collect unique values and their counts
@@ -158,108 +441,189 @@ fn score_five_cards(cards: [Card; 5]) -> (u8, u8) {
     sorted_counts[0..num_unique].sort_unstable_by(|a, b| b.cmp(a));
 
     // Determine hand rank
-    let rank = match (
+    let category = match (
         sorted_counts[0],
         sorted_counts.get(1).copied().unwrap_or(0),
         is_flush,
         is_straight,
     ) {
-        (_, _, true, true) => 9,  // Straight Flush
-        (4, _, _, _) => 8,        // Four of a Kind
-        (3, 2, _, _) => 7,        // Full House
-        (_, _, true, false) => 6, // Flush
-        (_, _, false, true) => 5, // Straight
-        (3, _, _, _) => 4,        // Three of a Kind
-        (2, 2, _, _) => 3,        // Two Pair
-        (2, _, _, _) => 2,        // One Pair
-        _ => 1,                   // High Card
+        (_, _, true, true) => HandCategory::StraightFlush,
+        (4, _, _, _) => HandCategory::FourOfAKind,
+        (3, 2, _, _) => HandCategory::FullHouse,
+        (_, _, true, false) => HandCategory::Flush,
+        (_, _, false, true) => HandCategory::Straight,
+        (3, _, _, _) => HandCategory::ThreeOfAKind,
+        (2, 2, _, _) => HandCategory::TwoPair,
+        (2, _, _, _) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
     };
 
-    (rank, high_card)
-}
-
-pub fn all_hands() -> impl Iterator<Item = Hand> {
-    Hands::new()
-}
-
-struct Hands {
-    // Current state: 7 card indices in increasing order
-    // None means iteration is complete
-    state: Option<[u8; 7]>,
-}
+    // Order the card values for tiebreaking: bigger groups first, then higher values
+    // Straights compare on their top card, so the wheel orders its ace as a 1
+    let kickers = if is_straight {
+        [
+            high_card,
+            high_card - 1,
+            high_card - 2,
+            high_card - 3,
+            high_card - 4,
+        ]
+    } else {
+        let mut groups: Vec<(u8, u8)> = (0..num_unique)
+            .map(|i| (unique_counts[i], unique_values[i]))
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
 
-impl Hands {
-    pub fn new() -> Self {
-        // Start with the first combination: [0, 1, 2, 3, 4, 5, 6]
-        Self {
-            state: Some([0, 1, 2, 3, 4, 5, 6]),
+        let mut kickers = [0u8; 5];
+        let mut idx = 0;
+        for (count, value) in groups {
+            for _ in 0..count {
+                kickers[idx] = value;
+                idx += 1;
+            }
         }
+        kickers
+    };
+
+    TableEntry {
+        rank: category.into(),
+        hi: high_card,
+        kickers,
     }
 }
 
-// An iterator over all possible hands
-// A hand cannot contain the same card twice
-impl Iterator for Hands {
-    type Item = Hand;
-    fn next(&mut self) -> Option<Self::Item> {
-        // Get current state, return None if exhausted
-        let current = self.state?;
-
-        // Create the hand from current state
-        let hand = Hand([
-            Card(current[0]),
-            Card(current[1]),
-            Card(current[2]),
-            Card(current[3]),
-            Card(current[4]),
-            Card(current[5]),
-            Card(current[6]),
-        ]);
+/// All hands in `colex_index` order, skipping the first `index`
+pub fn colex_hands_from(index: usize) -> impl Iterator<Item = Hand> {
+    let mut next = (index < ALL_HANDS).then(|| Hand::from_colex_index(index));
+    std::iter::from_fn(move || {
+        let hand = next?;
+        next = hand.next_colex();
+        Some(hand)
+    })
+}
 
-        // Increment to next combination
-        // Find the rightmost index that can be incremented
-        const MAX_CARD: u8 = 51;
-        let mut pos = None;
+/// Represents Hole Cards
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Player(pub [Card; 2]);
 
-        for i in (0..7).rev() {
-            // Check if this position can be incremented
-            // Position i can be incremented if current[i] < MAX_CARD - (6 - i)
-            if current[i] < MAX_CARD - (6 - i) as u8 {
-                pos = Some(i);
-                break;
-            }
-        }
+/// Represents Pot-Limit Omaha Hole Cards
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OmahaPlayer(pub [Card; 4]);
 
-        match pos {
-            Some(i) => {
-                // Increment position i and reset all positions to the right
-                let mut next_state = current;
-                next_state[i] += 1;
-                for j in (i + 1)..7 {
-                    next_state[j] = next_state[j - 1] + 1;
+impl OmahaPlayer {
+    /// Score the best hand using exactly two hole cards and exactly three community cards
+    /// The precomputed table scores Hold'em's best-5-of-7, so Omaha hands are scored directly
+    /// from all C(4,2) * C(5,3) = 60 combinations (on a standard board)
+    pub fn score(&self, common: &Common) -> TableEntry {
+        let mut best = TableEntry::default();
+        for [i, j] in combinations(4) {
+            for [k, l, m] in combinations(common.0.len()) {
+                let score =
+                    score_five_cards([self.0[i], self.0[j], common.0[k], common.0[l], common.0[m]]);
+                if score > best {
+                    best = score;
                 }
-                self.state = Some(next_state);
-            }
-            None => {
-                // No position can be incremented, we're done
-                self.state = None;
             }
         }
+        best
+    }
+}
 
-        Some(hand)
+/// One seat's hole cards, for whichever game is being played
+#[derive(Debug, Clone, Copy)]
+pub enum HoleCards {
+    Holdem(Player),
+    Omaha(OmahaPlayer),
+}
+
+impl From<Player> for HoleCards {
+    fn from(player: Player) -> Self {
+        HoleCards::Holdem(player)
     }
 }
 
-/// Represents Hole Cards
-#[derive(Debug, Clone, Copy, Default)]
-pub struct Player(pub [Card; 2]);
+impl From<OmahaPlayer> for HoleCards {
+    fn from(player: OmahaPlayer) -> Self {
+        HoleCards::Omaha(player)
+    }
+}
+
+/// The number of community cards in a standard board
+pub const BOARD_SIZE: usize = 5;
 
+/// The community cards, normally `BOARD_SIZE` of them
+/// Experimental variants may use other board sizes, which are scored directly
 #[derive(Debug, Clone, Default)]
-pub struct Common(pub [Card; 5]);
+pub struct Common(pub Vec<Card>);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::combinations::Combinations;
+
+    /// Every 7-card hand, in lexicographic order
+    /// A hand cannot contain the same card twice
+    struct Hands(Combinations<HAND_SIZE>);
+
+    impl Hands {
+        fn new() -> Self {
+            Self(combinations(52))
+        }
+
+        /// Start at the `index`th hand in iteration (lexicographic) order
+        fn starting_at(mut index: usize) -> Self {
+            if index >= ALL_HANDS {
+                // More cards than the deck has, so this yields nothing
+                return Self(Combinations::starting_at(0, [0; HAND_SIZE]));
+            }
+
+            // Choose each card in turn, skipping over the blocks of hands
+            // that start with a smaller card at this position
+            let mut state = [0; HAND_SIZE];
+            let mut next_card = 0usize;
+            for (pos, slot) in state.iter_mut().enumerate() {
+                loop {
+                    let remaining = HAND_SIZE - pos - 1;
+                    let block = binomial(51 - next_card, remaining);
+                    if index < block {
+                        break;
+                    }
+                    index -= block;
+                    next_card += 1;
+                }
+                *slot = next_card;
+                next_card += 1;
+            }
+            Self(Combinations::starting_at(52, state))
+        }
+    }
+
+    impl Iterator for Hands {
+        type Item = Hand;
+        fn next(&mut self) -> Option<Self::Item> {
+            let cards = self.0.next()?;
+            let card = |i: usize| Card(cards[i] as u8);
+            Some(Hand([
+                card(0),
+                card(1),
+                card(2),
+                card(3),
+                card(4),
+                card(5),
+                card(6),
+            ]))
+        }
+    }
+
+    /// Seven distinct random cards, in the order they were drawn
+    fn random_seven(rng: &mut oorandom::Rand32) -> [Card; 7] {
+        let mut ids: Vec<u8> = (0..52).collect();
+        for i in 0..7 {
+            let j = rng.rand_range(i as u32..52) as usize;
+            ids.swap(i, j);
+        }
+        std::array::from_fn(|i| Card(ids[i]))
+    }
 
     #[test]
     fn test_hands_iterator_count() {
@@ -270,6 +634,21 @@ mod tests {
         assert_eq!(count, ALL_HANDS);
     }
 
+    #[test]
+    fn test_from_cards_sorts_and_rejects_duplicates() {
+        let cards: [Card; 7] = [40, 3, 17, 51, 0, 29, 8].map(Card);
+        let mut sorted = cards;
+        sorted.sort();
+        assert_eq!(Hand::from_cards(cards), Hand::from_cards(sorted));
+        assert_eq!(Hand::from_cards(cards), Ok(Hand(sorted)));
+
+        let repeated = [40, 3, 17, 51, 3, 29, 8].map(Card);
+        assert_eq!(
+            Hand::from_cards(repeated),
+            Err(HandError::Duplicate(Card(3)))
+        );
+    }
+
     #[test]
     fn test_hands_no_duplicates() {
         let hands = Hands::new();
@@ -298,6 +677,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hands_starting_at_matches_iteration_order() {
+        for index in [0, 1, 45, 46, 1000, 123_456] {
+            assert_eq!(
+                Hands::starting_at(index).next(),
+                Hands::new().nth(index),
+                "hand {}",
+                index
+            );
+        }
+        let last = Hands::starting_at(ALL_HANDS - 1).next().unwrap();
+        assert_eq!(last, Hand([45, 46, 47, 48, 49, 50, 51].map(Card)));
+        assert_eq!(Hands::starting_at(ALL_HANDS - 1).count(), 1);
+        assert!(Hands::starting_at(ALL_HANDS).next().is_none());
+    }
+
+    #[test]
+    fn test_colex_index_endpoints() {
+        let first = Hands::new().next().unwrap();
+        assert_eq!(first.colex_index(), 0);
+
+        let last = Hand([45, 46, 47, 48, 49, 50, 51].map(Card));
+        assert_eq!(last.colex_index(), ALL_HANDS - 1);
+        assert_eq!(binomial(52, 7), ALL_HANDS);
+    }
+
+    /// Every 7-card hand drawn from the 12 cards starting at `offset`
+    fn hands_from_window(offset: u8) -> Vec<Hand> {
+        (0u32..1 << 12)
+            .filter(|mask| mask.count_ones() == 7)
+            .map(|mask| {
+                let mut cards = [Card::default(); 7];
+                let mut i = 0;
+                for bit in 0..12 {
+                    if mask & (1 << bit) != 0 {
+                        cards[i] = Card(offset + bit as u8);
+                        i += 1;
+                    }
+                }
+                Hand(cards)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_colex_index_is_a_bijection_on_first_and_last_hands() {
+        // In colex order, the hands using only the lowest 12 cards come first,
+        // so they must map exactly onto 0..C(12, 7)
+        let mut indices: Vec<usize> = hands_from_window(0)
+            .iter()
+            .map(|h| h.colex_index())
+            .collect();
+        indices.sort();
+        assert_eq!(indices, (0..binomial(12, 7)).collect::<Vec<_>>());
+
+        // The hands using only the highest 12 cards must be distinct, in range,
+        // and include the very last index
+        let mut indices: Vec<usize> = hands_from_window(40)
+            .iter()
+            .map(|h| h.colex_index())
+            .collect();
+        indices.sort();
+        indices.dedup();
+        assert_eq!(indices.len(), binomial(12, 7));
+        assert_eq!(*indices.last().unwrap(), ALL_HANDS - 1);
+    }
+
+    #[test]
+    fn test_colex_iteration_follows_colex_index() {
+        assert_eq!(colex_hands_from(0).next().unwrap().colex_index(), 0);
+        assert!(colex_hands_from(ALL_HANDS).next().is_none());
+        assert_eq!(colex_hands_from(ALL_HANDS - 1).count(), 1);
+        for start in [0, 12_345, 50_000_000, ALL_HANDS - 3_000] {
+            for (i, hand) in colex_hands_from(start).take(2_000).enumerate() {
+                assert!(hand.0.is_sorted(), "{:?}", hand);
+                assert_eq!(hand.colex_index(), start + i);
+                assert_eq!(Hand::from_colex_index(start + i), hand);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hand_category_roundtrip() {
+        for rank in 1..=9u8 {
+            let category = HandCategory::from(rank);
+            assert_eq!(u8::from(category), rank);
+        }
+        assert!(HandCategory::StraightFlush > HandCategory::FourOfAKind);
+        assert_eq!(HandCategory::FullHouse.to_string(), "Full House");
+    }
+
+    #[test]
+    fn test_score_category_accessor() {
+        // Full house: 5♣ 5♦ 5♠ 7♥ 7♣ + two extra cards 2♥ 3♦
+        let hand = Hand([
+            Card::new(Value::new(2), Suit::Hearts),
+            Card::new(Value::new(3), Suit::Diamonds),
+            Card::new(Value::new(5), Suit::Clubs),
+            Card::new(Value::new(5), Suit::Diamonds),
+            Card::new(Value::new(5), Suit::Spades),
+            Card::new(Value::new(7), Suit::Hearts),
+            Card::new(Value::new(7), Suit::Clubs),
+        ]);
+        let entry: TableEntry = hand.score().into();
+        assert_eq!(entry.category(), HandCategory::FullHouse);
+        assert_eq!(entry.category().to_string(), "Full House");
+    }
+
     #[test]
     fn test_score_high_card() {
         // High card: 2♣ 4♦ 6♠ 9♥ K♣ + two extra cards 3♥ 8♦
@@ -468,4 +955,595 @@ mod tests {
         assert_eq!(entry.rank, 9); // Straight flush (royal flush is the highest straight flush)
         assert_eq!(entry.hi, 14); // Ace high (14)
     }
+
+    #[test]
+    fn test_pair_kicker_breaks_tie() {
+        // Both hands: pair of Kings with a 9 kicker, second kicker differs (7 vs 6)
+        let better = Hand([
+            Card::new(Value::new(2), Suit::Hearts),
+            Card::new(Value::new(3), Suit::Diamonds),
+            Card::new(Value::new(4), Suit::Clubs),
+            Card::new(Value::new(7), Suit::Spades),
+            Card::new(Value::new(9), Suit::Hearts),
+            Card::new(Value::new(13), Suit::Clubs),
+            Card::new(Value::new(13), Suit::Diamonds),
+        ]);
+        let worse = Hand([
+            Card::new(Value::new(2), Suit::Spades),
+            Card::new(Value::new(3), Suit::Clubs),
+            Card::new(Value::new(4), Suit::Hearts),
+            Card::new(Value::new(6), Suit::Spades),
+            Card::new(Value::new(9), Suit::Diamonds),
+            Card::new(Value::new(13), Suit::Hearts),
+            Card::new(Value::new(13), Suit::Spades),
+        ]);
+        let better: TableEntry = better.score().into();
+        let worse: TableEntry = worse.score().into();
+        assert_eq!(better.rank, 2);
+        assert_eq!(worse.rank, 2);
+        assert_eq!(better.kickers, [13, 13, 9, 7, 4]);
+        assert_eq!(worse.kickers, [13, 13, 9, 6, 4]);
+        assert!(better > worse);
+    }
+
+    #[test]
+    fn test_flush_second_card_breaks_tie() {
+        // Both hands: Ace-high club flush, second card differs (Q vs J)
+        let better = Hand([
+            Card::new(Value::new(1), Suit::Clubs),
+            Card::new(Value::new(3), Suit::Clubs),
+            Card::new(Value::new(5), Suit::Clubs),
+            Card::new(Value::new(8), Suit::Clubs),
+            Card::new(Value::new(12), Suit::Clubs),
+            Card::new(Value::new(2), Suit::Hearts),
+            Card::new(Value::new(4), Suit::Diamonds),
+        ]);
+        let worse = Hand([
+            Card::new(Value::new(1), Suit::Hearts),
+            Card::new(Value::new(3), Suit::Hearts),
+            Card::new(Value::new(5), Suit::Hearts),
+            Card::new(Value::new(8), Suit::Hearts),
+            Card::new(Value::new(11), Suit::Hearts),
+            Card::new(Value::new(2), Suit::Clubs),
+            Card::new(Value::new(4), Suit::Diamonds),
+        ]);
+        let better: TableEntry = better.score().into();
+        let worse: TableEntry = worse.score().into();
+        assert_eq!(better.rank, 6);
+        assert_eq!(worse.rank, 6);
+        assert_eq!(better.hi, worse.hi);
+        assert_eq!(better.kickers, [14, 12, 8, 5, 3]);
+        assert!(better > worse);
+        assert!(better.to_score() > worse.to_score());
+    }
+
+    #[test]
+    fn test_full_house_kickers_order_trips_first() {
+        // Full house: 5♣ 5♦ 5♠ 7♥ 7♣ should compare on the fives before the sevens
+        let hand = Hand([
+            Card::new(Value::new(2), Suit::Hearts),
+            Card::new(Value::new(3), Suit::Diamonds),
+            Card::new(Value::new(5), Suit::Clubs),
+            Card::new(Value::new(5), Suit::Diamonds),
+            Card::new(Value::new(5), Suit::Spades),
+            Card::new(Value::new(7), Suit::Hearts),
+            Card::new(Value::new(7), Suit::Clubs),
+        ]);
+        let entry = hand.score();
+        assert_eq!(entry.rank, 7);
+        assert_eq!(entry.kickers, [5, 5, 5, 7, 7]);
+    }
+
+    /// Build a hand from (value, suit) pairs, sorted like the table expects
+    fn hand(cards: [(u8, Suit); 7]) -> Hand {
+        let mut cards = cards.map(|(value, suit)| Card::new(Value::new(value), suit));
+        cards.sort();
+        Hand(cards)
+    }
+
+    #[test]
+    fn test_describe_every_category() {
+        use Suit::*;
+        let cases = [
+            (
+                hand([
+                    (2, Clubs),
+                    (3, Hearts),
+                    (4, Diamonds),
+                    (6, Spades),
+                    (8, Diamonds),
+                    (9, Hearts),
+                    (13, Clubs),
+                ]),
+                "High card, King",
+            ),
+            (
+                hand([
+                    (2, Clubs),
+                    (3, Hearts),
+                    (4, Diamonds),
+                    (6, Spades),
+                    (6, Diamonds),
+                    (9, Hearts),
+                    (13, Clubs),
+                ]),
+                "Pair of Sixes",
+            ),
+            (
+                hand([
+                    (2, Clubs),
+                    (4, Hearts),
+                    (4, Diamonds),
+                    (6, Spades),
+                    (8, Diamonds),
+                    (13, Hearts),
+                    (13, Clubs),
+                ]),
+                "Two pair, Kings and Fours",
+            ),
+            (
+                hand([
+                    (2, Clubs),
+                    (3, Hearts),
+                    (7, Diamonds),
+                    (7, Spades),
+                    (7, Hearts),
+                    (9, Hearts),
+                    (13, Clubs),
+                ]),
+                "Three of a kind, Sevens",
+            ),
+            (
+                hand([
+                    (1, Clubs),
+                    (2, Hearts),
+                    (3, Diamonds),
+                    (4, Spades),
+                    (5, Diamonds),
+                    (9, Hearts),
+                    (13, Clubs),
+                ]),
+                "Five-high straight",
+            ),
+            (
+                hand([
+                    (1, Clubs),
+                    (3, Clubs),
+                    (5, Clubs),
+                    (8, Clubs),
+                    (12, Clubs),
+                    (2, Hearts),
+                    (4, Diamonds),
+                ]),
+                "Ace-high flush",
+            ),
+            (
+                hand([
+                    (4, Clubs),
+                    (4, Hearts),
+                    (13, Diamonds),
+                    (13, Spades),
+                    (13, Hearts),
+                    (9, Hearts),
+                    (2, Clubs),
+                ]),
+                "Full house, Kings full of Fours",
+            ),
+            (
+                hand([
+                    (5, Clubs),
+                    (5, Hearts),
+                    (5, Diamonds),
+                    (5, Spades),
+                    (8, Diamonds),
+                    (9, Hearts),
+                    (13, Clubs),
+                ]),
+                "Four of a kind, Fives",
+            ),
+            (
+                hand([
+                    (5, Clubs),
+                    (6, Clubs),
+                    (7, Clubs),
+                    (8, Clubs),
+                    (9, Clubs),
+                    (2, Hearts),
+                    (3, Diamonds),
+                ]),
+                "Nine-high straight flush",
+            ),
+            (
+                hand([
+                    (1, Clubs),
+                    (10, Clubs),
+                    (11, Clubs),
+                    (12, Clubs),
+                    (13, Clubs),
+                    (2, Hearts),
+                    (3, Diamonds),
+                ]),
+                "Royal flush",
+            ),
+        ];
+        for (hand, expected) in cases {
+            let entry: TableEntry = hand.score().into();
+            assert_eq!(entry.describe(), expected);
+        }
+    }
+
+    #[test]
+    fn test_omaha_needs_two_hole_cards_for_a_flush() {
+        use Suit::*;
+        // Board: 2♥ 5♥ 9♥ J♥ K♣, hole: A♥ 3♣ 4♠ 7♦
+        // Hold'em would make an ace-high flush with one heart, but Omaha needs two
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let common = Common(vec![
+            card(2, Hearts),
+            card(5, Hearts),
+            card(9, Hearts),
+            card(11, Hearts),
+            card(13, Clubs),
+        ]);
+        let player = OmahaPlayer([
+            card(1, Hearts),
+            card(3, Clubs),
+            card(4, Spades),
+            card(7, Diamonds),
+        ]);
+
+        let entry = player.score(&common);
+        assert_eq!(entry.category(), HandCategory::HighCard);
+        assert_eq!(entry.kickers, [14, 13, 11, 9, 7]);
+
+        // With a second heart in hand the flush is available
+        let player = OmahaPlayer([
+            card(1, Hearts),
+            card(3, Hearts),
+            card(4, Spades),
+            card(7, Diamonds),
+        ]);
+        let entry = player.score(&common);
+        assert_eq!(entry.category(), HandCategory::Flush);
+        assert_eq!(entry.kickers, [14, 11, 9, 5, 3]);
+    }
+
+    #[test]
+    fn test_ranked_hands_each_beat_the_one_before() {
+        let score = |hand: &[&str; 5]| score_five_cards(hand.map(|c| c.parse().unwrap()));
+        for pair in crate::ranked_hands::RANKED_HANDS.windows(2) {
+            let (lower, higher) = (&pair[0], &pair[1]);
+            assert!(
+                score(lower) < score(higher),
+                "{lower:?} should lose to {higher:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ace_high_and_ace_low_straights() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let ace = card(1, Spades);
+
+        let broadway = [
+            ace,
+            card(13, Clubs),
+            card(12, Hearts),
+            card(11, Spades),
+            card(10, Clubs),
+        ];
+        let entry = score_five_cards(broadway);
+        assert_eq!(entry.category(), HandCategory::Straight);
+        assert_eq!(entry.hi, ace.rank_value());
+        assert_eq!(entry.kickers, [14, 13, 12, 11, 10]);
+
+        let five = card(5, Hearts);
+        let wheel = [
+            ace,
+            card(2, Clubs),
+            card(3, Hearts),
+            card(4, Diamonds),
+            five,
+        ];
+        let entry = score_five_cards(wheel);
+        assert_eq!(entry.category(), HandCategory::Straight);
+        assert_eq!(entry.hi, five.rank_value());
+        assert_eq!(entry.kickers[4], ace.straight_low_value());
+        assert!(entry < score_five_cards(broadway));
+
+        // The ace can't sit in the middle of a straight
+        let wraparound = [
+            card(12, Clubs),
+            card(13, Hearts),
+            ace,
+            card(2, Spades),
+            card(3, Clubs),
+        ];
+        assert_eq!(
+            score_five_cards(wraparound).category(),
+            HandCategory::HighCard
+        );
+    }
+
+    #[test]
+    fn test_early_exit_matches_the_full_search() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        // Pools dense in straight flushes and quads, so every early exit gets exercised
+        let pools = [
+            vec![
+                card(1, Spades),
+                card(13, Spades),
+                card(12, Spades),
+                card(11, Spades),
+                card(10, Spades),
+                card(9, Spades),
+                card(8, Spades),
+                card(1, Hearts),
+                card(1, Diamonds),
+                card(1, Clubs),
+            ],
+            vec![
+                card(1, Hearts),
+                card(2, Hearts),
+                card(3, Hearts),
+                card(4, Hearts),
+                card(5, Hearts),
+                card(6, Hearts),
+                card(7, Hearts),
+                card(7, Spades),
+                card(7, Diamonds),
+                card(7, Clubs),
+                card(13, Clubs),
+            ],
+        ];
+        for pool in pools {
+            for idx in combinations::<7>(pool.len()) {
+                let hand = Hand::from_cards(idx.map(|i| pool[i])).unwrap();
+                let entry: TableEntry = hand.score().into();
+                assert_eq!(entry, score_best_five(&hand.0), "{:?}", hand);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_deck_ace_six_wheel() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let wheel = [
+            card(1, Clubs),
+            card(6, Hearts),
+            card(7, Spades),
+            card(8, Diamonds),
+            card(9, Clubs),
+        ];
+
+        // Not a straight in a full deck
+        assert_eq!(score_five_cards(wheel).category(), HandCategory::HighCard);
+
+        let entry = score_five_cards_short(wheel);
+        assert_eq!(entry.category(), HandCategory::Straight);
+        assert_eq!(entry.hi, 9);
+
+        // It's the lowest straight: 6-7-8-9-T beats it
+        let six_high = score_five_cards_short([
+            card(6, Clubs),
+            card(7, Hearts),
+            card(8, Spades),
+            card(9, Diamonds),
+            card(10, Clubs),
+        ]);
+        assert!(six_high.cmp_short_deck(&entry).is_gt());
+
+        // And it's found within seven cards
+        let hand = hand([
+            (1, Clubs),
+            (6, Hearts),
+            (7, Spades),
+            (8, Diamonds),
+            (9, Clubs),
+            (11, Hearts),
+            (13, Hearts),
+        ]);
+        let entry = best_five_of(&hand.0, score_five_cards_short, TableEntry::cmp_short_deck);
+        assert_eq!(entry.category(), HandCategory::Straight);
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let flush = score_five_cards_short([
+            card(6, Hearts),
+            card(8, Hearts),
+            card(10, Hearts),
+            card(11, Hearts),
+            card(13, Hearts),
+        ]);
+        let full_house = score_five_cards_short([
+            card(1, Clubs),
+            card(1, Hearts),
+            card(1, Spades),
+            card(13, Diamonds),
+            card(13, Clubs),
+        ]);
+        assert_eq!(flush.category(), HandCategory::Flush);
+        assert_eq!(full_house.category(), HandCategory::FullHouse);
+
+        // Full-deck ordering is unchanged, short-deck ordering swaps them
+        assert!(flush < full_house);
+        assert!(flush.cmp_short_deck(&full_house).is_gt());
+        assert!(full_house.cmp_short_deck(&flush).is_lt());
+    }
+
+    #[test]
+    fn test_lowball_wheel_is_the_nut_low() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        // A suited wheel: a straight flush for high, but just the best low
+        let wheel = score_five_cards_lowball([
+            card(1, Hearts),
+            card(2, Hearts),
+            card(3, Hearts),
+            card(4, Hearts),
+            card(5, Hearts),
+        ]);
+        assert_eq!(wheel.category(), HandCategory::HighCard);
+        assert_eq!(wheel.kickers, [5, 4, 3, 2, 1]);
+
+        let six_low = score_five_cards_lowball([
+            card(1, Clubs),
+            card(2, Hearts),
+            card(3, Spades),
+            card(4, Diamonds),
+            card(6, Clubs),
+        ]);
+        let six_five = score_five_cards_lowball([
+            card(2, Clubs),
+            card(3, Hearts),
+            card(4, Spades),
+            card(5, Diamonds),
+            card(6, Clubs),
+        ]);
+        assert!(wheel.cmp_lowball(&six_low).is_gt());
+        // Aces are low, so 6-4-3-2-A beats 6-5-4-3-2
+        assert!(six_low.cmp_lowball(&six_five).is_gt());
+        assert!(six_low.cmp_lowball(&six_low).is_eq());
+
+        let hand = hand([
+            (1, Clubs),
+            (2, Hearts),
+            (3, Spades),
+            (4, Diamonds),
+            (5, Clubs),
+            (13, Hearts),
+            (13, Spades),
+        ]);
+        let best = best_five_of(&hand.0, score_five_cards_lowball, TableEntry::cmp_lowball);
+        assert_eq!(best, wheel);
+    }
+
+    #[test]
+    fn test_lowball_pair_is_worse_than_any_no_pair() {
+        use Suit::*;
+        let card = |value, suit| Card::new(Value::new(value), suit);
+        let aces = score_five_cards_lowball([
+            card(1, Clubs),
+            card(1, Hearts),
+            card(2, Spades),
+            card(3, Diamonds),
+            card(4, Clubs),
+        ]);
+        let king_high = score_five_cards_lowball([
+            card(13, Clubs),
+            card(12, Hearts),
+            card(11, Spades),
+            card(10, Diamonds),
+            card(8, Clubs),
+        ]);
+        let deuces = score_five_cards_lowball([
+            card(2, Clubs),
+            card(2, Hearts),
+            card(3, Spades),
+            card(4, Diamonds),
+            card(5, Clubs),
+        ]);
+        assert_eq!(aces.category(), HandCategory::Pair);
+        assert!(king_high.cmp_lowball(&aces).is_gt());
+        // Between pairs, the lower pair wins
+        assert!(aces.cmp_lowball(&deuces).is_gt());
+    }
+
+    #[test]
+    fn test_five_card_lookup_matches_direct_scoring() {
+        let first = [0, 1, 2, 3, 4].map(Card);
+        let last = [47, 48, 49, 50, 51].map(Card);
+        assert_eq!(five_card_index(first), 0);
+        assert_eq!(five_card_index(last), FIVE_CARD_HANDS - 1);
+
+        let mut rng = oorandom::Rand32::new(3);
+        for _ in 0..10_000 {
+            // Deliberately unsorted
+            let cards: [Card; 5] = random_seven(&mut rng)[..5].try_into().unwrap();
+            assert_eq!(
+                five_card_scores()[five_card_index(cards)],
+                score_five_cards(cards)
+            );
+        }
+    }
+
+    #[test]
+    fn test_best_five_of_matches_seven_card_score() {
+        let mut rng = oorandom::Rand32::new(11);
+        for _ in 0..500 {
+            let mut cards = random_seven(&mut rng);
+            cards.sort();
+            let expected: TableEntry = Hand(cards).score().into();
+            assert_eq!(score_best_five(&cards), expected);
+            // Fewer cards can only do worse
+            assert!(score_best_five(&cards[..6]) <= expected);
+            assert_eq!(
+                score_best_five(&cards[..5]),
+                score_five_cards(cards[..5].try_into().unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_score_best_five_on_the_flop_turn_and_river() {
+        let cards: Vec<Card> = ["As", "Ks", "Qs", "Js", "2c", "Th", "3s"]
+            .iter()
+            .map(|card| card.parse().unwrap())
+            .collect();
+        // The flop gives A♠ K♠ high, the turn makes Broadway, and the river the flush
+        let flop = score_best_five(&cards[..5]);
+        assert_eq!(flop.category(), HandCategory::HighCard);
+        assert_eq!(flop.kickers, [14, 13, 12, 11, 2]);
+        let turn = score_best_five(&cards[..6]);
+        assert_eq!(turn.category(), HandCategory::Straight);
+        assert_eq!(turn.kickers, [14, 13, 12, 11, 10]);
+        let river = score_best_five(&cards);
+        assert_eq!(river.category(), HandCategory::Flush);
+        assert_eq!(river.kickers, [14, 13, 12, 11, 3]);
+        assert_eq!(
+            river,
+            Hand::from_cards(cards.try_into().unwrap())
+                .unwrap()
+                .score()
+                .into()
+        );
+    }
+
+    #[test]
+    fn test_best_five_cards_score_to_the_entry() {
+        use Suit::*;
+        // Two pair, kings and sevens, with an ace kicker
+        let two_pair = hand([
+            (13, Spades),
+            (13, Hearts),
+            (7, Diamonds),
+            (7, Clubs),
+            (1, Spades),
+            (4, Clubs),
+            (2, Hearts),
+        ]);
+        let (cards, entry) = two_pair.best_five();
+        assert_eq!(entry, two_pair.score().into());
+        assert_eq!(score_five_cards(cards), entry);
+        let mut values: Vec<u8> = cards.iter().map(|c| c.into_inner().0.0).collect();
+        values.sort();
+        assert_eq!(values, [1, 7, 7, 13, 13]);
+
+        let mut rng = oorandom::Rand32::new(17);
+        for _ in 0..500 {
+            let mut cards = random_seven(&mut rng);
+            cards.sort();
+            let hand = Hand(cards);
+            let (five, entry) = hand.best_five();
+            assert_eq!(score_five_cards(five), entry);
+            assert_eq!(entry, hand.score().into());
+            assert!(five.iter().all(|card| cards.contains(card)));
+        }
+    }
 }
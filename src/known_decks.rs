@@ -0,0 +1,17 @@
+//! Decks whose win counts are known, as card ids
+
+/// Wins every cut for three players: the `q`th card dealt has value `q mod 13` and
+/// suit `q mod 4`, and cards are dealt from the end of the deck
+pub fn three_player_perfect_deck() -> [u8; 52] {
+    std::array::from_fn(|i| {
+        let q = (51 - i) as u8;
+        q % 4 * 13 + q % 13
+    })
+}
+
+/// `three_player_perfect_deck` with its first two cards swapped, which loses cuts 1, 2, 14, and 15
+pub fn three_player_swapped_deck() -> [u8; 52] {
+    let mut deck = three_player_perfect_deck();
+    deck.swap(0, 1);
+    deck
+}
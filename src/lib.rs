@@ -0,0 +1,17 @@
+//! Searching for deck orders where the dealer wins at every cut
+//! The `poker_wins` binary is a command line over these modules
+
+pub mod card_ids;
+pub mod cards;
+pub mod combinations;
+pub mod deck;
+pub mod evaluator;
+pub mod game;
+pub mod hands;
+pub mod known_decks;
+pub mod precompute;
+pub mod ranked_hands;
+#[cfg(test)]
+mod reference_scoring;
+pub mod search;
+pub mod viz;
@@ -1,19 +1,15 @@
-mod cards;
-mod deck;
-mod game;
-mod hands;
-mod precompute;
-mod search;
-mod viz;
-
 use clap::{Parser, Subcommand};
-use std::io::{self, stdout};
+use poker_wins::{cards, deck, evaluator, game, hands, precompute, search, viz};
+use std::io::{self, Write, stdout};
 use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "poker_wins")]
 #[command(about = "Poker hand analysis tool", long_about = None)]
 struct Cli {
+    /// Don't report progress on stderr, only print the final result
+    #[arg(long, global = true)]
+    quiet: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -21,24 +17,167 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Precompute poker hand lookup table
-    Precompute,
+    Precompute {
+        /// Number of threads to score hands on (defaults to the available parallelism)
+        #[arg(short, long)]
+        threads: Option<usize>,
+        /// File to write the table to
+        #[arg(short, long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        output: PathBuf,
+    },
+    /// Rewrite a legacy lookup table in the compact format
+    ConvertTable {
+        /// Table file to convert
+        #[arg(default_value = precompute::DEFAULT_TABLE_PATH)]
+        input: PathBuf,
+    },
+    /// Build the state-machine hand evaluator and save it
+    /// Other commands take the file as `--table` in place of the much larger precomputed table
+    BuildEvaluator {
+        /// File to write the evaluator to
+        #[arg(short, long, default_value = evaluator::DEFAULT_EVALUATOR_PATH)]
+        output: PathBuf,
+    },
+    /// Compare how fast the evaluator and the precomputed table score random hands
+    BenchEvaluator {
+        /// Number of random hands to score
+        #[arg(short, long, default_value = "10000000")]
+        samples: usize,
+        /// Evaluator file
+        #[arg(long, default_value = evaluator::DEFAULT_EVALUATOR_PATH)]
+        evaluator: PathBuf,
+        /// Precomputed table file
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
+    },
     /// Search for optimal deck configuration
-    Search {
+    Search(Box<SearchArgs>),
+    /// Run genetic, island, beam, aco, and simulated-annealing with the same seed and timeout,
+    /// and compare the best deck each finds
+    Bench {
         /// Number of players (including dealer)
         #[arg(short, long, default_value = "2")]
         num_players: usize,
-        /// Search algorithm to use: genetic, island, beam, aco, simulated-annealing, hill-climbing
-        #[arg(short, long, default_value = "genetic")]
-        algorithm: String,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
+        /// Seconds each algorithm runs for
+        #[arg(long, default_value = "60")]
+        timeout: u64,
+        /// RNG seed shared by every algorithm
+        #[arg(long, default_value = "4")]
+        seed: u64,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
     },
     /// Analyze problem difficulty for given player count
     Analyze {
         /// Number of players (including dealer)
         #[arg(short, long, default_value = "2")]
         num_players: usize,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
         /// Number of random samples to test
         #[arg(short, long, default_value = "10000")]
         samples: usize,
+        /// Estimate by scoring only this many random cuts per deck (faster, approximate)
+        #[arg(long)]
+        cuts_per_deck: Option<usize>,
+        /// Analyze every player count from 2 up to this one, with a summary line for each
+        #[arg(long, conflicts_with = "cuts_per_deck")]
+        max_players: Option<usize>,
+        /// Sample new deck order riffle-shuffled this many times instead of uniform shuffles
+        #[arg(long, conflicts_with = "cuts_per_deck")]
+        riffles: Option<usize>,
+        /// Also show how often the dealer wins at each cut position
+        /// With --format json this is printed as a second JSON line
+        #[arg(long, conflicts_with_all = ["cuts_per_deck", "max_players"])]
+        positions: bool,
+        /// Number of threads to sample on (defaults to the available parallelism)
+        #[arg(short, long)]
+        threads: Option<usize>,
+        /// Which game to deal and score
+        #[arg(long, value_enum, default_value_t)]
+        variant: game::GameVariant,
+        /// How to print the statistics
+        #[arg(long, value_enum, default_value_t)]
+        format: search::OutputFormat,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
+    },
+    /// Score a deck (e.g. from the SMT solver) at every cut position
+    Verify {
+        /// Deck as 52 comma-separated card ids
+        deck: String,
+        /// Number of players (including dealer)
+        #[arg(short, long, default_value = "2")]
+        num_players: usize,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
+        /// Deal the board without burning a card before the flop, turn, and river
+        #[arg(long)]
+        no_burns: bool,
+        /// Community cards to deal: 3 stops at the flop, more than 5 extends the river
+        #[arg(long, default_value_t = hands::BOARD_SIZE)]
+        board_size: usize,
+        /// Which game to deal and score
+        #[arg(long, value_enum, default_value_t)]
+        variant: game::GameVariant,
+        /// Also list where every card goes when the deck is cut at this position
+        #[arg(long, value_name = "CUT")]
+        trace: Option<usize>,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
+    },
+    /// Show the dealer's and best opponent's hands and the margin at every cut position
+    Positions {
+        /// Deck as 52 comma-separated card ids
+        deck: String,
+        /// Number of players (including dealer)
+        #[arg(short, long, default_value = "2")]
+        num_players: usize,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
+        /// Also show the dealer's equity over every board the unseen cards could make
+        /// This scores over a million boards per cut, so it takes a while
+        #[arg(long)]
+        equity: bool,
+        /// Also list where every card goes when the deck is cut at this position
+        #[arg(long, value_name = "CUT")]
+        trace: Option<usize>,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
+    },
+    /// Find the cut position that gives the dealer the biggest margin for a fixed deck
+    BestCut {
+        /// Deck as 52 comma-separated card ids
+        deck: String,
+        /// Number of players (including dealer)
+        #[arg(short, long, default_value = "2")]
+        num_players: usize,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
+    },
+    /// Preflop all-in equity between two Hold'em hands over every possible board
+    HeadsUp {
+        /// First player's hole cards, e.g. "As,Ah"
+        hand: String,
+        /// Second player's hole cards, e.g. "Ks,Kh"
+        other: String,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
     },
     /// Export an interactive fitness-landscape visualization as a self-contained HTML file
     Viz {
@@ -54,44 +193,543 @@ enum Commands {
         /// RNG seed
         #[arg(long, default_value = "4")]
         seed: u64,
+        /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+        #[arg(long)]
+        real: bool,
+        /// Precomputed table file, or an evaluator file from build-evaluator
+        #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+        table: PathBuf,
     },
 }
 
+/// The options of `search`, boxed in `Commands` so the enum stays small as flags are added
+#[derive(clap::Args)]
+struct SearchArgs {
+    /// Number of players (including dealer)
+    #[arg(short, long, default_value = "2")]
+    num_players: usize,
+    /// Only score the cut positions a real dealer would allow (5 through 46), out of 42
+    #[arg(long)]
+    real: bool,
+    /// Search algorithm to use: genetic, island, beam, aco, simulated-annealing,
+    /// simulated-annealing-deterministic, hill-climbing, tabu
+    #[arg(short, long, default_value = "genetic")]
+    algorithm: String,
+    /// Population size for genetic search (per island for island search)
+    #[arg(long)]
+    pop_size: Option<usize>,
+    /// Generations before genetic search gives up
+    #[arg(long)]
+    max_generations: Option<usize>,
+    /// Base mutation rate for genetic and island search
+    #[arg(long)]
+    mutation_rate: Option<f32>,
+    /// Keep genetic search survivors by Pareto rank on wins and margin, not wins alone
+    #[arg(long)]
+    pareto: bool,
+    /// Number of islands for island search
+    #[arg(long)]
+    islands: Option<usize>,
+    /// Stop island search after this many migration cycles
+    #[arg(long)]
+    max_cycles: Option<usize>,
+    /// Beam width for beam search
+    #[arg(long)]
+    beam_width: Option<usize>,
+    /// Number of ants for ant colony search
+    #[arg(long)]
+    ants: Option<usize>,
+    /// Number of threads for simulated annealing
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Accept simulated-annealing moves on margins as well as wins (see `hybrid_score`)
+    #[arg(long)]
+    sa_hybrid: bool,
+    /// Wins to take off a deck's hybrid score for looking nothing like a real shuffle
+    /// Only beam search and simulated annealing with --sa-hybrid rank on the hybrid score
+    #[arg(long)]
+    plausibility_weight: Option<f64>,
+    /// Which mutations simulated annealing (and genetic search's local refinement) may try
+    #[arg(long, value_enum, default_value_t)]
+    mutation_policy: deck::MutationPolicy,
+    /// Have genetic search pick each mating's crossover from these (comma-separated),
+    /// favouring the ones that breed improvements, instead of always using two-point
+    #[arg(long, value_enum, value_delimiter = ',')]
+    crossover: Vec<deck::CrossoverOperator>,
+    /// How genetic and beam search measure how different two decks are
+    #[arg(long, value_enum, default_value_t)]
+    diversity_metric: deck::DeckDistance,
+    /// Chance (0 to 1) that a hill-climbing or simulated-annealing restart perturbs the
+    /// best deck instead of reshuffling
+    #[arg(long)]
+    restart_perturbation: Option<f32>,
+    /// Mutations applied to the best deck on a perturbed restart
+    #[arg(long)]
+    perturbation_strength: Option<usize>,
+    /// Save the best deck here during simulated annealing, and resume from it if it exists
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+    /// Start genetic or beam search from this deck, as 52 comma-separated card ids
+    /// Repeat to seed more of the initial population
+    #[arg(long)]
+    seed_deck: Vec<String>,
+    /// Start genetic or beam search from this many decks built a card at a time to win
+    /// each cut, ahead of random ones
+    #[arg(long)]
+    heuristic_seeds: Option<usize>,
+    /// How to print the deck found
+    #[arg(long, value_enum, default_value_t)]
+    format: search::OutputFormat,
+    /// Print this many of the best distinct decks genetic search found, instead of one
+    #[arg(long)]
+    top: Option<usize>,
+    /// RNG seed (defaults to one derived from the system time)
+    /// A fixed seed makes single-threaded algorithms fully reproducible
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Stop after this many seconds and print the best deck found so far
+    #[arg(long)]
+    timeout: Option<u64>,
+    /// Optimize for this seat's wins instead of the dealer's (seat 0)
+    #[arg(long, default_value_t = 0)]
+    target_player: usize,
+    /// Comma-separated seats still in the hand, which the target must beat
+    /// (defaults to every other seat)
+    #[arg(long, value_delimiter = ',')]
+    active_opponents: Option<Vec<usize>>,
+    /// Opponents fold unpaired hole cards below this rank (2 to 14, aces high), and then
+    /// don't have to be beaten (by default every opponent plays to showdown)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(2..=14))]
+    fold_below: Option<u8>,
+    /// Deal the board without burning a card before the flop, turn, and river
+    #[arg(long)]
+    no_burns: bool,
+    /// Community cards to deal: 3 stops at the flop, more than 5 extends the river
+    #[arg(long, default_value_t = hands::BOARD_SIZE)]
+    board_size: usize,
+    /// Which game to deal and score
+    #[arg(long, value_enum, default_value_t)]
+    variant: game::GameVariant,
+    /// Precomputed table file, or an evaluator file from build-evaluator
+    #[arg(long, default_value = precompute::DEFAULT_TABLE_PATH)]
+    table: PathBuf,
+}
+
+/// Searches and analyses sample full 52-card decks, so can't deal the short-deck game
+fn check_full_deck(variant: game::GameVariant) -> io::Result<()> {
+    if variant == game::GameVariant::ShortDeck {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "short-deck games are dealt from 36 cards; only verify scores short decks",
+        ));
+    }
+    Ok(())
+}
+
+/// The dealing procedure the `--no-burns`, `--board-size` and `--variant` flags ask for
+fn deal_config(
+    no_burns: bool,
+    board_size: usize,
+    variant: game::GameVariant,
+) -> io::Result<game::DealConfig> {
+    let config = if no_burns {
+        game::DealConfig::no_burns()
+    } else {
+        game::DealConfig::default()
+    };
+    config
+        .with_variant(variant)
+        .with_board_size(board_size)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// `--trace` has to name one of the deck's cut positions
+fn check_trace_cut(trace: Option<usize>, deck: &deck::Deck) -> io::Result<()> {
+    match trace {
+        Some(cut_pos) if cut_pos >= deck.len() => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("can't cut a {}-card deck at {cut_pos}", deck.len()),
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Precompute => {
-            precompute::precompute(stdout())?;
+        Commands::Precompute { threads, output } => {
+            let threads = threads.unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            });
+            precompute::precompute_to_file(&output, threads, cli.quiet)?;
+        }
+        Commands::ConvertTable { input } => {
+            let f = std::fs::File::open(input)?;
+            precompute::convert_table(f, stdout())?;
         }
-        Commands::Search { num_players, algorithm } => {
-            let search_fn: search::SearchFn = match algorithm.as_str() {
-                "genetic" => search::genetic_search,
-                "island" => search::island_genetic_search,
-                "beam" => search::beam_search,
-                "aco" => search::ant_colony_search,
-                "simulated-annealing" => search::simulated_annealing,
-                "hill-climbing" | "hill" => search::hill_climbing,
+        Commands::BuildEvaluator { output } => {
+            let evaluator = evaluator::Evaluator::build();
+            let mut output = io::BufWriter::new(std::fs::File::create(output)?);
+            evaluator.write(&mut output)?;
+            output.flush()?;
+        }
+        Commands::BenchEvaluator {
+            samples,
+            evaluator,
+            table,
+        } => {
+            let evaluator = evaluator::load_evaluator_file(&evaluator)?;
+            let table = precompute::load_table_file(&table)?;
+            evaluator::compare_throughput(&evaluator, &table, samples, 4);
+        }
+        Commands::Search(args) => {
+            let SearchArgs {
+                num_players,
+                real,
+                mut algorithm,
+                pop_size,
+                max_generations,
+                mutation_rate,
+                pareto,
+                islands,
+                max_cycles,
+                beam_width,
+                ants,
+                threads,
+                sa_hybrid,
+                plausibility_weight,
+                mutation_policy,
+                crossover,
+                diversity_metric,
+                restart_perturbation,
+                perturbation_strength,
+                checkpoint,
+                seed_deck,
+                heuristic_seeds,
+                format,
+                top,
+                seed,
+                timeout,
+                target_player,
+                active_opponents,
+                fold_below,
+                no_burns,
+                board_size,
+                variant,
+                table,
+            } = *args;
+            let seed = seed.unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or(4)
+            });
+            check_full_deck(variant)?;
+            let deal = deal_config(no_burns, board_size, variant)?;
+            if !cli.quiet {
+                eprintln!("Using seed {}", seed);
+            }
+            let seed_decks = seed_deck
+                .iter()
+                .map(|ids| deck::Deck::from_id_string(ids))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let defaults = search::SearchConfig::default();
+            let config = search::SearchConfig {
+                seed,
+                timeout: timeout.map(std::time::Duration::from_secs),
+                target_player,
+                active_opponents,
+                opponent_model: fold_below.map_or(game::OpponentModel::Showdown, |rank| {
+                    game::OpponentModel::FoldsBelow(rank)
+                }),
+                deal,
+                real,
+                quiet: cli.quiet,
+                pop_size: pop_size.unwrap_or(defaults.pop_size),
+                max_generations: max_generations.unwrap_or(defaults.max_generations),
+                base_mutation_rate: mutation_rate.unwrap_or(defaults.base_mutation_rate),
+                adaptive_crossover: !crossover.is_empty(),
+                crossover_operators: if crossover.is_empty() {
+                    defaults.crossover_operators.clone()
+                } else {
+                    crossover
+                },
+                pareto_selection: pareto,
+                num_islands: islands.unwrap_or(defaults.num_islands),
+                max_cycles,
+                beam_width: beam_width.unwrap_or(defaults.beam_width),
+                num_ants: ants.unwrap_or(defaults.num_ants),
+                sa_threads: threads.unwrap_or(defaults.sa_threads),
+                sa_hybrid_acceptance: sa_hybrid,
+                plausibility_weight: plausibility_weight.unwrap_or(defaults.plausibility_weight),
+                mutation_policy,
+                diversity_metric,
+                restart_perturbation_chance: restart_perturbation
+                    .unwrap_or(defaults.restart_perturbation_chance),
+                restart_perturbation_strength: perturbation_strength
+                    .unwrap_or(defaults.restart_perturbation_strength),
+                checkpoint,
+                seed_decks,
+                heuristic_seeds: heuristic_seeds.unwrap_or(defaults.heuristic_seeds),
+                ..defaults.clone()
+            };
+            let (search_fn, unit): (search::SearchFn, _) = match algorithm.as_str() {
+                "genetic" => (search::genetic_search, "Generation"),
+                "island" => (search::island_genetic_search, "Cycle"),
+                "beam" => (search::beam_search, "Iteration"),
+                "aco" => (search::ant_colony_search, "Iteration"),
+                "simulated-annealing" => (search::simulated_annealing, "Iteration"),
+                "simulated-annealing-deterministic" => {
+                    (search::simulated_annealing_deterministic, "Iteration")
+                }
+                "hill-climbing" | "hill" => (search::hill_climbing, "Restart"),
+                "tabu" => (search::tabu_search, "Iteration"),
                 _ => {
                     eprintln!("Unknown algorithm '{}'. Using genetic search.", algorithm);
-                    search::genetic_search
+                    algorithm = "genetic".to_string();
+                    (search::genetic_search, "Generation")
+                }
+            };
+            // Only beam search and hybrid simulated annealing rank decks on `hybrid_score`
+            let ranks_on_hybrid =
+                algorithm == "beam" || (sa_hybrid && algorithm.starts_with("simulated-annealing"));
+            if plausibility_weight.is_some() && !ranks_on_hybrid {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "--plausibility-weight only affects beam search and simulated annealing \
+                         with --sa-hybrid, not {algorithm}"
+                    ),
+                ));
+            }
+            if top.is_some() && algorithm != "genetic" {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("--top only works with genetic search, not {algorithm}"),
+                ));
+            }
+            let mut stderr_observer;
+            let observer: &mut dyn search::SearchObserver = if cli.quiet {
+                &mut search::QuietObserver
+            } else {
+                stderr_observer = search::StderrObserver::new(unit, config.max_wins(num_players));
+                &mut stderr_observer
+            };
+            match top {
+                Some(n) => {
+                    search::run_search_top_n(num_players, &table, &config, n, format, observer)?
                 }
+                None => search::run_search(
+                    num_players,
+                    &table,
+                    search_fn,
+                    &algorithm,
+                    &config,
+                    format,
+                    observer,
+                )?,
+            }
+        }
+        Commands::Bench {
+            num_players,
+            real,
+            timeout,
+            seed,
+            table,
+        } => {
+            let config = search::SearchConfig {
+                seed,
+                timeout: Some(std::time::Duration::from_secs(timeout)),
+                real,
+                quiet: cli.quiet,
+                ..search::SearchConfig::default()
             };
-            search::run_search(num_players, search_fn)?;
+            search::run_bench(num_players, &table, &config)?;
+        }
+        Commands::Analyze {
+            num_players,
+            real,
+            samples,
+            cuts_per_deck,
+            max_players,
+            riffles,
+            positions,
+            threads,
+            variant,
+            format,
+            table,
+        } => {
+            check_full_deck(variant)?;
+            let config = game::DealConfig::default().with_variant(variant);
+            game::check_player_count(max_players.unwrap_or(num_players), 52, config)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            if samples == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "need at least one sample to analyze",
+                ));
+            }
+            let table = precompute::load_table_file(&table)?;
+            let threads = threads.unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            });
+            if let Some(max_players) = max_players {
+                return search::analyze_player_counts(
+                    max_players,
+                    table,
+                    samples,
+                    riffles,
+                    threads,
+                    real,
+                    config,
+                    format,
+                    cli.quiet,
+                );
+            }
+            match cuts_per_deck {
+                Some(cuts) => {
+                    let estimate = search::analyze_difficulty_sampled(
+                        num_players,
+                        &table,
+                        samples,
+                        cuts,
+                        real,
+                        config,
+                    );
+                    match format {
+                        search::OutputFormat::Human => println!(
+                            "Estimated mean wins: {:.2} ± {:.2} /{} ({:.1}% win rate, 95% CI)",
+                            estimate.mean_wins,
+                            estimate.margin,
+                            game::max_wins(num_players, real),
+                            estimate.win_rate(num_players, real) * 100.0
+                        ),
+                        search::OutputFormat::Json => {
+                            println!("{}", serde_json::to_string(&estimate)?)
+                        }
+                    }
+                }
+                None => search::analyze_difficulty(
+                    num_players,
+                    table,
+                    samples,
+                    riffles,
+                    threads,
+                    real,
+                    config,
+                    positions,
+                    format,
+                    cli.quiet,
+                )?,
+            }
+        }
+        Commands::Verify {
+            deck,
+            num_players,
+            real,
+            no_burns,
+            board_size,
+            variant,
+            trace,
+            table,
+        } => {
+            let short_deck = variant == game::GameVariant::ShortDeck;
+            let deck = if short_deck {
+                deck::Deck::from_short_deck_id_string(&deck)
+            } else {
+                deck::Deck::from_id_string(&deck)
+            }
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let config = deal_config(no_burns, board_size, variant)?;
+            game::check_player_count(num_players, deck.len(), config)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            check_trace_cut(trace, &deck)?;
+            let table = precompute::load_table_file(&table)?;
+            if short_deck {
+                search::verify_short_deck(num_players, &deck, &table, config);
+            } else {
+                search::verify_deck(num_players, &deck, &table, real, config);
+            }
+            if let Some(cut_pos) = trace {
+                search::print_deal_trace(num_players, &deck, cut_pos, config);
+            }
+        }
+        Commands::Positions {
+            deck,
+            num_players,
+            real,
+            equity,
+            trace,
+            table,
+        } => {
+            let deck = deck::Deck::from_id_string(&deck)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let config = game::DealConfig::default();
+            game::check_player_count(num_players, deck.len(), config)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            check_trace_cut(trace, &deck)?;
+            let table = precompute::load_table_file(&table)?;
+            search::print_positions(num_players, &deck, &table, real, equity);
+            if let Some(cut_pos) = trace {
+                search::print_deal_trace(num_players, &deck, cut_pos, config);
+            }
         }
-        Commands::Analyze { num_players, samples } => {
-            let f = std::fs::File::open("hands")?;
-            let table = precompute::load_table(f)?;
-            search::analyze_difficulty(num_players, table, samples);
+        Commands::BestCut {
+            deck,
+            num_players,
+            real,
+            table,
+        } => {
+            let deck = deck::Deck::from_id_string(&deck)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            game::check_player_count(num_players, deck.len(), game::DealConfig::default())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let table = precompute::load_table_file(&table)?;
+            search::print_best_cut(num_players, &deck, &table, real);
+        }
+        Commands::HeadsUp { hand, other, table } => {
+            let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+            let parse_hole = |s: &str| -> io::Result<hands::Player> {
+                let cards = s
+                    .split(',')
+                    .map(|card| card.trim().parse::<cards::Card>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| invalid(e.to_string()))?;
+                let cards = <[cards::Card; 2]>::try_from(cards).map_err(|cards| {
+                    invalid(format!("expected 2 hole cards, got {}", cards.len()))
+                })?;
+                Ok(hands::Player(cards))
+            };
+            let (p0, p1) = (parse_hole(&hand)?, parse_hole(&other)?);
+            let dealt = [p0.0, p1.0].concat();
+            for (i, card) in dealt.iter().enumerate() {
+                if dealt[..i].contains(card) {
+                    return Err(invalid(format!("{card} is dealt more than once")));
+                }
+            }
+            let table = precompute::load_table_file(&table)?;
+            search::print_heads_up(p0, p1, &table);
         }
-        Commands::Viz { output, restarts, players, seed } => {
+        Commands::Viz {
+            output,
+            restarts,
+            players,
+            seed,
+            real,
+            table,
+        } => {
             let player_counts: Vec<usize> = players
                 .split(',')
                 .map(|s| s.trim().parse().expect("invalid player count"))
                 .collect();
-            let f = std::fs::File::open("hands")?;
-            let table = precompute::load_table(f)?;
-            viz::export(&table, &player_counts, restarts, seed, &output)?;
+            let table = precompute::load_table_file(&table)?;
+            viz::export(&table, real, &player_counts, restarts, seed, &output)?;
         }
     }
 
@@ -1,52 +1,419 @@
 use crate::cards::Card;
+use crate::evaluator::{EVALUATOR_MAGIC, Evaluator};
 use crate::hands::*;
-use bytes::{Buf, BufMut, Bytes, BytesMut};
-use rustc_hash::FxHashMap;
+use bytes::{Buf, BufMut, Bytes};
 use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-pub struct ScoreTable(FxHashMap<Hand, TableEntry>);
+/// Scores for every 7-card hand
+pub struct ScoreTable(Scores);
+
+/// Where a `ScoreTable` finds its scores
+enum Scores {
+    /// One precomputed score per hand, indexed by `Hand::colex_index`
+    Entries(Box<[TableEntry]>),
+    /// Walked card by card through the evaluator's state machine, with nothing stored per hand
+    Evaluator(Evaluator),
+    /// Scored from the hand itself, see `ScoreTable::direct`
+    #[cfg(test)]
+    Direct,
+}
+
+impl From<Evaluator> for ScoreTable {
+    fn from(evaluator: Evaluator) -> Self {
+        ScoreTable(Scores::Evaluator(evaluator))
+    }
+}
 
 impl ScoreTable {
     pub fn score(&self, hand: &Hand) -> TableEntry {
-        *self.0.get(hand).unwrap()
+        match &self.0 {
+            Scores::Entries(entries) => entries[hand.colex_index()],
+            Scores::Evaluator(evaluator) => evaluator.score(hand),
+            #[cfg(test)]
+            Scores::Direct => hand.score().into(),
+        }
+    }
+
+    /// The scores of `hands`, in order
+    pub fn score_many(&self, hands: &[Hand]) -> impl Iterator<Item = TableEntry> {
+        hands.iter().map(|hand| self.score(hand))
+    }
+
+    /// Which of two hands is stronger, by their scores in this table
+    pub fn compare(&self, a: &Hand, b: &Hand) -> std::cmp::Ordering {
+        self.score(a).cmp(&self.score(b))
+    }
+
+    /// `hand`, carrying its score in this table
+    pub fn scored(&self, hand: Hand) -> ScoredHand {
+        ScoredHand {
+            hand,
+            entry: self.score(&hand),
+        }
+    }
+}
+
+/// A hand with its looked-up score, so collections of hands sort by strength directly
+/// Compares by score alone: different hands of equal strength are equal
+#[derive(Debug, Clone, Copy)]
+pub struct ScoredHand {
+    pub hand: Hand,
+    pub entry: TableEntry,
+}
+
+impl PartialEq for ScoredHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.entry == other.entry
+    }
+}
+
+impl Eq for ScoredHand {}
+
+impl PartialOrd for ScoredHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.entry.cmp(&other.entry)
+    }
+}
+
+#[cfg(test)]
+impl ScoreTable {
+    /// A table that scores every lookup directly instead of reading precomputed entries
+    /// Lets tests run without the precomputed `hands` file
+    pub fn direct() -> Self {
+        ScoreTable(Scores::Direct)
+    }
+}
+
+impl ScoreTable {
+    /// Write the table in the compact format: a header, then one packed score per hand
+    /// in `colex_index` order, so no hand bytes are stored
+    /// An evaluator-backed table is written out in full, scoring every hand
+    /// Scores are written a `CHUNK_BYTES` buffer at a time, like `precompute_parallel`
+    pub fn write_compact(&self, mut output: impl Write) -> std::io::Result<()> {
+        let entries: Box<dyn Iterator<Item = TableEntry>> = match &self.0 {
+            Scores::Entries(entries) => Box::new(entries.iter().copied()),
+            Scores::Evaluator(evaluator) => Box::new(
+                colex_hands_from(0)
+                    .take(ALL_HANDS)
+                    .map(|hand| evaluator.score(&hand)),
+            ),
+            #[cfg(test)]
+            Scores::Direct => Box::new(
+                colex_hands_from(0)
+                    .take(ALL_HANDS)
+                    .map(|hand| hand.score().into()),
+            ),
+        };
+        write_entries(&mut output, entries, CHUNK_BYTES / COMPACT_ENTRY_SIZE)
+    }
+}
+
+/// Write a compact table of `entries`, packing `chunk_hands` of them at a time into a
+/// buffer that is reused for every chunk
+fn write_entries(
+    output: &mut impl Write,
+    entries: impl Iterator<Item = TableEntry>,
+    chunk_hands: usize,
+) -> std::io::Result<()> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    put_header(&mut header, TABLE_VERSION, COMPACT_ENTRY_SIZE);
+    output.write_all(&header)?;
+
+    let mut buffer = Vec::with_capacity(chunk_hands * COMPACT_ENTRY_SIZE);
+    for entry in entries {
+        buffer.put_u32(entry.pack());
+        if buffer.len() == chunk_hands * COMPACT_ENTRY_SIZE {
+            output.write_all(&buffer)?;
+            buffer.clear();
+        }
     }
+    output.write_all(&buffer)
+}
+
+/// Table files start with this magic, then the format version and the size of each entry,
+/// both as big-endian `u16`s
+/// Old files have no header: they start with the card ids of the first hand, all below 52
+const TABLE_MAGIC: [u8; 8] = *b"PKRHANDS";
+const HEADER_SIZE: usize = TABLE_MAGIC.len() + 2 + 2;
+/// Version 1 is a list of serialized `Entry`s
+const ENTRY_TABLE_VERSION: u16 = 1;
+/// Version 2 is the compact format, one `TableEntry::pack` per hand
+/// Bump this whenever the layout of `Entry` or the packed `TableEntry` changes
+pub const TABLE_VERSION: u16 = 2;
+const COMPACT_ENTRY_SIZE: usize = 4;
+
+fn put_header(bytes: &mut impl BufMut, version: u16, entry_size: usize) {
+    bytes.put_slice(&TABLE_MAGIC);
+    bytes.put_u16(version);
+    bytes.put_u16(entry_size as u16);
+}
+
+/// Why a `hands` file could not be loaded
+#[derive(Debug)]
+pub enum TableError {
+    Io(std::io::Error),
+    /// The file is shorter than a full table
+    Truncated {
+        expected: usize,
+        actual: usize,
+    },
+    /// The file is longer than a full table
+    TrailingData {
+        expected: usize,
+        actual: usize,
+    },
+    /// The file is not a hand table
+    BadMagic,
+    /// The file looks like a table from before headers were added
+    MissingHeader,
+    /// A table written by an unknown format version
+    WrongVersion(u16),
+    /// The header's entry size doesn't match its version
+    WrongEntrySize {
+        expected: usize,
+        actual: usize,
+    },
 }
 
-pub fn load_table(mut file: impl Read) -> std::io::Result<ScoreTable> {
-    let mut table = FxHashMap::with_capacity_and_hasher(ALL_HANDS, Default::default());
-    let mut v = Vec::with_capacity(buffer_size());
+impl std::fmt::Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableError::Io(e) => write!(f, "{e}"),
+            TableError::Truncated { expected, actual } => {
+                write!(
+                    f,
+                    "table is truncated: expected {expected} bytes, got {actual}"
+                )
+            }
+            TableError::TrailingData { expected, actual } => {
+                write!(
+                    f,
+                    "table is too long: expected {expected} bytes, got {actual}"
+                )
+            }
+            TableError::BadMagic => write!(f, "not a hand table file"),
+            TableError::MissingHeader => {
+                write!(f, "table has no header; rewrite it with `convert-table`")
+            }
+            TableError::WrongVersion(v) => {
+                write!(
+                    f,
+                    "unsupported table version {v} (expected {TABLE_VERSION})"
+                )
+            }
+            TableError::WrongEntrySize { expected, actual } => {
+                write!(f, "table entries are {actual} bytes, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TableError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TableError {
+    fn from(e: std::io::Error) -> Self {
+        TableError::Io(e)
+    }
+}
+
+impl From<TableError> for std::io::Error {
+    fn from(e: TableError) -> Self {
+        match e {
+            TableError::Io(e) => e,
+            e => std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// Where `precompute` writes the table, and commands read it from, unless told otherwise
+pub const DEFAULT_TABLE_PATH: &str = "hands";
+
+/// Load a full table from a file with a valid header, or an evaluator written by
+/// `build-evaluator`, which scores the same hands without the 1.2 GB of precomputed scores
+pub fn load_table(mut file: impl Read) -> Result<ScoreTable, TableError> {
+    let mut v = Vec::new();
     file.read_to_end(&mut v)?;
+    if v.starts_with(&EVALUATOR_MAGIC) {
+        return Ok(Evaluator::parse(v)?.into());
+    }
+    parse_table(v, ALL_HANDS)
+}
+
+/// `load_table` from the file at `path`
+pub fn load_table_file(path: &Path) -> Result<ScoreTable, TableError> {
+    load_table(std::fs::File::open(path)?)
+}
+
+/// Rewrite a table file in the compact format
+/// Headerless files from before the header was added are accepted, as are
+/// files with a header, so converting twice is harmless
+pub fn convert_table(mut input: impl Read, output: impl Write) -> Result<(), TableError> {
+    let mut v = Vec::new();
+    input.read_to_end(&mut v)?;
+    let table = if is_headerless(&v) {
+        parse_headerless(v, ALL_HANDS)?
+    } else {
+        parse_table(v, ALL_HANDS)?
+    };
+    table.write_compact(output)?;
+    Ok(())
+}
+
+/// Load a table of exactly `num_hands` entries, checking its header and size first
+fn parse_table(v: Vec<u8>, num_hands: usize) -> Result<ScoreTable, TableError> {
+    if !v.starts_with(&TABLE_MAGIC) {
+        return Err(if v.is_empty() {
+            TableError::Truncated {
+                expected: HEADER_SIZE,
+                actual: 0,
+            }
+        } else if is_headerless(&v) {
+            TableError::MissingHeader
+        } else {
+            TableError::BadMagic
+        });
+    }
+    if v.len() < HEADER_SIZE {
+        return Err(TableError::Truncated {
+            expected: HEADER_SIZE,
+            actual: v.len(),
+        });
+    }
+
     let mut bs = Bytes::from_owner(v);
-    for _ in 0..ALL_HANDS {
+    bs.advance(TABLE_MAGIC.len());
+    let version = bs.get_u16();
+    let entry_size = bs.get_u16() as usize;
+    let expected_size = match version {
+        ENTRY_TABLE_VERSION => Entry::size(),
+        TABLE_VERSION => COMPACT_ENTRY_SIZE,
+        v => return Err(TableError::WrongVersion(v)),
+    };
+    if entry_size != expected_size {
+        return Err(TableError::WrongEntrySize {
+            expected: expected_size,
+            actual: entry_size,
+        });
+    }
+    check_length(HEADER_SIZE + bs.len(), HEADER_SIZE + num_hands * entry_size)?;
+
+    if version == ENTRY_TABLE_VERSION {
+        load_entries(bs, num_hands)
+    } else {
+        Ok(load_compact(bs, num_hands))
+    }
+}
+
+/// Old tables are a bare list of `Entry`s, so they start with a real card id
+fn is_headerless(v: &[u8]) -> bool {
+    !v.starts_with(&TABLE_MAGIC) && v.first().is_some_and(|&id| Card(id).valid())
+}
+
+fn parse_headerless(v: Vec<u8>, num_hands: usize) -> Result<ScoreTable, TableError> {
+    check_length(v.len(), num_hands * Entry::size())?;
+    load_entries(Bytes::from_owner(v), num_hands)
+}
+
+fn check_length(actual: usize, expected: usize) -> Result<(), TableError> {
+    if actual < expected {
+        return Err(TableError::Truncated { expected, actual });
+    }
+    if actual > expected {
+        return Err(TableError::TrailingData { expected, actual });
+    }
+    Ok(())
+}
+
+fn load_compact(mut bs: Bytes, num_hands: usize) -> ScoreTable {
+    let table = (0..num_hands)
+        .map(|_| TableEntry::unpack(bs.get_u32()))
+        .collect();
+    ScoreTable(Scores::Entries(table))
+}
+
+fn load_entries(mut bs: Bytes, num_hands: usize) -> Result<ScoreTable, TableError> {
+    let mut table = vec![TableEntry::default(); num_hands].into_boxed_slice();
+    for _ in 0..num_hands {
         let next = Entry::deserialize(&mut bs);
-        table.insert(next.hand, next.into());
+        // Hands that aren't real, or don't fit this size of table, mean the file isn't a table
+        if !next.hand.0.iter().all(Card::valid) {
+            return Err(TableError::BadMagic);
+        }
+        let idx = next.hand.colex_index();
+        if idx >= num_hands {
+            return Err(TableError::BadMagic);
+        }
+        table[idx] = next.into();
     }
 
-    Ok(ScoreTable(table))
+    Ok(ScoreTable(Scores::Entries(table)))
 }
 
-pub fn precompute(mut output: impl Write) -> std::io::Result<()> {
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  Precomputing poker hand lookup table");
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+/// How many bytes of scores `precompute_parallel` holds at once before writing them out
+const CHUNK_BYTES: usize = 8 << 20;
+
+/// Score every hand on `num_threads` threads and write the compact table to `output`
+///
+/// The hands are scored in `colex_index` order, a chunk at a time, into one reused
+/// buffer that is flushed to `output` after each chunk, so peak memory stays at
+/// `CHUNK_BYTES` however many hands there are
+/// Progress is reported on stderr unless `quiet`
+pub fn precompute_parallel(
+    mut output: impl Write,
+    num_threads: usize,
+    quiet: bool,
+) -> std::io::Result<()> {
+    if !quiet {
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        eprintln!("  Precomputing poker hand lookup table");
+        eprintln!("  Threads: {}", num_threads);
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
 
-    let mut buffer = BytesMut::with_capacity(buffer_size());
     let total = ALL_HANDS;
+    let progress = AtomicUsize::new(0);
+    let done = AtomicBool::new(false);
 
-    for (i, hand) in all_hands().enumerate() {
-        let e = hand.score();
-        e.serialize(&mut buffer);
+    let result = std::thread::scope(|scope| {
+        let (progress, done) = (&progress, &done);
+        // Report progress until every hand is written, or writing fails
+        scope.spawn(move || {
+            while !quiet && !done.load(Ordering::Relaxed) {
+                let i = progress.load(Ordering::Relaxed);
+                let percent = (i as f64 / total as f64) * 100.0;
+                eprint!(
+                    "\r  ⚡ Progress: {}/{} ({:.1}%)",
+                    format_number(i),
+                    format_number(total),
+                    percent
+                );
+                std::thread::sleep(std::time::Duration::from_millis(200));
+            }
+        });
 
-        // Update progress every 100k hands
-        if i % 100_000 == 0 {
-            let percent = (i as f64 / total as f64) * 100.0;
-            eprint!(
-                "\r  ⚡ Progress: {}/{} ({:.1}%)",
-                format_number(i),
-                format_number(total),
-                percent
-            );
-        }
+        let chunk_hands = CHUNK_BYTES / COMPACT_ENTRY_SIZE;
+        let result = write_scores(&mut output, total, chunk_hands, num_threads, progress);
+        done.store(true, Ordering::Relaxed);
+        result
+    });
+    result?;
+    if quiet {
+        return Ok(());
     }
 
     eprintln!(
@@ -54,13 +421,10 @@ pub fn precompute(mut output: impl Write) -> std::io::Result<()> {
         format_number(total),
         format_number(total)
     );
-    eprintln!();
-    eprintln!("  Writing to disk...");
-
-    let buffer = buffer.freeze();
-    output.write_all(&buffer)?;
-
-    eprintln!("  ✓ Wrote {} bytes", format_number(buffer.len()));
+    eprintln!(
+        "  ✓ Wrote {} bytes",
+        format_number(HEADER_SIZE + total * COMPACT_ENTRY_SIZE)
+    );
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     eprintln!("  Done!");
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -68,6 +432,62 @@ pub fn precompute(mut output: impl Write) -> std::io::Result<()> {
     Ok(())
 }
 
+/// `precompute_parallel`, writing the table to a new file at `path`
+pub fn precompute_to_file(path: &Path, num_threads: usize, quiet: bool) -> std::io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    precompute_parallel(file, num_threads, quiet)
+}
+
+/// Write the compact table of the first `num_hands` hands in `colex_index` order,
+/// scoring `chunk_hands` of them at a time into a buffer that is reused for every chunk
+fn write_scores(
+    output: &mut impl Write,
+    num_hands: usize,
+    chunk_hands: usize,
+    num_threads: usize,
+    progress: &AtomicUsize,
+) -> std::io::Result<()> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    put_header(&mut header, TABLE_VERSION, COMPACT_ENTRY_SIZE);
+    output.write_all(&header)?;
+
+    let mut buffer = vec![0u8; chunk_hands.min(num_hands) * COMPACT_ENTRY_SIZE];
+    for first in (0..num_hands).step_by(chunk_hands) {
+        let len = chunk_hands.min(num_hands - first);
+        let chunk = &mut buffer[..len * COMPACT_ENTRY_SIZE];
+        score_hands_into(chunk, first, num_threads, progress);
+        output.write_all(chunk)?;
+    }
+    Ok(())
+}
+
+/// Pack the scores for the hands starting at `first` (in `colex_index` order)
+/// into `buffer`, which holds a whole number of scores, using `num_threads` threads
+/// Adds the number of hands scored to `progress` as it goes
+fn score_hands_into(buffer: &mut [u8], first: usize, num_threads: usize, progress: &AtomicUsize) {
+    let num_hands = buffer.len() / COMPACT_ENTRY_SIZE;
+    let per_thread = num_hands.div_ceil(num_threads.max(1)).max(1);
+
+    std::thread::scope(|scope| {
+        let chunks = buffer.chunks_mut(per_thread * COMPACT_ENTRY_SIZE);
+        for (chunk_idx, mut chunk) in chunks.enumerate() {
+            let start = first + chunk_idx * per_thread;
+            scope.spawn(move || {
+                let len = chunk.len() / COMPACT_ENTRY_SIZE;
+                for (i, hand) in colex_hands_from(start).take(len).enumerate() {
+                    let entry: TableEntry = hand.score().into();
+                    chunk.put_u32(entry.pack());
+                    // Update progress every 100k hands
+                    if i % 100_000 == 99_999 {
+                        progress.fetch_add(100_000, Ordering::Relaxed);
+                    }
+                }
+                progress.fetch_add(len % 100_000, Ordering::Relaxed);
+            });
+        }
+    });
+}
+
 fn format_number(n: usize) -> String {
     let s = n.to_string();
     let mut result = String::new();
@@ -80,15 +500,12 @@ fn format_number(n: usize) -> String {
     result.chars().rev().collect()
 }
 
-pub const fn buffer_size() -> usize {
-    Entry::size() * ALL_HANDS
-}
-
 #[derive(Default, PartialEq, Eq)]
 pub struct Entry {
     pub hand: Hand,
     pub rank: u8,
     pub hi: u8,
+    pub kickers: [u8; 5],
 }
 
 impl Into<TableEntry> for Entry {
@@ -96,48 +513,146 @@ impl Into<TableEntry> for Entry {
         TableEntry {
             rank: self.rank,
             hi: self.hi,
+            kickers: self.kickers,
         }
     }
 }
 
-#[derive(Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub struct TableEntry {
     pub rank: u8,
     pub hi: u8,
+    /// The five card values of the best hand in comparison order
+    pub kickers: [u8; 5],
 }
 
 impl PartialOrd for TableEntry {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.rank.cmp(&other.rank) {
-            std::cmp::Ordering::Equal => Some(self.hi.cmp(&other.hi)),
-            ord => Some(ord),
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for TableEntry {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        match self.rank.cmp(&other.rank) {
+            std::cmp::Ordering::Equal => self.kickers.cmp(&other.kickers),
+            ord => ord,
+        }
     }
 }
 
 impl TableEntry {
+    pub fn category(&self) -> HandCategory {
+        self.rank.into()
+    }
+
+    /// Compare under short-deck rules, where a flush beats a full house
+    pub fn cmp_short_deck(&self, other: &Self) -> std::cmp::Ordering {
+        self.short_deck_rank()
+            .cmp(&other.short_deck_rank())
+            .then_with(|| self.kickers.cmp(&other.kickers))
+    }
+
+    /// Compare lowball scores (from `score_five_cards_lowball`), where the lower hand wins
+    /// Greater means the better low hand, like `cmp` for high hands
+    pub fn cmp_lowball(&self, other: &Self) -> std::cmp::Ordering {
+        other.cmp(self)
+    }
+
+    fn short_deck_rank(&self) -> u8 {
+        match self.category() {
+            HandCategory::Flush => HandCategory::FullHouse.into(),
+            HandCategory::FullHouse => HandCategory::Flush.into(),
+            _ => self.rank,
+        }
+    }
+
+    /// Describe the hand in words, e.g. "Full house, Kings full of Fours"
+    pub fn describe(&self) -> String {
+        let k = &self.kickers;
+        match self.category() {
+            HandCategory::HighCard => format!("High card, {}", value_name(k[0])),
+            HandCategory::Pair => format!("Pair of {}", value_plural(k[0])),
+            HandCategory::TwoPair => {
+                format!(
+                    "Two pair, {} and {}",
+                    value_plural(k[0]),
+                    value_plural(k[2])
+                )
+            }
+            HandCategory::ThreeOfAKind => format!("Three of a kind, {}", value_plural(k[0])),
+            HandCategory::Straight => format!("{}-high straight", value_name(k[0])),
+            HandCategory::Flush => format!("{}-high flush", value_name(k[0])),
+            HandCategory::FullHouse => format!(
+                "Full house, {} full of {}",
+                value_plural(k[0]),
+                value_plural(k[3])
+            ),
+            HandCategory::FourOfAKind => format!("Four of a kind, {}", value_plural(k[0])),
+            HandCategory::StraightFlush if k[0] == 14 => "Royal flush".to_string(),
+            HandCategory::StraightFlush => format!("{}-high straight flush", value_name(k[0])),
+        }
+    }
+
+    /// Pack into the 4-byte compact table format: 4 bits each for rank, hi, and the
+    /// five kickers (all at most 14), with the rank in the top bits
+    pub fn pack(&self) -> u32 {
+        self.kickers
+            .iter()
+            .fold(((self.rank as u32) << 4) | self.hi as u32, |acc, &k| {
+                (acc << 4) | k as u32
+            })
+    }
+
+    pub fn unpack(packed: u32) -> Self {
+        let nibble = |shift: u32| ((packed >> shift) & 0xf) as u8;
+        TableEntry {
+            rank: nibble(24),
+            hi: nibble(20),
+            kickers: [nibble(16), nibble(12), nibble(8), nibble(4), nibble(0)],
+        }
+    }
+
     /// Convert TableEntry to a numeric score for margin calculations
-    /// Preserves the ordering: higher rank = higher score, hi is tiebreaker
+    /// Preserves the ordering: higher rank = higher score, kickers are tiebreakers
+    /// Each kicker (at most 14) takes 4 bits, so a rank step is worth 1 << 20
     pub fn to_score(&self) -> i32 {
-        (self.rank as i32) * 256 + (self.hi as i32)
+        self.kickers
+            .iter()
+            .fold(self.rank as i32, |acc, &k| (acc << 4) | k as i32)
     }
 }
 
-impl Entry {
-    pub const fn size() -> usize {
-        HAND_SIZE + 2
+/// The name of a kicker value (aces are 14, or 1 in the wheel)
+fn value_name(value: u8) -> &'static str {
+    match value {
+        1 | 14 => "Ace",
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => panic!("Invalid card value {value}"),
     }
+}
 
-    pub fn serialize(&self, bytes: &mut BytesMut) {
-        bytes.put_slice(self.hand.as_slice());
-        bytes.put_u8(self.rank);
-        bytes.put_u8(self.hi);
+fn value_plural(value: u8) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        _ => format!("{}s", value_name(value)),
+    }
+}
+
+impl Entry {
+    pub const fn size() -> usize {
+        HAND_SIZE + 2 + 5
     }
 
     pub fn deserialize(bytes: &mut Bytes) -> Self {
@@ -147,6 +662,334 @@ impl Entry {
         }
         e.rank = bytes.get_u8();
         e.hi = bytes.get_u8();
+        bytes.copy_to_slice(&mut e.kickers);
         e
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+
+    #[test]
+    fn parallel_scoring_matches_sequential_layout() {
+        const NUM_HANDS: usize = 5_000;
+        const FIRST: usize = 1_000_000;
+
+        let mut sequential = BytesMut::new();
+        for index in FIRST..FIRST + NUM_HANDS {
+            let entry: TableEntry = Hand::from_colex_index(index).score().into();
+            sequential.put_u32(entry.pack());
+        }
+
+        for num_threads in [1, 3, 8] {
+            let mut parallel = vec![0u8; NUM_HANDS * COMPACT_ENTRY_SIZE];
+            let progress = AtomicUsize::new(0);
+            score_hands_into(&mut parallel, FIRST, num_threads, &progress);
+            assert_eq!(&parallel[..], &sequential[..], "{} threads", num_threads);
+            assert_eq!(progress.load(Ordering::Relaxed), NUM_HANDS);
+        }
+    }
+
+    /// Every hand of the lowest 12 cards; these are exactly the first hands in colex order
+    fn low_hands() -> Vec<Hand> {
+        (0u32..1 << 12)
+            .filter(|mask| mask.count_ones() == 7)
+            .map(|mask| {
+                let mut cards = (0..12).filter(|i| mask & (1 << i) != 0).map(Card);
+                Hand(std::array::from_fn(|_| cards.next().unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn compare_sorts_hands_like_their_scores() {
+        let table = ScoreTable::direct();
+        let hands: Vec<Hand> = (0..300)
+            .map(|i| Hand::from_colex_index(i * 445_679 % ALL_HANDS))
+            .collect();
+
+        let mut by_compare = hands.clone();
+        by_compare.sort_by(|a, b| table.compare(a, b));
+        let mut by_score = hands.clone();
+        by_score.sort_by_key(|hand| table.score(hand));
+        assert_eq!(by_compare, by_score);
+
+        let mut scored: Vec<ScoredHand> = hands.iter().map(|&hand| table.scored(hand)).collect();
+        scored.sort();
+        let entries: Vec<TableEntry> = scored.iter().map(|s| s.entry).collect();
+        let expected: Vec<TableEntry> = by_score.iter().map(|hand| table.score(hand)).collect();
+        assert_eq!(entries, expected);
+        assert!(scored.iter().all(|s| s.entry == table.score(&s.hand)));
+    }
+
+    #[test]
+    fn pack_roundtrips() {
+        for hand in low_hands()
+            .into_iter()
+            .chain((0..2_000).map(|i| Hand::from_colex_index(i * 66_889)))
+        {
+            let entry: TableEntry = hand.score().into();
+            assert_eq!(TableEntry::unpack(entry.pack()), entry);
+            assert!(entry.pack() < 1 << 28);
+        }
+    }
+
+    /// The legacy per-hand format `Entry::deserialize` reads
+    fn serialized_entries(hands: &[Hand]) -> BytesMut {
+        let mut bytes = BytesMut::new();
+        for hand in hands {
+            let entry = hand.score();
+            bytes.put_slice(entry.hand.as_slice());
+            bytes.put_u8(entry.rank);
+            bytes.put_u8(entry.hi);
+            bytes.put_slice(&entry.kickers);
+        }
+        bytes
+    }
+
+    #[test]
+    fn converted_table_scores_identically() {
+        let hands = low_hands();
+        let legacy = serialized_entries(&hands);
+        // `convert_table`, for a table of just these hands
+        let legacy_table = parse_headerless(legacy.to_vec(), hands.len()).unwrap();
+        let mut compact = Vec::new();
+        legacy_table.write_compact(&mut compact).unwrap();
+        assert!(compact.starts_with(b"PKRHANDS"));
+        assert_eq!(
+            compact.len(),
+            HEADER_SIZE + hands.len() * COMPACT_ENTRY_SIZE
+        );
+        assert!(compact.len() < legacy.len() / 3);
+        let compact_table = parse_table(compact.to_vec(), hands.len()).unwrap();
+
+        for hand in &hands {
+            let expected: TableEntry = hand.score().into();
+            assert_eq!(legacy_table.score(hand), expected);
+            assert_eq!(compact_table.score(hand), expected);
+        }
+
+        // Converting a compact file leaves it unchanged
+        let mut again = Vec::new();
+        compact_table.write_compact(&mut again).unwrap();
+        assert_eq!(again, compact);
+    }
+
+    #[test]
+    fn chunked_output_matches_monolithic_output() {
+        // The lowest 12 cards' hands are the first in colex order, so the
+        // converted table of just those is what precomputing them should write
+        let hands = low_hands();
+        let legacy = parse_headerless(serialized_entries(&hands).to_vec(), hands.len()).unwrap();
+        let mut monolithic = Vec::new();
+        legacy.write_compact(&mut monolithic).unwrap();
+
+        for (chunk_hands, num_threads) in [(hands.len(), 1), (1, 2), (100, 3), (10_000, 4)] {
+            let mut chunked = Vec::new();
+            let progress = AtomicUsize::new(0);
+            write_scores(
+                &mut chunked,
+                hands.len(),
+                chunk_hands,
+                num_threads,
+                &progress,
+            )
+            .unwrap();
+            assert_eq!(chunked, monolithic, "chunks of {chunk_hands}");
+            assert_eq!(progress.load(Ordering::Relaxed), hands.len());
+        }
+    }
+
+    /// Remembers every write, to check how much was written at once
+    #[derive(Default)]
+    struct Writes(Vec<Vec<u8>>);
+
+    impl Write for Writes {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn compact_tables_are_written_in_chunks() {
+        let hands = low_hands();
+        let legacy = parse_headerless(serialized_entries(&hands).to_vec(), hands.len()).unwrap();
+        let mut monolithic = Vec::new();
+        legacy.write_compact(&mut monolithic).unwrap();
+
+        let entries = || hands.iter().map(|hand| legacy.score(hand));
+        for chunk_hands in [1, 100, hands.len(), 10_000] {
+            let mut writes = Writes::default();
+            write_entries(&mut writes, entries(), chunk_hands).unwrap();
+            assert_eq!(writes.0.concat(), monolithic, "chunks of {chunk_hands}");
+            let largest = writes.0.iter().map(Vec::len).max().unwrap();
+            assert!(largest <= HEADER_SIZE.max(chunk_hands * COMPACT_ENTRY_SIZE));
+        }
+    }
+
+    #[test]
+    fn score_many_matches_single_lookups() {
+        let hands = low_hands();
+        let legacy = parse_headerless(serialized_entries(&hands).to_vec(), hands.len()).unwrap();
+        for table in [legacy, ScoreTable::direct()] {
+            let batched: Vec<TableEntry> = table.score_many(&hands).collect();
+            let single: Vec<TableEntry> = hands.iter().map(|hand| table.score(hand)).collect();
+            assert_eq!(batched, single);
+        }
+        assert_eq!(ScoreTable::direct().score_many(&[]).count(), 0);
+    }
+
+    #[test]
+    fn loads_version_1_tables() {
+        let hands = low_hands();
+        let mut v1 = BytesMut::new();
+        put_header(&mut v1, 1, Entry::size());
+        v1.extend_from_slice(&serialized_entries(&hands));
+        let table = parse_table(v1.to_vec(), hands.len()).unwrap();
+        for hand in &hands {
+            assert_eq!(table.score(hand), hand.score().into());
+        }
+    }
+
+    #[test]
+    fn rejects_headerless_tables() {
+        let hands = low_hands();
+        let legacy = serialized_entries(&hands);
+        let err = parse_table(legacy.to_vec(), hands.len()).err().unwrap();
+        assert!(matches!(err, TableError::MissingHeader), "{err}");
+        assert!(err.to_string().contains("convert-table"), "{err}");
+    }
+
+    #[test]
+    fn rejects_unknown_table_version() {
+        let mut bytes = Vec::new();
+        put_header(&mut bytes, TABLE_VERSION + 1, COMPACT_ENTRY_SIZE);
+        let err = load_table(&bytes[..]).err().unwrap();
+        assert!(matches!(err, TableError::WrongVersion(3)), "{err}");
+        let err = std::io::Error::from(err);
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("version 3"), "{err}");
+
+        // A version 1 header that claims compact entries
+        let mut bytes = Vec::new();
+        put_header(&mut bytes, 1, COMPACT_ENTRY_SIZE);
+        let err = load_table(&bytes[..]).err().unwrap();
+        assert!(
+            matches!(
+                err,
+                TableError::WrongEntrySize {
+                    expected: 14,
+                    actual: 4
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_tables() {
+        let hands = low_hands();
+        let legacy = serialized_entries(&hands);
+        let full = legacy.len();
+        let err = parse_headerless(legacy[..full - 3].to_vec(), hands.len())
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, TableError::Truncated { expected, actual } if expected == full && actual == full - 3),
+            "{err}"
+        );
+
+        let mut compact = Vec::new();
+        parse_headerless(legacy.to_vec(), hands.len())
+            .unwrap()
+            .write_compact(&mut compact)
+            .unwrap();
+        let err = parse_table(compact[..compact.len() - 4].to_vec(), hands.len())
+            .err()
+            .unwrap();
+        assert!(matches!(err, TableError::Truncated { .. }), "{err}");
+        let err = parse_table(compact[..10].to_vec(), hands.len())
+            .err()
+            .unwrap();
+        assert!(
+            matches!(
+                err,
+                TableError::Truncated {
+                    expected: HEADER_SIZE,
+                    actual: 10
+                }
+            ),
+            "{err}"
+        );
+
+        // Nowhere near a full table
+        let err = load_table(&compact[..]).err().unwrap();
+        assert!(matches!(err, TableError::Truncated { .. }), "{err}");
+    }
+
+    #[test]
+    fn rejects_unrecognized_files() {
+        assert!(matches!(
+            load_table(&b"hello"[..]),
+            Err(TableError::BadMagic)
+        ));
+        assert!(matches!(
+            load_table(&[][..]),
+            Err(TableError::Truncated { actual: 0, .. })
+        ));
+        // The right size for a headerless table, but with cards that don't exist
+        let mut garbage = [60u8; Entry::size()];
+        garbage[0] = 0;
+        assert!(matches!(
+            parse_headerless(garbage.to_vec(), 1),
+            Err(TableError::BadMagic)
+        ));
+
+        let mut compact = Vec::new();
+        put_header(&mut compact, TABLE_VERSION, COMPACT_ENTRY_SIZE);
+        compact.put_u64(0);
+        let err = parse_table(compact.to_vec(), 1).err().unwrap();
+        assert!(
+            matches!(
+                err,
+                TableError::TrailingData {
+                    expected: 16,
+                    actual: 20
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn missing_table_file_is_an_io_error() {
+        let path = std::env::temp_dir().join(format!("poker_wins_missing_{}", std::process::id()));
+        let err = load_table_file(&path).err().unwrap();
+        assert!(
+            matches!(&err, TableError::Io(e) if e.kind() == std::io::ErrorKind::NotFound),
+            "{err}"
+        );
+    }
+
+    #[test]
+    #[ignore = "Scores every 7-card hand, which takes minutes"]
+    fn precomputed_file_loads() {
+        let path = std::env::temp_dir().join(format!("poker_wins_table_{}", std::process::id()));
+        precompute_to_file(&path, 4, true).unwrap();
+        let table = load_table_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        for hand in (0..ALL_HANDS)
+            .step_by(1_000_003)
+            .map(Hand::from_colex_index)
+        {
+            assert_eq!(table.score(&hand), hand.score().into());
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Five-card hands from weakest to strongest, each beating the one before it
+//!
+//! Many neighbours share a category and high card, so only the kickers (grouped by
+//! count, then by value) can order them
+
+pub const RANKED_HANDS: [[&str; 5]; 23] = [
+    ["7c", "5d", "4h", "3s", "2c"],
+    ["Ac", "Kd", "Qh", "Js", "8c"],
+    ["Ad", "Kh", "Qs", "Jc", "9d"],
+    // Pairs, where the pair outranks a higher kicker
+    ["2c", "2d", "Ah", "Ks", "Qc"],
+    ["3c", "3d", "6h", "5s", "4c"],
+    ["3h", "3s", "7d", "5c", "4d"],
+    // Two pair, compared on the top pair, then the bottom pair, then the kicker
+    ["Kc", "Kd", "2h", "2s", "Ac"],
+    ["Kh", "Ks", "3c", "3d", "2h"],
+    ["Kh", "Ks", "3c", "3d", "4h"],
+    ["Ac", "Ad", "2c", "2s", "3h"],
+    ["2c", "2d", "2h", "Ac", "Kd"],
+    ["3c", "3d", "3h", "4s", "5c"],
+    // The wheel is the lowest straight
+    ["Ah", "2d", "3c", "4s", "5h"],
+    ["2s", "3d", "4c", "5h", "6d"],
+    ["Tc", "Jd", "Qh", "Ks", "Ad"],
+    ["Tc", "8c", "6c", "4c", "2c"],
+    ["Ts", "9s", "7s", "5s", "3s"],
+    // Full houses compare on the trips before the pair
+    ["2c", "2d", "2h", "Ac", "Ad"],
+    ["3c", "3d", "3h", "Kc", "Kd"],
+    ["2c", "2d", "2h", "2s", "Ac"],
+    ["3c", "3d", "3h", "3s", "Kc"],
+    ["Ah", "2h", "3h", "4h", "5h"],
+    ["2s", "3s", "4s", "5s", "6s"],
+];
@@ -0,0 +1,170 @@
+//! A second, deliberately naive 5-card evaluator, to cross-check `score_five_cards` against
+//! Written from the rules rather than from the fast scorer: count each value, look for every
+//! possible straight, and read the category off the counts
+
+use crate::cards::Card;
+use crate::hands::HandCategory;
+
+/// A hand's category, and the values that break ties within it, most significant first
+pub type ReferenceScore = (HandCategory, Vec<u8>);
+
+/// Score five cards, with aces high (14) except in the wheel
+pub fn reference_score(cards: [Card; 5]) -> ReferenceScore {
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[card.rank_value() as usize] += 1;
+    }
+    let flush = cards
+        .iter()
+        .all(|card| card.into_inner().1 == cards[0].into_inner().1);
+
+    // The highest top card of five values that are all present, where an ace is also a 1
+    let present = |value: u8| counts[if value == 1 { 14 } else { value } as usize] > 0;
+    let straight = (5..=14).rev().find(|&top| (top - 4..=top).all(present));
+
+    // Values by how many times they appear, then by how high they are
+    let mut by_count: Vec<(u8, u8)> = (2..=14)
+        .filter(|&v| counts[v as usize] > 0)
+        .map(|v| (counts[v as usize], v))
+        .collect();
+    by_count.sort_by(|a, b| b.cmp(a));
+    let shape: Vec<u8> = by_count.iter().map(|&(count, _)| count).collect();
+
+    let category = match (straight, flush, shape.as_slice()) {
+        (Some(_), true, _) => HandCategory::StraightFlush,
+        (_, _, [4, 1]) => HandCategory::FourOfAKind,
+        (_, _, [3, 2]) => HandCategory::FullHouse,
+        (_, true, _) => HandCategory::Flush,
+        (Some(_), _, _) => HandCategory::Straight,
+        (_, _, [3, 1, 1]) => HandCategory::ThreeOfAKind,
+        (_, _, [2, 2, 1]) => HandCategory::TwoPair,
+        (_, _, [2, 1, 1, 1]) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
+    };
+
+    // Five different values that make a straight compare on the top one alone
+    let tiebreak = match straight {
+        Some(top) => vec![top],
+        None => by_count
+            .iter()
+            .flat_map(|&(count, value)| std::iter::repeat_n(value, count as usize))
+            .collect(),
+    };
+    (category, tiebreak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hands::score_five_cards;
+    use crate::precompute::TableEntry;
+
+    /// Assert that `score_five_cards` gives these cards the reference category and tiebreak
+    fn assert_agrees(cards: [Card; 5]) -> TableEntry {
+        let score = score_five_cards(cards);
+        let (category, tiebreak) = reference_score(cards);
+        assert_eq!(score.category(), category, "{cards:?}");
+        match category {
+            HandCategory::Straight | HandCategory::StraightFlush => {
+                assert_eq!(score.kickers[0], tiebreak[0], "{cards:?}");
+            }
+            _ => assert_eq!(score.kickers.as_slice(), tiebreak.as_slice(), "{cards:?}"),
+        }
+        score
+    }
+
+    fn hand(cards: [&str; 5]) -> [Card; 5] {
+        cards.map(|card| card.parse().unwrap())
+    }
+
+    #[test]
+    fn agrees_on_category_boundaries() {
+        let cases = [
+            // The wheel is a five-high straight, and a five-high straight flush when suited
+            (
+                ["Ah", "2d", "3c", "4s", "5h"],
+                HandCategory::Straight,
+                vec![5],
+            ),
+            (
+                ["Ac", "2c", "3c", "4c", "5c"],
+                HandCategory::StraightFlush,
+                vec![5],
+            ),
+            (
+                ["Tc", "Jd", "Qh", "Ks", "Ad"],
+                HandCategory::Straight,
+                vec![14],
+            ),
+            (
+                ["Ts", "Js", "Qs", "Ks", "As"],
+                HandCategory::StraightFlush,
+                vec![14],
+            ),
+            // Straights don't wrap around the ace, and need all five values
+            (
+                ["Jc", "Qd", "Kh", "As", "2c"],
+                HandCategory::HighCard,
+                vec![14, 13, 12, 11, 2],
+            ),
+            (
+                ["Ac", "2d", "3h", "4s", "6c"],
+                HandCategory::HighCard,
+                vec![14, 6, 4, 3, 2],
+            ),
+            // Two pair orders the high pair, the low pair, then the kicker
+            (
+                ["2c", "2d", "Kh", "Ks", "Ac"],
+                HandCategory::TwoPair,
+                vec![13, 13, 2, 2, 14],
+            ),
+            (
+                ["Ac", "Ad", "3h", "3s", "2c"],
+                HandCategory::TwoPair,
+                vec![14, 14, 3, 3, 2],
+            ),
+            (
+                ["2c", "2d", "2h", "Ac", "Ad"],
+                HandCategory::FullHouse,
+                vec![2, 2, 2, 14, 14],
+            ),
+            (
+                ["Ac", "Ad", "Ah", "2c", "2d"],
+                HandCategory::FullHouse,
+                vec![14, 14, 14, 2, 2],
+            ),
+            (
+                ["2c", "2d", "2h", "2s", "Ac"],
+                HandCategory::FourOfAKind,
+                vec![2, 2, 2, 2, 14],
+            ),
+            (
+                ["9h", "7h", "5h", "3h", "2h"],
+                HandCategory::Flush,
+                vec![9, 7, 5, 3, 2],
+            ),
+        ];
+        for (cards, category, tiebreak) in cases {
+            let cards = hand(cards);
+            assert_eq!(reference_score(cards), (category, tiebreak), "{cards:?}");
+            assert_agrees(cards);
+        }
+    }
+
+    #[test]
+    fn agrees_on_sampled_hands() {
+        let mut rng = oorandom::Rand32::new(97);
+        let mut previous: Option<([Card; 5], TableEntry)> = None;
+        for _ in 0..50_000 {
+            let deck = crate::deck::Deck::new_deck_order().shuffle(&mut rng);
+            let cards: [Card; 5] = std::array::from_fn(|i| deck[i]);
+            let score = assert_agrees(cards);
+            // Both agree on which of two hands is stronger
+            if let Some((other, other_score)) = previous {
+                let reference = reference_score(cards).cmp(&reference_score(other));
+                assert_eq!(score.cmp(&other_score), reference, "{cards:?} vs {other:?}");
+            }
+            previous = Some((cards, score));
+        }
+    }
+}
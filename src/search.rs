@@ -1,34 +1,541 @@
+use crate::cards::Card;
 use crate::deck::*;
 use crate::game::*;
+use crate::hands::{HandCategory, Player};
 use crate::precompute::*;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A search takes the table behind an `Arc` so threaded searches can share it
+/// and the caller can keep using it once the search returns
+pub type SearchFn =
+    fn(usize, Arc<ScoreTable>, &SearchConfig, &mut dyn SearchObserver) -> SearchOutcome;
+
+/// Why a search stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The deck wins every game, so no deck does better
+    Perfect,
+    /// The search used up its iteration or generation budget
+    MaxIterations,
+    /// The search kept getting stuck and gave up
+    Stagnated,
+    /// The search ran out of time
+    Timeout,
+    /// Every search thread failed, so the deck is only a fallback, not a search result
+    Failed,
+}
 
-pub type SearchFn = fn(usize, ScoreTable) -> Deck;
+impl std::fmt::Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StopReason::Perfect => write!(f, "found a perfect deck"),
+            StopReason::MaxIterations => write!(f, "reached the iteration limit"),
+            StopReason::Stagnated => write!(f, "stopped improving"),
+            StopReason::Timeout => write!(f, "ran out of time"),
+            StopReason::Failed => write!(f, "every search thread failed"),
+        }
+    }
+}
 
-pub const REAL: bool = false;
+/// The best deck a search found, and whether it's known to be optimal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOutcome {
+    pub deck: Deck,
+    pub wins: usize,
+    pub reason: StopReason,
+}
 
-/// Calculate Hamming distance between two decks (how many positions differ)
-fn hamming_distance(deck1: &Deck, deck2: &Deck) -> usize {
-    deck1
-        .0
-        .iter()
-        .zip(deck2.0.iter())
-        .filter(|(a, b)| a != b)
-        .count()
+impl SearchOutcome {
+    /// A search that stopped for `reason`, unless `deck` is perfect anyway
+    /// `max_wins` is the most games any deck could win, see `SearchConfig::max_wins`
+    pub fn new(max_wins: usize, deck: Deck, wins: usize, reason: StopReason) -> Self {
+        let reason = if wins == max_wins {
+            StopReason::Perfect
+        } else {
+            reason
+        };
+        Self { deck, wins, reason }
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.reason == StopReason::Perfect
+    }
+}
+
+/// Receives a search's progress, so callers decide how (or whether) to report it
+/// A "step" is the algorithm's unit of progress: a generation, iteration, or restart
+pub trait SearchObserver {
+    /// The search found a deck that wins more games than any before it
+    fn on_improvement(&mut self, step: usize, score: usize, deck: &Deck);
+    /// Periodic update while the search runs without improving
+    fn on_tick(&mut self, step: usize, best_score: usize, stale: usize);
+    /// The population a generation of genetic search ends with
+    fn on_generation(&mut self, _generation: usize, _population: &[(Deck, usize)]) {}
+}
+
+/// `eprintln!` for a search's progress, unless `config.quiet` asks for only the result
+macro_rules! progress {
+    ($config:expr) => {
+        if !$config.quiet {
+            eprintln!()
+        }
+    };
+    ($config:expr, $($arg:tt)*) => {
+        if !$config.quiet {
+            eprintln!($($arg)*)
+        }
+    };
+}
+
+/// Prints progress to stderr on a single, overwritten line
+pub struct StderrObserver {
+    /// What a step is called in the output, e.g. "Generation"
+    unit: &'static str,
+    /// The denominator of the printed scores
+    max_wins: usize,
+}
+
+impl StderrObserver {
+    pub fn new(unit: &'static str, max_wins: usize) -> Self {
+        Self { unit, max_wins }
+    }
+}
+
+impl SearchObserver for StderrObserver {
+    fn on_improvement(&mut self, step: usize, score: usize, _deck: &Deck) {
+        eprint!(
+            "\r  ⚡ {} {}: Best score {}/{}          ",
+            self.unit, step, score, self.max_wins
+        );
+    }
+
+    fn on_tick(&mut self, step: usize, best_score: usize, stale: usize) {
+        eprint!(
+            "\r  🔄 {} {}: Best score {}/{} (stale: {})",
+            self.unit, step, best_score, self.max_wins, stale
+        );
+    }
+}
+
+/// Reports nothing, for `--quiet` runs where only the final result should print
+pub struct QuietObserver;
+
+impl SearchObserver for QuietObserver {
+    fn on_improvement(&mut self, _step: usize, _score: usize, _deck: &Deck) {}
+
+    fn on_tick(&mut self, _step: usize, _best_score: usize, _stale: usize) {}
+}
+
+/// Tuning parameters for the search algorithms
+/// Each algorithm reads only the fields it uses; the defaults are the values the
+/// algorithms were originally tuned with
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// Base RNG seed; threads and islands derive their own seeds from it
+    /// A fixed seed makes a single-threaded search (genetic, aco, hill-climbing)
+    /// fully reproducible. Threaded searches still race, so their results may vary
+    pub seed: u64,
+    /// Wall-clock limit, after which a search returns the best deck it has found
+    pub timeout: Option<Duration>,
+    /// The seat whose wins a search maximizes; 0 is the dealer
+    pub target_player: usize,
+    /// The opponents still in the hand, which the target must beat; None means every other seat
+    pub active_opponents: Option<Vec<usize>>,
+    /// Only score the cut positions a real dealer would allow, skipping 5 at each end
+    pub real: bool,
+    /// Which of the opponents stay in to showdown, and so have to be beaten
+    pub opponent_model: OpponentModel,
+    /// How each cut is dealt: the burns and the board size
+    pub deal: DealConfig,
+    /// Don't report progress on stderr
+    pub quiet: bool,
+    /// Known-good decks that genetic and beam search start from, ahead of random ones
+    pub seed_decks: Vec<Deck>,
+    /// Decks from `heuristic_seed_deck` that genetic and beam search start from, after
+    /// `seed_decks` and ahead of random ones
+    pub heuristic_seeds: usize,
+
+    // Genetic and island search
+    /// Individuals kept after each generation (per island for island search)
+    pub pop_size: usize,
+    /// Top individuals carried over unchanged
+    pub elite_size: usize,
+    /// Crossover children per generation in genetic search
+    pub num_crossovers: usize,
+    /// Crossover children per generation on each island
+    pub island_crossovers: usize,
+    /// SA-optimized mutants per generation
+    pub num_mutations: usize,
+    pub base_mutation_rate: f32,
+    /// Mutation rate once the population has stagnated
+    pub high_mutation_rate: f32,
+    /// Generations without improvement before boosting mutation
+    pub stagnation_threshold: usize,
+    /// Generations before genetic search gives up
+    pub max_generations: usize,
+    /// Have genetic search pick each mating's operator from `crossover_operators`, favouring
+    /// the ones whose children have beaten their better parent most often so far
+    /// Off, every mating uses `Deck::crossover`
+    pub adaptive_crossover: bool,
+    pub crossover_operators: Vec<CrossoverOperator>,
+    /// Rank genetic search survivors by Pareto dominance on (wins, total margin) rather than
+    /// by wins alone, so equal-win decks with better margins aren't culled at random
+    pub pareto_selection: bool,
+    pub num_islands: usize,
+    /// Generations each island evolves between migrations
+    pub migration_interval: usize,
+    /// Individuals each island sends to its neighbour
+    pub num_migrants: usize,
+    /// Migration cycles before island search gives up
+    /// `None` runs until a perfect deck is found or the timeout
+    pub max_cycles: Option<usize>,
+
+    // Local SA refinement used by genetic and island search
+    pub local_sa_iterations: usize,
+    /// Local SA budget once the population has stagnated
+    pub local_sa_iterations_stuck: usize,
+    pub local_sa_temp: f32,
+    pub local_sa_cooling: f32,
+    /// Which mutations local SA and the simulated-annealing workers may try
+    pub mutation_policy: MutationPolicy,
+
+    // Beam search
+    pub beam_width: usize,
+    pub mutations_per_beam: usize,
+    pub beam_iterations: usize,
+    pub beam_diversity_weight: f32,
+    /// How genetic and beam search measure how different a deck is from the rest
+    pub diversity_metric: DeckDistance,
+    /// Local SA budget for the first quarter of the iterations
+    pub beam_sa_iterations_early: usize,
+    pub beam_sa_iterations_late: usize,
+
+    // Ant colony optimization
+    pub num_ants: usize,
+    /// Iterations per restart
+    pub aco_iterations: usize,
+    /// Pheromone weight
+    pub aco_alpha: f32,
+    /// Heuristic weight
+    pub aco_beta: f32,
+    /// Evaporation rate
+    pub aco_rho: f32,
+    /// Top ants that deposit pheromone
+    pub elite_ants: usize,
+    pub aco_sa_iterations: usize,
+    /// Iterations without improvement before resetting pheromones
+    pub aco_restart_threshold: usize,
+    pub aco_max_restarts: usize,
+
+    // Parallel simulated annealing
+    pub sa_threads: usize,
+    pub sa_initial_temp: f32,
+    pub sa_cooling_rate: f32,
+    /// Base iterations without improvement before a random restart
+    pub sa_restart_interval: usize,
+    /// Restart once the temperature drops below this
+    pub sa_min_temp: f32,
+    /// Accept or reject moves on `hybrid_score`, so margins give a gradient across the plateaus
+    /// of equal win counts. The best deck is still the one that wins the most games
+    pub sa_hybrid_acceptance: bool,
+    /// Wins `hybrid_score` takes off an entirely implausible deck (see `shuffle_plausibility`),
+    /// steering searches towards decks that could pass for a real shuffle. 0 turns it off
+    /// Only beam search and `sa_hybrid_acceptance` rank on `hybrid_score`; the other searches
+    /// select on `wins` and see the penalty at most in their local refinement
+    pub plausibility_weight: f64,
+    /// Chance that a hill-climbing or SA restart perturbs the best deck so far instead of
+    /// reshuffling, keeping most of what the search has learned
+    pub restart_perturbation_chance: f32,
+    /// Mutations applied to the best deck on a perturbed restart
+    pub restart_perturbation_strength: usize,
+    /// Iterations each worker runs in `simulated_annealing_deterministic`
+    pub sa_iteration_budget: usize,
+    /// File the best deck is periodically saved to, and resumed from if it exists
+    pub checkpoint: Option<PathBuf>,
+    /// Iterations between checkpoints, or 0 to never write one
+    pub checkpoint_interval: usize,
+
+    // Tabu search
+    pub tabu_iterations: usize,
+    /// Random swaps evaluated per step
+    pub tabu_neighbors: usize,
+    /// Steps a swapped pair of positions stays tabu
+    pub tabu_tenure: usize,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            seed: 4,
+            timeout: None,
+            target_player: 0,
+            active_opponents: None,
+            real: false,
+            opponent_model: OpponentModel::Showdown,
+            deal: DealConfig::default(),
+            quiet: false,
+            seed_decks: Vec::new(),
+            heuristic_seeds: 0,
+
+            pop_size: 30,
+            elite_size: 3,
+            num_crossovers: 10,
+            island_crossovers: 15,
+            num_mutations: 15,
+            base_mutation_rate: 0.1,
+            high_mutation_rate: 0.3,
+            stagnation_threshold: 30,
+            max_generations: 200,
+            adaptive_crossover: false,
+            crossover_operators: vec![
+                CrossoverOperator::TwoPoint,
+                CrossoverOperator::Pmx,
+                CrossoverOperator::Cycle,
+                CrossoverOperator::Uniform,
+            ],
+            pareto_selection: false,
+            num_islands: 10,
+            migration_interval: 20,
+            num_migrants: 2,
+            max_cycles: None,
+
+            local_sa_iterations: 1000,
+            local_sa_iterations_stuck: 5000,
+            local_sa_temp: 5.0,
+            local_sa_cooling: 0.998,
+            mutation_policy: MutationPolicy::Mixed,
+
+            beam_width: 50,
+            mutations_per_beam: 10,
+            beam_iterations: 500,
+            beam_diversity_weight: 0.3,
+            diversity_metric: DeckDistance::Hamming,
+            beam_sa_iterations_early: 500,
+            beam_sa_iterations_late: 2000,
+
+            num_ants: 30,
+            aco_iterations: 500,
+            aco_alpha: 1.0,
+            aco_beta: 2.0,
+            aco_rho: 0.1,
+            elite_ants: 5,
+            aco_sa_iterations: 500,
+            aco_restart_threshold: 50,
+            aco_max_restarts: 10,
+
+            sa_threads: 10,
+            sa_initial_temp: 10.0,
+            sa_cooling_rate: 0.9999,
+            sa_restart_interval: 50_000,
+            sa_min_temp: 0.01,
+            sa_hybrid_acceptance: false,
+            plausibility_weight: 0.0,
+            restart_perturbation_chance: 0.0,
+            restart_perturbation_strength: 3,
+            sa_iteration_budget: 1_000_000,
+            checkpoint: None,
+            checkpoint_interval: 100_000,
+
+            tabu_iterations: 10_000,
+            tabu_neighbors: 100,
+            tabu_tenure: 30,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// When a search started now must stop, if it has a timeout
+    pub fn deadline(&self) -> Option<Instant> {
+        self.timeout.map(|timeout| Instant::now() + timeout)
+    }
+
+    /// The `n` decks a population starts as: `seed_decks`, then `heuristic_seeds` decks from
+    /// `heuristic_seed_deck`, then random shuffles for the rest
+    fn initial_decks(
+        &self,
+        num_players: usize,
+        table: &ScoreTable,
+        n: usize,
+        rng: &mut oorandom::Rand32,
+    ) -> Vec<Deck> {
+        let mut decks: Vec<Deck> = self.seed_decks.iter().take(n).cloned().collect();
+        let heuristic = self.heuristic_seeds.min(n - decks.len());
+        for _ in 0..heuristic {
+            decks.push(self.heuristic_seed_deck(num_players, table, rng));
+        }
+        let start = Deck::new_deck_order();
+        decks.resize_with(n, || start.shuffle(rng));
+        decks
+    }
+
+    /// A deck placed one card at a time from the top, biased towards `target_player` winning
+    /// Placing position `p` completes the deal at cut `p + 1`, of which it's the first card
+    /// dealt, so it's drawn from the remaining cards that win that cut where any do. Only the
+    /// cuts whose deal wraps past the bottom of the deck are left to chance
+    /// (`calculate_heuristic` can't do this: it scores positions alone, and every position is
+    /// dealt to every seat equally often across the cuts)
+    pub fn heuristic_seed_deck(
+        &self,
+        num_players: usize,
+        table: &ScoreTable,
+        rng: &mut oorandom::Rand32,
+    ) -> Deck {
+        let mut deck = Deck::new_deck_order().shuffle(rng);
+        let dealt = self.deal.cards_per_round(num_players);
+        for pos in 0..deck.len() {
+            let cut_pos = (pos + 1) % deck.len();
+            if pos + 1 < dealt || !cut_positions(self.real).contains(&cut_pos) {
+                continue;
+            }
+            // Cards below `pos` are still in random order, so trying them in turn tries
+            // the winning ones in random order
+            if let Some(winner) = (pos..deck.len()).find(|&candidate| {
+                let mut trial = deck;
+                trial.swap(pos, candidate);
+                self.wins_game(num_players, trial.cut(cut_pos), table)
+            }) {
+                deck.swap(pos, winner);
+            }
+        }
+        deck
+    }
+
+    /// The seats `target_player` has to beat
+    pub fn opponents(&self, num_players: usize) -> Vec<usize> {
+        match &self.active_opponents {
+            Some(active) => active.clone(),
+            None => all_opponents(num_players, self.target_player),
+        }
+    }
+
+    /// The most games any deck can win: one per scored cut position
+    pub fn max_wins(&self, num_players: usize) -> usize {
+        max_wins(num_players, self.real)
+    }
+
+    /// A search outcome, which is perfect if `wins` is this config's `max_wins`
+    pub fn outcome(
+        &self,
+        num_players: usize,
+        deck: Deck,
+        wins: usize,
+        reason: StopReason,
+    ) -> SearchOutcome {
+        SearchOutcome::new(self.max_wins(num_players), deck, wins, reason)
+    }
+
+    /// The games a deck wins for `target_player`: the fitness every search maximizes
+    pub fn wins(&self, num_players: usize, deck: &Deck, table: &ScoreTable) -> usize {
+        self.winning_positions(num_players, deck, table)
+            .count_ones() as usize
+    }
+
+    pub fn winning_positions(&self, num_players: usize, deck: &Deck, table: &ScoreTable) -> u64 {
+        let opponents = self.opponents(num_players);
+        let (target, real, model) = (self.target_player, self.real, self.opponent_model);
+        winning_positions_vs(
+            num_players,
+            target,
+            &opponents,
+            deck,
+            table,
+            real,
+            model,
+            self.deal,
+        )
+    }
+
+    /// `hybrid_score_vs` for `target_player`, less the `plausibility_weight` penalty
+    pub fn hybrid_score(&self, num_players: usize, deck: &Deck, table: &ScoreTable) -> f64 {
+        let opponents = self.opponents(num_players);
+        let (target, real, model) = (self.target_player, self.real, self.opponent_model);
+        let score = hybrid_score_vs(
+            num_players,
+            target,
+            &opponents,
+            deck,
+            table,
+            real,
+            model,
+            self.deal,
+        );
+        if self.plausibility_weight == 0.0 {
+            return score;
+        }
+        let implausibility = 1.0 - f64::from(shuffle_plausibility(deck));
+        score - self.plausibility_weight * WIN_WEIGHT * implausibility
+    }
+
+    /// The wins and total margin a deck scores for `target_player`, see `wins_and_margin_vs`
+    pub fn wins_and_margin(
+        &self,
+        num_players: usize,
+        deck: &Deck,
+        table: &ScoreTable,
+    ) -> (usize, f64) {
+        let opponents = self.opponents(num_players);
+        let (target, real, model) = (self.target_player, self.real, self.opponent_model);
+        wins_and_margin_vs(
+            num_players,
+            target,
+            &opponents,
+            deck,
+            table,
+            real,
+            model,
+            self.deal,
+        )
+    }
+
+    /// Where a search restarts from: a fresh shuffle, or with `restart_perturbation_chance`
+    /// a copy of `best` with `restart_perturbation_strength` aggressive mutations applied
+    pub fn restart_deck(&self, best: &Deck, rng: &mut oorandom::Rand32) -> Deck {
+        if rng.rand_float() < self.restart_perturbation_chance {
+            (0..self.restart_perturbation_strength).fold(*best, |deck, _| {
+                AdvancedMutation::generate(rng, self.high_mutation_rate).apply(deck, rng)
+            })
+        } else {
+            Deck::new_deck_order().shuffle(rng)
+        }
+    }
+
+    /// Whether `target_player` wins the round dealt from an already cut deck
+    pub fn wins_game(&self, num_players: usize, cut_deck: Deck, table: &ScoreTable) -> bool {
+        let opponents = self.opponents(num_players);
+        let (target, model) = (self.target_player, self.opponent_model);
+        player_wins_game_vs(
+            num_players,
+            target,
+            &opponents,
+            cut_deck,
+            table,
+            model,
+            self.deal,
+        )
+    }
+}
+
+fn past(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
 }
 
 /// Calculate average diversity of a deck compared to all other decks in population
-fn calculate_diversity(deck: &Deck, population: &[(Deck, usize)]) -> f32 {
+/// Distances are scaled to Hamming's 0..=52, so diversity weights mean the same for any metric
+fn calculate_diversity(deck: &Deck, population: &[(Deck, usize)], metric: DeckDistance) -> f32 {
     if population.is_empty() {
         return 0.0;
     }
 
     let total_distance: usize = population
         .iter()
-        .map(|(other_deck, _)| hamming_distance(deck, other_deck))
+        .map(|(other_deck, _)| deck_distance(deck, other_deck, metric))
         .sum();
 
-    total_distance as f32 / population.len() as f32
+    let scale = DECK_SIZE as f32 / metric.max(DECK_SIZE) as f32;
+    total_distance as f32 / population.len() as f32 * scale
 }
 
 /// Calculate a diversity-adjusted fitness score
@@ -38,28 +545,139 @@ fn diversity_fitness(score: usize, diversity: f32, diversity_weight: f32) -> f32
 }
 
 /// Select a parent index using fitness-proportionate (roulette wheel) selection
-/// Higher scores have higher probability of being selected
-fn select_parent(population: &[(Deck, usize)], rng: &mut oorandom::Rand32) -> usize {
-    // Calculate total fitness
-    let total_fitness: usize = population.iter().map(|(_, score)| score).sum();
+/// Index i is picked with probability fitness[i] / total, for any non-negative fitness
+/// (win counts, hybrid scores, ...); if every fitness is zero, the pick is uniform
+fn select_parent(fitness: &[f64], rng: &mut oorandom::Rand32) -> usize {
+    debug_assert!(
+        fitness.iter().all(|&f| f >= 0.0),
+        "fitness must be non-negative"
+    );
+    let total_fitness: f64 = fitness.iter().sum();
 
-    if total_fitness == 0 {
+    if total_fitness <= 0.0 {
         // All individuals have 0 fitness, select randomly
-        return rng.rand_range(0..population.len() as u32) as usize;
+        return rng.rand_range(0..fitness.len() as u32) as usize;
     }
 
     // Spin the roulette wheel
-    let mut spin = rng.rand_range(0..total_fitness as u32) as usize;
-
-    for (idx, (_, score)) in population.iter().enumerate() {
-        if spin < *score {
+    let spin = rng.rand_float() as f64 * total_fitness;
+    let mut cumulative = 0.0;
+    for (idx, &f) in fitness.iter().enumerate() {
+        cumulative += f;
+        if spin < cumulative {
             return idx;
         }
-        spin -= score;
     }
 
-    // Fallback (shouldn't reach here due to rounding)
-    population.len() - 1
+    // Rounding can leave the spin just past the last slot; take the last one with any fitness
+    fitness.iter().rposition(|&f| f > 0.0).unwrap()
+}
+
+/// How often each crossover operator's children have beaten their better parent, for
+/// adaptive operator selection
+struct CrossoverStats {
+    operators: Vec<CrossoverOperator>,
+    /// (children that beat their better parent, children bred) per operator
+    outcomes: Vec<(usize, usize)>,
+}
+
+impl CrossoverStats {
+    fn new(config: &SearchConfig) -> Self {
+        let operators = if config.adaptive_crossover {
+            config.crossover_operators.clone()
+        } else {
+            vec![CrossoverOperator::TwoPoint]
+        };
+        assert!(
+            !operators.is_empty(),
+            "crossover needs at least one operator"
+        );
+        let outcomes = vec![(0, 0); operators.len()];
+        Self {
+            operators,
+            outcomes,
+        }
+    }
+
+    /// The index of the operator to breed with, drawn in proportion to each one's success rate
+    /// (smoothed, so untried and unlucky operators still get picked)
+    /// With a single operator, no random number is drawn
+    fn pick(&self, rng: &mut oorandom::Rand32) -> usize {
+        if self.operators.len() == 1 {
+            return 0;
+        }
+        let rates: Vec<f64> = self
+            .outcomes
+            .iter()
+            .map(|&(successes, bred)| (successes as f64 + 1.0) / (bred as f64 + 2.0))
+            .collect();
+        select_parent(&rates, rng)
+    }
+
+    fn record(&mut self, operator: usize, improved: bool) {
+        let (successes, bred) = &mut self.outcomes[operator];
+        *successes += usize::from(improved);
+        *bred += 1;
+    }
+}
+
+/// The fitness `select_parent` spins over: each individual's win count
+fn win_fitness(population: &[(Deck, usize)]) -> Vec<f64> {
+    population.iter().map(|(_, score)| *score as f64).collect()
+}
+
+/// Whether `a` is at least as good as `b` on both wins and margin, and better on one
+fn dominates(a: (usize, f64), b: (usize, f64)) -> bool {
+    a.0 >= b.0 && a.1 >= b.1 && (a.0 > b.0 || a.1 > b.1)
+}
+
+/// Indices of `objectives` in selection order, best first, by non-dominated sorting
+///
+/// Individuals are peeled off in Pareto fronts: the first front is everyone nobody dominates,
+/// the next is everyone only the first front dominates, and so on. Within a front, the
+/// individuals furthest from their neighbours (by crowding distance) come first, so
+/// truncating keeps the front spread out; ties go to more wins
+fn pareto_order(objectives: &[(usize, f64)]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..objectives.len()).collect();
+    let mut order = Vec::with_capacity(objectives.len());
+    while !remaining.is_empty() {
+        let (front, rest): (Vec<usize>, Vec<usize>) = remaining.iter().partition(|&&i| {
+            !remaining
+                .iter()
+                .any(|&j| dominates(objectives[j], objectives[i]))
+        });
+        let crowding = crowding_distances(&front, objectives);
+        let mut ranked: Vec<(usize, f64)> = front.into_iter().zip(crowding).collect();
+        ranked.sort_by(|(a, da), (b, db)| {
+            db.total_cmp(da)
+                .then(objectives[*b].0.cmp(&objectives[*a].0))
+        });
+        order.extend(ranked.into_iter().map(|(i, _)| i));
+        remaining = rest;
+    }
+    order
+}
+
+/// How isolated each member of `front` is: the normalized size of the box its neighbours
+/// span on each objective, or infinite at either end of an objective
+fn crowding_distances(front: &[usize], objectives: &[(usize, f64)]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    let axes: [fn((usize, f64)) -> f64; 2] = [|(wins, _)| wins as f64, |(_, margin)| margin];
+    for axis in axes {
+        let value = |k: usize| axis(objectives[front[k]]);
+        let mut sorted: Vec<usize> = (0..front.len()).collect();
+        sorted.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+        let (first, last) = (sorted[0], sorted[front.len() - 1]);
+        distance[first] = f64::INFINITY;
+        distance[last] = f64::INFINITY;
+        let span = value(last) - value(first);
+        if span > 0.0 {
+            for w in sorted.windows(3) {
+                distance[w[1]] += (value(w[2]) - value(w[0])) / span;
+            }
+        }
+    }
+    distance
 }
 
 /// Perform local search using simulated annealing with hybrid scoring
@@ -70,25 +688,24 @@ pub fn local_search_sa(
     num_players: usize,
     table: &ScoreTable,
     max_iterations: usize,
-    initial_temp: f32,
-    cooling_rate: f32,
+    config: &SearchConfig,
     rng: &mut oorandom::Rand32,
 ) -> (Deck, usize) {
     let mut current_deck = starting_deck;
-    let mut current_score = hybrid_score(num_players, &current_deck, table, REAL);
-    let mut best_deck = current_deck.clone();
+    let mut current_score = config.hybrid_score(num_players, &current_deck, table);
+    let mut best_deck = current_deck;
     let mut best_score = current_score;
-    let mut best_wins = num_wins(num_players, &best_deck, table, REAL);
-    let mut temperature = initial_temp;
+    let mut best_wins = config.wins(num_players, &best_deck, table);
+    let mut temperature = config.local_sa_temp;
 
     for _ in 0..max_iterations {
         // Try a random modification using a single simple mutation
-        let mutation = generate_adaptive_mutations(rng, 0.2)
+        let mutation = generate_adaptive_mutations_with(rng, 0.2, config.mutation_policy)
             .into_iter()
             .next()
             .unwrap();
-        let new_deck = mutation.apply(current_deck.clone(), rng);
-        let new_score = hybrid_score(num_players, &new_deck, table, REAL);
+        let new_deck = mutation.apply(current_deck, rng);
+        let new_score = config.hybrid_score(num_players, &new_deck, table);
 
         // Calculate acceptance probability
         let accept = if new_score > current_score {
@@ -108,135 +725,372 @@ pub fn local_search_sa(
 
             if current_score > best_score {
                 best_score = current_score;
-                best_deck = current_deck.clone();
-                best_wins = num_wins(num_players, &best_deck, table, REAL);
+                best_deck = current_deck;
+                best_wins = config.wins(num_players, &best_deck, table);
 
                 // Early exit if perfect solution found
-                if best_wins == max_wins(REAL) {
+                if best_wins == config.max_wins(num_players) {
                     return (best_deck, best_wins);
                 }
             }
         }
 
         // Cool down
-        temperature *= cooling_rate;
+        temperature *= config.local_sa_cooling;
     }
 
     (best_deck, best_wins)
 }
 
-pub fn run_random_search(num_players: usize) -> std::io::Result<()> {
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  Loading precomputed hand scores...");
-    let f = std::fs::File::open("hands")?;
-    let table = load_table(f)?;
-    eprintln!("  ✓ Loaded successfully");
-    eprintln!();
-    eprintln!("  Searching for optimal deck ({} players)...", num_players);
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!();
-    let result = simulated_annealing(num_players, table);
-    eprintln!();
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  ✓ Found optimal deck!");
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("{}", result);
+/// Reject a player count or target seat the search can't deal or score
+fn check_search(num_players: usize, config: &SearchConfig) -> std::io::Result<()> {
+    let invalid = |e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e);
+    check_player_count(num_players, 52, config.deal).map_err(|e| invalid(e.to_string()))?;
+    if config.target_player >= num_players {
+        return Err(invalid(format!(
+            "target player {} isn't one of the {num_players} players",
+            config.target_player
+        )));
+    }
+    let active = config.active_opponents.iter().flatten();
+    if let Some(seat) = active.copied().find(|&seat| seat >= num_players) {
+        return Err(invalid(format!(
+            "active opponent {seat} isn't one of the {num_players} players"
+        )));
+    }
+    if config
+        .opponents(num_players)
+        .contains(&config.target_player)
+    {
+        return Err(invalid(format!(
+            "target player {} can't be its own opponent",
+            config.target_player
+        )));
+    }
     Ok(())
 }
 
-pub fn run_search(num_players: usize, search: SearchFn) -> std::io::Result<()> {
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  Loading precomputed hand scores...");
-    let f = std::fs::File::open("hands")?;
-    let table = load_table(f)?;
-    eprintln!("  ✓ Loaded successfully");
-    eprintln!();
-    eprintln!("  Searching for optimal deck ({} players)...", num_players);
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!();
-    let result = search(num_players, table);
+pub fn run_random_search(
+    num_players: usize,
+    table_path: &Path,
+    config: &SearchConfig,
+) -> std::io::Result<()> {
+    check_search(num_players, config)?;
+    progress!(config, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    progress!(config, "  Loading precomputed hand scores...");
+    let table = load_table_file(table_path)?;
+    progress!(config, "  ✓ Loaded successfully");
+    progress!(config);
+    progress!(
+        config,
+        "  Searching for optimal deck ({} players)...",
+        num_players
+    );
+    progress!(config, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    progress!(config);
+    let mut observer = StderrObserver::new("Iteration", config.max_wins(num_players));
+    let outcome = simulated_annealing(num_players, Arc::new(table), config, &mut observer);
+    print_outcome(config.max_wins(num_players), &outcome);
+    println!("{}", outcome.deck);
+    Ok(())
+}
+
+/// Report how a search ended on stderr
+fn print_outcome(max_wins: usize, outcome: &SearchOutcome) {
     eprintln!();
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  ✓ Found optimal deck!");
+    let score = format!("{}/{}", outcome.wins, max_wins);
+    match outcome.reason {
+        StopReason::Perfect => eprintln!("  ✓ Found optimal deck! ({score})"),
+        StopReason::MaxIterations => {
+            eprintln!("  ⚠️  Hit the iteration limit. Best found: {score}")
+        }
+        StopReason::Stagnated => eprintln!("  ⚠️  Search stagnated. Best found: {score}"),
+        StopReason::Timeout => eprintln!("  ⏱️  Timed out. Best found: {score}"),
+        StopReason::Failed => eprintln!("  ❌ Every search thread failed. Fallback deck: {score}"),
+    }
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("{}", result);
+}
+
+/// How `run_search` prints the deck it found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// The deck's `Display` form
+    #[default]
+    Human,
+    /// A JSON `SearchResult`
+    Json,
+}
+
+/// The outcome of a search, as printed by `--format json`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SearchResult {
+    pub num_players: usize,
+    pub wins: usize,
+    /// Card ids, as `Deck::as_card_ids` lists them
+    pub deck: Vec<u8>,
+    pub algorithm: String,
+    /// Whether the deck wins every game, see `SearchOutcome::is_perfect`
+    pub perfect: bool,
+}
+
+impl SearchResult {
+    pub fn new(num_players: usize, outcome: &SearchOutcome, algorithm: &str) -> Self {
+        Self {
+            num_players,
+            wins: outcome.wins,
+            deck: outcome.deck.as_card_ids(),
+            algorithm: algorithm.to_string(),
+            perfect: outcome.is_perfect(),
+        }
+    }
+}
+
+/// Load the table at `table_path`, run `search` on it, and print the deck found
+/// `config.quiet` skips the banners on stderr, leaving only the deck
+pub fn run_search(
+    num_players: usize,
+    table_path: &Path,
+    search: SearchFn,
+    algorithm: &str,
+    config: &SearchConfig,
+    format: OutputFormat,
+    observer: &mut dyn SearchObserver,
+) -> std::io::Result<()> {
+    check_search(num_players, config)?;
+    let quiet = config.quiet;
+    if !quiet {
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        eprintln!("  Loading precomputed hand scores...");
+    }
+    let table = load_table_file(table_path)?;
+    if !quiet {
+        eprintln!("  ✓ Loaded successfully");
+        eprintln!();
+        eprintln!("  Searching for optimal deck ({} players)...", num_players);
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        eprintln!();
+    }
+    let outcome = search(num_players, Arc::new(table), config, observer);
+    if !quiet {
+        print_outcome(config.max_wins(num_players), &outcome);
+    }
+    match format {
+        OutputFormat::Human => println!("{}", outcome.deck),
+        OutputFormat::Json => {
+            let result = SearchResult::new(num_players, &outcome, algorithm);
+            println!("{}", serde_json::to_string(&result)?);
+        }
+    }
+    Ok(())
+}
+
+/// Load the table at `table_path`, run `genetic_search_top_n`, and print the `n` best
+/// distinct decks it found, best first
+pub fn run_search_top_n(
+    num_players: usize,
+    table_path: &Path,
+    config: &SearchConfig,
+    n: usize,
+    format: OutputFormat,
+    observer: &mut dyn SearchObserver,
+) -> std::io::Result<()> {
+    check_search(num_players, config)?;
+    let table = load_table_file(table_path)?;
+    let top = genetic_search_top_n(num_players, Arc::new(table), config, n, observer);
+    let max_wins = config.max_wins(num_players);
+    for (deck, wins) in top {
+        match format {
+            OutputFormat::Human => println!("{wins}/{max_wins} {deck}"),
+            OutputFormat::Json => {
+                let result = SearchResult {
+                    num_players,
+                    wins,
+                    deck: deck.as_card_ids(),
+                    algorithm: "genetic".to_string(),
+                    perfect: wins == max_wins,
+                };
+                println!("{}", serde_json::to_string(&result)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The searches `bench` compares. The deterministic SA variant stands in for
+/// `simulated_annealing`, whose workers race to report improvements
+pub const BENCH_SEARCHES: [(SearchFn, &str); 5] = [
+    (genetic_search, "genetic"),
+    (island_genetic_search, "island"),
+    (beam_search, "beam"),
+    (ant_colony_search, "aco"),
+    (
+        simulated_annealing_deterministic,
+        "simulated-annealing-deterministic",
+    ),
+];
+
+/// How one search did in a `bench` run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+    pub algorithm: &'static str,
+    pub outcome: SearchOutcome,
+    /// When the search first reported its best deck, or `elapsed` if it never reported it
+    pub time_to_best: Duration,
+    pub elapsed: Duration,
+}
+
+/// Remembers when a search last improved
+struct BenchObserver {
+    started: Instant,
+    best: Option<(usize, Duration)>,
+}
+
+impl SearchObserver for BenchObserver {
+    fn on_improvement(&mut self, _step: usize, score: usize, _deck: &Deck) {
+        if self.best.is_none_or(|(best, _)| score > best) {
+            self.best = Some((score, self.started.elapsed()));
+        }
+    }
+
+    fn on_tick(&mut self, _step: usize, _best_score: usize, _stale: usize) {}
+}
+
+/// Run every search in `BENCH_SEARCHES` one after another with the same config
+/// Each search is seeded from `config.seed`, so only where the timeout cuts it off
+/// depends on the machine
+pub fn bench(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+) -> Vec<BenchResult> {
+    BENCH_SEARCHES
+        .iter()
+        .map(|&(search, algorithm)| {
+            let mut observer = BenchObserver {
+                started: Instant::now(),
+                best: None,
+            };
+            let outcome = search(num_players, Arc::clone(&table), config, &mut observer);
+            let elapsed = observer.started.elapsed();
+            let time_to_best = match observer.best {
+                Some((score, at)) if score >= outcome.wins => at,
+                _ => elapsed,
+            };
+            BenchResult {
+                algorithm,
+                outcome,
+                time_to_best,
+                elapsed,
+            }
+        })
+        .collect()
+}
+
+/// Load the table at `table_path`, `bench` every search on it, and print a table of results
+pub fn run_bench(
+    num_players: usize,
+    table_path: &Path,
+    config: &SearchConfig,
+) -> std::io::Result<()> {
+    check_search(num_players, config)?;
+    let table = Arc::new(load_table_file(table_path)?);
+    let results = bench(num_players, table, config);
+
+    println!(
+        "{:<34} {:>7} {:>13} {:>9}  stopped because",
+        "algorithm", "wins", "time to best", "elapsed"
+    );
+    for result in results {
+        let wins = format!("{}/{}", result.outcome.wins, config.max_wins(num_players));
+        println!(
+            "{:<34} {:>7} {:>12.2}s {:>8.2}s  {}",
+            result.algorithm,
+            wins,
+            result.time_to_best.as_secs_f64(),
+            result.elapsed.as_secs_f64(),
+            result.outcome.reason
+        );
+    }
     Ok(())
 }
 
 /// Steepest-ascent hill climbing with random restarts.
 ///
-/// 1. Pick a random deck.
+/// 1. Pick a random deck, or on later restarts maybe perturb the best one (see `restart_deck`).
 /// 2. If it wins every game (max_wins), we're done.
 /// 3. Try every pair of swaps, keeping the one that wins the most games.
 /// 4. If no swap increases the score, we're on a hill with no steps up: restart from (1).
-pub fn hill_climbing(num_players: usize, table: ScoreTable) -> Deck {
-    let mut rng = oorandom::Rand32::new(4);
+pub fn hill_climbing(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
 
+    let mut best_ever_deck = Deck::new_deck_order();
     let mut best_ever_score = 0;
 
-    eprintln!("  🏔️  Starting hill climbing search...");
-    eprintln!();
+    progress!(config, "  🏔️  Starting hill climbing search...");
+    progress!(config);
 
     let mut restart = 0;
     loop {
         restart += 1;
 
-        // 1. Pick a random deck.
-        let mut deck = Deck::new_deck_order().shuffle(&mut rng);
-        let mut current_score = num_wins(num_players, &deck, &table, REAL);
+        // 1. Pick a random deck, or perturb the best one once we've climbed a hill.
+        let mut deck = if restart == 1 {
+            Deck::new_deck_order().shuffle(&mut rng)
+        } else {
+            config.restart_deck(&best_ever_deck, &mut rng)
+        };
+        let mut current_mask = config.winning_positions(num_players, &deck, &table);
+        let mut current_score = current_mask.count_ones() as usize;
 
         loop {
-            // 2. If we win every game, we're done.
-            if current_score == max_wins(REAL) {
-                eprintln!();
-                eprintln!("  ✓ Perfect deck found on restart {}!", restart);
-                return deck;
+            if past(deadline) {
+                let (deck, score) = if current_score >= best_ever_score {
+                    (deck, current_score)
+                } else {
+                    (best_ever_deck, best_ever_score)
+                };
+                return config.outcome(num_players, deck, score, StopReason::Timeout);
             }
 
-            // 3. Try every pair of swaps, keeping the one that wins the most games.
-            let mut best_swap: Option<(usize, usize)> = None;
-            let mut best_swap_score = current_score;
-
-            for i in 0..52 {
-                for j in (i + 1)..52 {
-                    deck.0.swap(i, j);
-                    let new_score = num_wins(num_players, &deck, &table, REAL);
-                    deck.0.swap(i, j); // undo
-
-                    if new_score > best_swap_score {
-                        best_swap_score = new_score;
-                        best_swap = Some((i, j));
-                    }
-                }
+            // 2. If we win every game, we're done.
+            if current_score == config.max_wins(num_players) {
+                progress!(config);
+                progress!(config, "  ✓ Perfect deck found on restart {}!", restart);
+                return config.outcome(num_players, deck, current_score, StopReason::Perfect);
             }
 
-            match best_swap {
-                Some((i, j)) => {
+            // 3. Try every pair of swaps, keeping the one that wins the most games.
+            match steepest_swap(num_players, deck, current_mask, config, &table) {
+                Some(((i, j), new_mask)) => {
                     // Take the best step up.
-                    deck.0.swap(i, j);
-                    current_score = best_swap_score;
+                    deck.swap(i, j);
+                    current_mask = new_mask;
+                    current_score = new_mask.count_ones() as usize;
 
                     if current_score > best_ever_score {
                         best_ever_score = current_score;
+                        best_ever_deck = deck;
+                        observer.on_improvement(restart, current_score, &deck);
                     }
-
-                    eprint!(
-                        "\r  ⚡ Restart {}: climbed to {}/{}          ",
-                        restart,
-                        current_score,
-                        max_wins(REAL)
-                    );
                 }
                 None => {
                     // 4. No swap increases the score: local optimum reached.
-                    eprintln!();
-                    eprintln!(
+                    progress!(config);
+                    progress!(
+                        config,
                         "  🛑 Restart {}: reached a hill with no steps up at {}/{} (best ever: {}/{}). Restarting...",
                         restart,
                         current_score,
-                        max_wins(REAL),
+                        config.max_wins(num_players),
                         best_ever_score,
-                        max_wins(REAL)
+                        config.max_wins(num_players)
                     );
                     break; // goto 1
                 }
@@ -245,106 +1099,219 @@ pub fn hill_climbing(num_players: usize, table: ScoreTable) -> Deck {
     }
 }
 
-pub fn genetic_search(num_players: usize, table: ScoreTable) -> Deck {
-    const POP_SIZE: usize = 30; // Reduced since SA is expensive per individual
-    const ELITE_SIZE: usize = 3; // Top 3 always survive unchanged
-    const NUM_CROSSOVERS: usize = 10; // Number of crossover children to create
-    const NUM_MUTATIONS: usize = 15; // Number of SA-optimized mutations to create
-    const BASE_MUTATION_RATE: f32 = 0.1;
-    const HIGH_MUTATION_RATE: f32 = 0.3;
-    const STAGNATION_THRESHOLD: usize = 30; // Generations without improvement before boosting mutation
-    const MAX_GENERATIONS: usize = 200; // Maximum generations before giving up
+/// The swap of two positions that wins the most games, with the `winning_positions` mask
+/// after it, or `None` if no swap wins more than `mask` does: a true local optimum
+/// Tries all C(52, 2) pairs, replaying only the cuts each one changes (see `num_wins_delta`)
+fn steepest_swap(
+    num_players: usize,
+    mut deck: Deck,
+    mask: u64,
+    config: &SearchConfig,
+    table: &ScoreTable,
+) -> Option<((usize, usize), u64)> {
+    let mut best_swap = None;
+    let mut best_score = mask.count_ones();
+    for i in 0..deck.len() {
+        for j in (i + 1)..deck.len() {
+            deck.swap(i, j);
+            let swapped = (i, j);
+            let new_mask = num_wins_delta(
+                num_players,
+                &deck,
+                swapped,
+                mask,
+                config.real,
+                config.deal,
+                |cut| config.wins_game(num_players, cut, table),
+            );
+            deck.swap(i, j); // undo
 
-    let start = Deck::new_deck_order();
-    let mut rng = oorandom::Rand32::new(4);
+            if new_mask.count_ones() > best_score {
+                best_score = new_mask.count_ones();
+                best_swap = Some(((i, j), new_mask));
+            }
+        }
+    }
+    best_swap
+}
 
-    eprintln!("  🧬 Initializing population (size: {})...", POP_SIZE);
-    // Initialize the population and evaluate fitness
-    let mut scored_population: Vec<(Deck, usize)> = Vec::with_capacity(POP_SIZE);
-    for _ in 0..POP_SIZE {
-        let deck = start.clone().shuffle(&mut rng);
-        let score = num_wins(num_players, &deck, &table, REAL);
-        scored_population.push((deck, score));
+pub fn genetic_search(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let (mut population, reason) = genetic_population(num_players, &table, config, observer);
+    let (deck, wins) = population.swap_remove(0);
+    config.outcome(num_players, deck, wins, reason)
+}
+
+/// Genetic search, returning the `n` highest-scoring distinct decks of the final population
+/// Sorted by descending win count
+pub fn genetic_search_top_n(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    n: usize,
+    observer: &mut dyn SearchObserver,
+) -> Vec<(Deck, usize)> {
+    top_n_distinct(
+        genetic_population(num_players, &table, config, observer).0,
+        n,
+    )
+}
+
+/// Keep the `n` best distinct decks, sorted by descending win count
+fn top_n_distinct(mut population: Vec<(Deck, usize)>, n: usize) -> Vec<(Deck, usize)> {
+    population.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    let mut top: Vec<(Deck, usize)> = Vec::with_capacity(n);
+    for (deck, score) in population {
+        if top.len() == n {
+            break;
+        }
+        if !top.iter().any(|(d, _)| d == &deck) {
+            top.push((deck, score));
+        }
     }
+    top
+}
+
+/// Run the genetic search, returning its final population with the best deck first,
+/// and why it stopped
+fn genetic_population(
+    num_players: usize,
+    table: &ScoreTable,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> (Vec<(Deck, usize)>, StopReason) {
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
+
+    progress!(
+        config,
+        "  🧬 Initializing population (size: {})...",
+        config.pop_size
+    );
+    // Initialize the population and evaluate fitness
+    let mut scored_population: Vec<(Deck, usize)> = config
+        .initial_decks(num_players, table, config.pop_size, &mut rng)
+        .into_iter()
+        .map(|deck| {
+            let score = config.wins(num_players, &deck, table);
+            (deck, score)
+        })
+        .collect();
 
     let initial_best = scored_population
         .iter()
         .map(|(_, score)| *score)
         .max()
         .unwrap();
-    eprintln!("  ✓ Initial population created");
-    eprintln!(
+    progress!(config, "  ✓ Initial population created");
+    progress!(
+        config,
         "  📊 Initial best score: {}/{}",
         initial_best,
-        max_wins(REAL)
+        config.max_wins(num_players)
     );
-    eprintln!();
+    progress!(config);
+
+    // A seeded deck may already be perfect
+    if initial_best == config.max_wins(num_players) {
+        scored_population.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        return (scored_population, StopReason::Perfect);
+    }
 
     let mut generation = 0;
     let mut best_score = initial_best;
     let mut generations_without_improvement = 0;
+    let mut crossover_stats = CrossoverStats::new(config);
 
     loop {
         generation += 1;
 
         // Check generation limit
-        if generation > MAX_GENERATIONS {
-            eprintln!();
-            eprintln!(
+        if generation > config.max_generations {
+            progress!(config);
+            progress!(
+                config,
                 "  ⚠️  Max generations ({}) reached. Best found: {}/{}",
-                MAX_GENERATIONS, best_score, max_wins(REAL)
+                config.max_generations,
+                best_score,
+                config.max_wins(num_players)
             );
-            return scored_population[0].0.clone();
+            return (scored_population, StopReason::MaxIterations);
+        }
+
+        if past(deadline) {
+            progress!(config);
+            progress!(
+                config,
+                "  ⏱️  Timed out after {} generations. Best found: {}/{}",
+                generation - 1,
+                best_score,
+                config.max_wins(num_players)
+            );
+            scored_population.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+            return (scored_population, StopReason::Timeout);
         }
 
         // Adaptive mutation rate and diversity weight based on progress
-        let (mutation_rate, diversity_weight) = if generations_without_improvement > STAGNATION_THRESHOLD {
-            // When stuck, use high mutation and high diversity pressure
-            (HIGH_MUTATION_RATE, 0.5)
-        } else {
-            // When progressing, focus more on fitness
-            (BASE_MUTATION_RATE, 0.1)
-        };
+        let (mutation_rate, diversity_weight) =
+            if generations_without_improvement > config.stagnation_threshold {
+                // When stuck, use high mutation and high diversity pressure
+                (config.high_mutation_rate, 0.5)
+            } else {
+                // When progressing, focus more on fitness
+                (config.base_mutation_rate, 0.1)
+            };
 
         // Extract just the decks for breeding (we'll re-score offspring)
-        let population: Vec<Deck> = scored_population.iter().map(|(d, _)| d.clone()).collect();
+        let population: Vec<Deck> = scored_population.iter().map(|(d, _)| *d).collect();
 
         // Phase 1: grow the population via crossover and mutation
         let mut new_generation: Vec<(Deck, usize)> = Vec::new();
 
         // ELITISM: Preserve the best individuals unchanged
-        for i in 0..ELITE_SIZE.min(scored_population.len()) {
-            new_generation.push(scored_population[i].clone());
+        for i in 0..config.elite_size.min(scored_population.len()) {
+            new_generation.push(scored_population[i]);
         }
 
         // Create children through crossover - use fitness-proportionate selection
-        for _ in 0..NUM_CROSSOVERS {
-            let i = select_parent(&scored_population, &mut rng);
-            let j = select_parent(&scored_population, &mut rng);
+        let fitness = win_fitness(&scored_population);
+        for _ in 0..config.num_crossovers {
+            let i = select_parent(&fitness, &mut rng);
+            let j = select_parent(&fitness, &mut rng);
             if i != j {
-                let child = Deck::crossover(&population[i], &population[j], &mut rng);
-                let score = num_wins(num_players, &child, &table, REAL);
+                let operator = crossover_stats.pick(&mut rng);
+                let child = crossover_stats.operators[operator].apply(
+                    &population[i],
+                    &population[j],
+                    &mut rng,
+                );
+                debug_assert!(child.is_valid_permutation());
+                let score = config.wins(num_players, &child, table);
+                let better_parent = scored_population[i].1.max(scored_population[j].1);
+                crossover_stats.record(operator, score > better_parent);
                 new_generation.push((child, score));
             }
         }
 
         // Create mutations using SA-based local search
         // Adaptive SA budget: low when progressing, high when stagnating
-        let sa_iterations = if generations_without_improvement > STAGNATION_THRESHOLD {
-            5000 // Deep search when stuck
+        let sa_iterations = if generations_without_improvement > config.stagnation_threshold {
+            config.local_sa_iterations_stuck // Deep search when stuck
         } else {
-            1000 // Fast search when progressing
+            config.local_sa_iterations // Fast search when progressing
         };
-        let sa_temp = 5.0;
-        let sa_cooling = 0.998;
 
-        for _ in 0..NUM_MUTATIONS {
+        for _ in 0..config.num_mutations {
             // Select parent using fitness-proportionate selection
-            let parent_idx = select_parent(&scored_population, &mut rng);
+            let parent_idx = select_parent(&fitness, &mut rng);
             let parent = &population[parent_idx];
 
             // Apply 1-2 simple mutations to create starting point
-            let mut child = parent.clone();
+            let mut child = *parent;
             let num_initial_mutations = if mutation_rate > 0.2 { 2 } else { 1 };
             for _ in 0..num_initial_mutations {
                 let mutation = generate_adaptive_mutations(&mut rng, mutation_rate)
@@ -355,32 +1322,36 @@ pub fn genetic_search(num_players: usize, table: ScoreTable) -> Deck {
             }
 
             // Run local search to optimize
-            let (optimized_child, score) = local_search_sa(
-                child,
-                num_players,
-                &table,
-                sa_iterations,
-                sa_temp,
-                sa_cooling,
-                &mut rng,
-            );
+            let (optimized_child, score) =
+                local_search_sa(child, num_players, table, sa_iterations, config, &mut rng);
             new_generation.push((optimized_child, score));
         }
 
         // Add rest of population (already scored, excluding elites which are already added)
-        for i in ELITE_SIZE..scored_population.len() {
-            new_generation.push(scored_population[i].clone());
+        for i in config.elite_size..scored_population.len() {
+            new_generation.push(scored_population[i]);
         }
 
-        // Sort by diversity-adjusted fitness when stagnating
-        if generations_without_improvement > STAGNATION_THRESHOLD / 2 {
+        if config.pareto_selection {
+            // Rank on wins and margin together; crowding keeps the fronts diverse
+            let objectives: Vec<(usize, f64)> = new_generation
+                .iter()
+                .map(|(deck, _)| config.wins_and_margin(num_players, deck, table))
+                .collect();
+            new_generation = pareto_order(&objectives)
+                .into_iter()
+                .map(|i| new_generation[i])
+                .collect();
+        } else if generations_without_improvement > config.stagnation_threshold / 2 {
+            // Sort by diversity-adjusted fitness when stagnating
             // Calculate diversity for each deck and use diversity-adjusted fitness
             let mut diversity_scored: Vec<_> = new_generation
                 .iter()
                 .map(|(deck, score)| {
-                    let diversity = calculate_diversity(deck, &new_generation);
+                    let diversity =
+                        calculate_diversity(deck, &new_generation, config.diversity_metric);
                     let adjusted_fitness = diversity_fitness(*score, diversity, diversity_weight);
-                    (deck.clone(), *score, adjusted_fitness)
+                    (*deck, *score, adjusted_fitness)
                 })
                 .collect();
 
@@ -394,6 +1365,9 @@ pub fn genetic_search(num_players: usize, table: ScoreTable) -> Deck {
             new_generation.sort_by_key(|(_, score)| *score);
             new_generation.reverse();
         }
+        // The diversity and Pareto orders may rank the best deck low; keep it first so truncation
+        // and elitism never lose it
+        promote_best(&mut new_generation);
 
         let current_best_score = new_generation[0].1;
 
@@ -401,106 +1375,106 @@ pub fn genetic_search(num_players: usize, table: ScoreTable) -> Deck {
         if current_best_score > best_score {
             best_score = current_best_score;
             generations_without_improvement = 0;
-            eprint!(
-                "\r  ⚡ Generation {}: Best score {}/{} (pop: {}, mut: {:.2})",
-                generation,
-                best_score,
-                max_wins(REAL),
-                new_generation.len(),
-                mutation_rate
-            );
+            observer.on_improvement(generation, best_score, &new_generation[0].0);
         } else {
             generations_without_improvement += 1;
             if generation % 10 == 0 {
-                // Print periodic update even without improvement
-                eprint!(
-                    "\r  🔄 Generation {}: Best score {}/{} (pop: {}, mut: {:.2}, stale: {})",
-                    generation,
-                    best_score,
-                    max_wins(REAL),
-                    new_generation.len(),
-                    mutation_rate,
-                    generations_without_improvement
-                );
+                // Report periodically even without improvement
+                observer.on_tick(generation, best_score, generations_without_improvement);
             }
         }
 
-        if current_best_score == max_wins(REAL) {
-            eprintln!();
-            eprintln!("  ✓ Perfect deck found after {} generations!", generation);
-            return new_generation[0].0.clone();
+        if current_best_score == config.max_wins(num_players) {
+            progress!(config);
+            progress!(
+                config,
+                "  ✓ Perfect deck found after {} generations!",
+                generation
+            );
+            return (new_generation, StopReason::Perfect);
         }
 
         // Phase 2: Selection - keep fixed population size
         // This enforces selection pressure by removing worst individuals
-        new_generation.truncate(POP_SIZE);
+        new_generation.truncate(config.pop_size);
+        observer.on_generation(generation, &new_generation);
 
         scored_population = new_generation;
     }
 }
 
+/// Move the first of the highest-scoring individuals to the front, keeping the others in order
+fn promote_best(population: &mut [(Deck, usize)]) {
+    let best = population
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, (_, score))| *score)
+        .map(|(i, _)| i);
+    if let Some(best) = best {
+        population[..=best].rotate_right(1);
+    }
+}
+
 /// Evolve a single island
 fn evolve_island(
-    _island_id: usize,
     mut population: Vec<(Deck, usize)>,
     num_players: usize,
     table: Arc<ScoreTable>,
     generations: usize,
     seed: u64,
+    config: &SearchConfig,
+    deadline: Option<Instant>,
 ) -> Vec<(Deck, usize)> {
-    const ISLAND_POP_SIZE: usize = 30;
-    const ELITE_SIZE: usize = 3;
-    const NUM_CROSSOVERS: usize = 15;
-    const NUM_MUTATIONS: usize = 15;
-    const BASE_MUTATION_RATE: f32 = 0.1;
-    const HIGH_MUTATION_RATE: f32 = 0.3;
-    const STAGNATION_THRESHOLD: usize = 30;
 
     let mut rng = oorandom::Rand32::new(seed);
     let mut stagnation = 0;
     let mut best_score = population[0].1;
 
     for _ in 0..generations {
-        let mutation_rate = if stagnation > STAGNATION_THRESHOLD {
-            HIGH_MUTATION_RATE
+        if past(deadline) {
+            break;
+        }
+        let mutation_rate = if stagnation > config.stagnation_threshold {
+            config.high_mutation_rate
         } else {
-            BASE_MUTATION_RATE
+            config.base_mutation_rate
         };
 
-        let population_decks: Vec<Deck> = population.iter().map(|(d, _)| d.clone()).collect();
+        let population_decks: Vec<Deck> = population.iter().map(|(d, _)| *d).collect();
         let mut new_generation: Vec<(Deck, usize)> = Vec::new();
 
         // Elitism
-        for i in 0..ELITE_SIZE.min(population.len()) {
-            new_generation.push(population[i].clone());
+        for i in 0..config.elite_size.min(population.len()) {
+            new_generation.push(population[i]);
         }
 
         // Crossover with fitness-proportionate selection
-        for _ in 0..NUM_CROSSOVERS {
-            let i = select_parent(&population, &mut rng);
-            let j = select_parent(&population, &mut rng);
+        let fitness = win_fitness(&population);
+        for _ in 0..config.island_crossovers {
+            let i = select_parent(&fitness, &mut rng);
+            let j = select_parent(&fitness, &mut rng);
             if i != j {
                 let child = Deck::crossover(&population_decks[i], &population_decks[j], &mut rng);
-                let score = num_wins(num_players, &child, &table, REAL);
+                debug_assert!(child.is_valid_permutation());
+                let score = config.wins(num_players, &child, &table);
                 new_generation.push((child, score));
             }
         }
 
         // Mutation using SA-based local search
-        let sa_iterations = if stagnation > STAGNATION_THRESHOLD {
-            5000 // Deep search when stuck
+        let sa_iterations = if stagnation > config.stagnation_threshold {
+            config.local_sa_iterations_stuck // Deep search when stuck
         } else {
-            1000 // Fast search when progressing
+            config.local_sa_iterations // Fast search when progressing
         };
-        let sa_temp = 5.0;
-        let sa_cooling = 0.998;
 
-        for _ in 0..NUM_MUTATIONS {
+        for _ in 0..config.num_mutations {
             let parent_idx = rng.rand_range(0..population.len() as u32) as usize;
             let parent = &population[parent_idx];
 
             // Apply 1-2 simple mutations to create starting point
-            let mut child = parent.0.clone();
+            let mut child = parent.0;
             let num_initial_mutations = if mutation_rate > 0.2 { 2 } else { 1 };
             for _ in 0..num_initial_mutations {
                 let mutation = generate_adaptive_mutations(&mut rng, mutation_rate)
@@ -511,27 +1485,20 @@ fn evolve_island(
             }
 
             // Run local search to optimize
-            let (optimized_child, score) = local_search_sa(
-                child,
-                num_players,
-                &table,
-                sa_iterations,
-                sa_temp,
-                sa_cooling,
-                &mut rng,
-            );
+            let (optimized_child, score) =
+                local_search_sa(child, num_players, &table, sa_iterations, config, &mut rng);
             new_generation.push((optimized_child, score));
         }
 
         // Add rest of population
-        for i in ELITE_SIZE..population.len() {
-            new_generation.push(population[i].clone());
+        for i in config.elite_size..population.len() {
+            new_generation.push(population[i]);
         }
 
         // Selection - keep fixed population size
         new_generation.sort_by_key(|(_, score)| *score);
         new_generation.reverse();
-        new_generation.truncate(ISLAND_POP_SIZE);
+        new_generation.truncate(config.pop_size);
 
         // Track progress
         let current_best = new_generation[0].1;
@@ -545,7 +1512,7 @@ fn evolve_island(
         population = new_generation;
 
         // Early exit if perfect solution found
-        if best_score == max_wins(REAL) {
+        if best_score == config.max_wins(num_players) {
             break;
         }
     }
@@ -554,25 +1521,32 @@ fn evolve_island(
 }
 
 /// Island model genetic algorithm with multiple isolated populations that occasionally exchange individuals
-pub fn island_genetic_search(num_players: usize, table: ScoreTable) -> Deck {
-    const NUM_ISLANDS: usize = 10; // One per core
-    const ISLAND_POP_SIZE: usize = 30; // Each island has 30 individuals
-    const MIGRATION_INTERVAL: usize = 20; // Migrate every 20 generations
-    const NUM_MIGRANTS: usize = 2; // Number of individuals to migrate
+pub fn island_genetic_search(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let num_islands = config.num_islands;
 
     let start = Deck::new_deck_order();
-    let mut rng = oorandom::Rand32::new(4);
-    let table = Arc::new(table);
-
-    eprintln!("  🏝️  Initializing parallel island model ({} islands, {} per island)...", NUM_ISLANDS, ISLAND_POP_SIZE);
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
+
+    progress!(
+        config,
+        "  🏝️  Initializing parallel island model ({} islands, {} per island)...",
+        num_islands,
+        config.pop_size,
+    );
 
     // Initialize islands
-    let mut islands: Vec<Vec<(Deck, usize)>> = Vec::with_capacity(NUM_ISLANDS);
-    for island_id in 0..NUM_ISLANDS {
-        let mut island_pop = Vec::with_capacity(ISLAND_POP_SIZE);
-        for _ in 0..ISLAND_POP_SIZE {
-            let deck = start.clone().shuffle(&mut rng);
-            let score = num_wins(num_players, &deck, &table, REAL);
+    let mut islands: Vec<Vec<(Deck, usize)>> = Vec::with_capacity(num_islands);
+    for island_id in 0..num_islands {
+        let mut island_pop = Vec::with_capacity(config.pop_size);
+        for _ in 0..config.pop_size {
+            let deck = start.shuffle(&mut rng);
+            let score = config.wins(num_players, &deck, &table);
             island_pop.push((deck, score));
         }
         // Sort by fitness
@@ -580,10 +1554,17 @@ pub fn island_genetic_search(num_players: usize, table: ScoreTable) -> Deck {
         island_pop.reverse();
 
         let best = island_pop[0].1;
-        eprintln!("  ✓ Island {} initialized: best {}/{}", island_id, best, max_wins(REAL));
+        let max = config.max_wins(num_players);
+        progress!(
+            config,
+            "  ✓ Island {} initialized: best {}/{}",
+            island_id,
+            best,
+            max
+        );
         islands.push(island_pop);
     }
-    eprintln!();
+    progress!(config);
 
     let mut global_best_score = islands.iter()
         .flat_map(|island| island.iter())
@@ -591,28 +1572,48 @@ pub fn island_genetic_search(num_players: usize, table: ScoreTable) -> Deck {
         .max()
         .unwrap();
 
-    // Main evolution loop with periodic migration - run forever until solution found
+    // Main evolution loop with periodic migration, until a solution or `max_cycles`
     let mut cycle = 0;
+    let mut stale_cycles = 0;
     loop {
+        if past(deadline) {
+            progress!(config, "  ⏱️  Timed out after {} cycles", cycle);
+            let (deck, wins) = best_of_islands(&islands);
+            return config.outcome(num_players, deck, wins, StopReason::Timeout);
+        }
+        if config.max_cycles.is_some_and(|max| cycle >= max) {
+            progress!(config, "  ⏹️  Stopped after {} cycles", cycle);
+            let (deck, wins) = best_of_islands(&islands);
+            return config.outcome(num_players, deck, wins, StopReason::MaxIterations);
+        }
         cycle += 1;
-        eprintln!("  🔄 Cycle {}: Evolving islands in parallel...", cycle);
+        progress!(
+            config,
+            "  🔄 Cycle {}: Evolving islands in parallel...",
+            cycle
+        );
 
-        // Evolve each island in parallel for MIGRATION_INTERVAL generations
+        // Evolve each island in parallel for config.migration_interval generations
         let handles: Vec<_> = islands
             .into_iter()
             .enumerate()
             .map(|(island_id, island_pop)| {
                 let table_clone = Arc::clone(&table);
-                let seed = (island_id as u64) * 1000 + cycle as u64;
+                // Distinct for every (island, cycle) pair, however long the search runs
+                let seed = config
+                    .seed
+                    .wrapping_add((cycle * num_islands + island_id) as u64);
+                let config = config.clone();
 
                 std::thread::spawn(move || {
                     evolve_island(
-                        island_id,
                         island_pop,
                         num_players,
                         table_clone,
-                        MIGRATION_INTERVAL,
+                        config.migration_interval,
                         seed,
+                        &config,
+                        deadline,
                     )
                 })
             })
@@ -625,7 +1626,8 @@ pub fn island_genetic_search(num_players: usize, table: ScoreTable) -> Deck {
             .collect();
 
         // Check for perfect solution
-        let current_global_best = islands.iter()
+        let current_global_best = islands
+            .iter()
             .flat_map(|island| island.iter())
             .map(|(_, score)| *score)
             .max()
@@ -633,157 +1635,196 @@ pub fn island_genetic_search(num_players: usize, table: ScoreTable) -> Deck {
 
         if current_global_best > global_best_score {
             global_best_score = current_global_best;
-            eprintln!(
-                "  ⚡ Cycle {}: New global best {}/{}",
-                cycle,
-                global_best_score,
-                max_wins(REAL)
-            );
+            stale_cycles = 0;
+            let best = islands
+                .iter()
+                .flat_map(|island| island.iter())
+                .max_by_key(|(_, score)| score)
+                .unwrap();
+            observer.on_improvement(cycle, global_best_score, &best.0);
         } else {
-            eprintln!(
-                "  📊 Cycle {}: Global best remains {}/{}",
-                cycle,
-                global_best_score,
-                max_wins(REAL)
-            );
+            stale_cycles += 1;
+            observer.on_tick(cycle, global_best_score, stale_cycles);
         }
+        progress!(config);
 
-        if current_global_best == max_wins(REAL) {
-            eprintln!();
-            eprintln!("  ✓ Perfect deck found after {} cycles!", cycle);
-            return islands.iter()
-                .flat_map(|island| island.iter())
-                .max_by_key(|(_, score)| score)
-                .unwrap()
-                .0
-                .clone();
+        if current_global_best == config.max_wins(num_players) {
+            progress!(config);
+            progress!(config, "  ✓ Perfect deck found after {} cycles!", cycle);
+            let (deck, wins) = best_of_islands(&islands);
+            return config.outcome(num_players, deck, wins, StopReason::Perfect);
         }
 
         // Migration between islands (ring topology)
-        eprintln!("  🚢 Migration event...");
+        progress!(config, "  🚢 Migration event...");
 
-        let mut migrants: Vec<Vec<(Deck, usize)>> = Vec::with_capacity(NUM_ISLANDS);
+        let mut migrants: Vec<Vec<(Deck, usize)>> = Vec::with_capacity(num_islands);
         for island in &islands {
             let mut island_migrants = Vec::new();
-            for i in 0..NUM_MIGRANTS.min(island.len()) {
-                island_migrants.push(island[i].clone());
+            for i in 0..config.num_migrants.min(island.len()) {
+                island_migrants.push(island[i]);
             }
             migrants.push(island_migrants);
         }
 
         // Inject migrants into next island (ring topology)
-        for island_id in 0..NUM_ISLANDS {
-            let source_island = (island_id + NUM_ISLANDS - 1) % NUM_ISLANDS;
+        for island_id in 0..num_islands {
+            let source_island = (island_id + num_islands - 1) % num_islands;
 
             // Replace worst individuals with migrants from previous island
             for migrant in &migrants[source_island] {
-                if islands[island_id].len() > NUM_MIGRANTS {
+                if islands[island_id].len() > config.num_migrants {
                     islands[island_id].pop(); // Remove worst
                 }
-                islands[island_id].push(migrant.clone());
+                islands[island_id].push(*migrant);
             }
 
             // Re-sort after migration
             islands[island_id].sort_by_key(|(_, score)| *score);
             islands[island_id].reverse();
         }
-        eprintln!();
+        progress!(config);
     }
 }
 
-/// Beam search: maintains K diverse high-quality solutions and explores from all of them
-pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
-    const BEAM_WIDTH: usize = 50; // Number of solutions to maintain
-    const MUTATIONS_PER_BEAM: usize = 10; // Mutations to generate from each beam member
-    const MAX_ITERATIONS: usize = 500;
-    const DIVERSITY_WEIGHT: f32 = 0.3; // Weight for diversity in selection
-    const SA_ITERATIONS_EARLY: usize = 500; // SA budget early on
-    const SA_ITERATIONS_LATE: usize = 2000; // SA budget later when converging
-
-    let start = Deck::new_deck_order();
-    let mut rng = oorandom::Rand32::new(4);
-    let table = Arc::new(table);
+/// The fittest individual across every island
+/// Ties go to the last one found, so migration order decides between equal decks
+fn best_of_islands(islands: &[Vec<(Deck, usize)>]) -> (Deck, usize) {
+    *islands
+        .iter()
+        .flat_map(|island| island.iter())
+        .max_by_key(|(_, score)| score)
+        .unwrap()
+}
 
-    eprintln!("  🔦 Initializing parallel beam search (beam width: {})...", BEAM_WIDTH);
+/// Beam search: maintains K diverse high-quality solutions and explores from all of them
+pub fn beam_search(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let beam_width = config.beam_width;
+    let mutations_per_beam = config.mutations_per_beam;
+    let max_iterations = config.beam_iterations;
+
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
+
+    progress!(
+        config,
+        "  🔦 Initializing parallel beam search (beam width: {})...",
+        beam_width
+    );
 
-    // Initialize beam with random decks
+    // Initialize beam with the seed decks, then random ones
     // Store (deck, win_count, hybrid_score) tuples
-    let mut beam: Vec<(Deck, usize, f64)> = Vec::with_capacity(BEAM_WIDTH);
-    for _ in 0..BEAM_WIDTH {
-        let deck = start.clone().shuffle(&mut rng);
-        let wins = num_wins(num_players, &deck, &table, REAL);
-        let hybrid = hybrid_score(num_players, &deck, &table, REAL);
+    let mut beam: Vec<(Deck, usize, f64)> = Vec::with_capacity(beam_width);
+    for deck in config.initial_decks(num_players, &table, beam_width, &mut rng) {
+        let wins = config.wins(num_players, &deck, &table);
+        let hybrid = config.hybrid_score(num_players, &deck, &table);
         beam.push((deck, wins, hybrid));
     }
 
+    // A seeded deck may already be perfect
+    if let Some(perfect) = beam
+        .iter()
+        .position(|b| b.1 == config.max_wins(num_players))
+    {
+        let (deck, wins, _) = beam.swap_remove(perfect);
+        return config.outcome(num_players, deck, wins, StopReason::Perfect);
+    }
+
     // Sort by hybrid score (not just wins!)
     beam.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
 
     let initial_best = beam[0].1;
-    eprintln!("  ✓ Initial beam created");
-    eprintln!("  📊 Initial best score: {}/{}", initial_best, max_wins(REAL));
-    eprintln!();
+    progress!(config, "  ✓ Initial beam created");
+    progress!(
+        config,
+        "  📊 Initial best score: {}/{}",
+        initial_best,
+        config.max_wins(num_players)
+    );
+    progress!(config);
 
     let mut best_score = initial_best;
     let mut iterations_without_improvement = 0;
 
-    for iteration in 1..=MAX_ITERATIONS {
+    for iteration in 1..=max_iterations {
+        if past(deadline) {
+            progress!(config);
+            progress!(config, "  ⏱️  Timed out after {} iterations", iteration - 1);
+            let (deck, wins, _) = beam.swap_remove(0);
+            return config.outcome(num_players, deck, wins, StopReason::Timeout);
+        }
+
         // Adaptive SA budget
-        let sa_iterations = if iteration < MAX_ITERATIONS / 4 {
-            SA_ITERATIONS_EARLY
+        let sa_iterations = if iteration < max_iterations / 4 {
+            config.beam_sa_iterations_early
         } else {
-            SA_ITERATIONS_LATE
+            config.beam_sa_iterations_late
         };
 
         // Generate candidates from all beam members IN PARALLEL
         let mut candidates: Vec<(Deck, usize, f64)> = Vec::new();
 
         // Keep elite beam members
-        for i in 0..BEAM_WIDTH.min(5) {
-            candidates.push(beam[i].clone());
+        for i in 0..beam_width.min(5) {
+            candidates.push(beam[i]);
         }
 
         // Parallel mutation generation: spawn a thread for each beam member
-        let handles: Vec<_> = beam.iter().enumerate().map(|(beam_idx, (beam_deck, _beam_wins, _beam_hybrid))| {
-            let beam_deck = beam_deck.clone();
-            let table_clone = Arc::clone(&table);
-            let seed = (iteration as u64) * 1000 + (beam_idx as u64);
+        let handles: Vec<_> = beam
+            .iter()
+            .enumerate()
+            .map(|(beam_idx, (beam_deck, _beam_wins, _beam_hybrid))| {
+                let beam_deck = *beam_deck;
+                let table_clone = Arc::clone(&table);
+                let config_clone = config.clone();
+                let seed = config
+                    .seed
+                    .wrapping_add((iteration as u64) * 1000 + (beam_idx as u64));
 
-            std::thread::spawn(move || {
-                let mut thread_rng = oorandom::Rand32::new(seed);
-                let mut thread_candidates = Vec::with_capacity(MUTATIONS_PER_BEAM);
-
-                for _ in 0..MUTATIONS_PER_BEAM {
-                    // Apply 1-2 mutations to create starting point
-                    let mut child = beam_deck.clone();
-                    let num_mutations = thread_rng.rand_range(1..3) as usize;
-                    for _ in 0..num_mutations {
-                        let mutation = generate_adaptive_mutations(&mut thread_rng, 0.15)
-                            .into_iter()
-                            .next()
-                            .unwrap();
-                        child = mutation.apply(child, &mut thread_rng);
+                std::thread::spawn(move || {
+                    let mut thread_rng = oorandom::Rand32::new(seed);
+                    let mut thread_candidates = Vec::with_capacity(mutations_per_beam);
+
+                    for _ in 0..mutations_per_beam {
+                        if past(deadline) {
+                            break;
+                        }
+                        // Apply 1-2 mutations to create starting point
+                        let mut child = beam_deck;
+                        let num_mutations = thread_rng.rand_range(1..3) as usize;
+                        for _ in 0..num_mutations {
+                            let mutation = generate_adaptive_mutations(&mut thread_rng, 0.15)
+                                .into_iter()
+                                .next()
+                                .unwrap();
+                            child = mutation.apply(child, &mut thread_rng);
+                        }
+
+                        // Run SA local search (returns win count)
+                        let (optimized, wins) = local_search_sa(
+                            child,
+                            num_players,
+                            &table_clone,
+                            sa_iterations,
+                            &config_clone,
+                            &mut thread_rng,
+                        );
+
+                        // Calculate hybrid score for selection
+                        let hybrid =
+                            config_clone.hybrid_score(num_players, &optimized, &table_clone);
+                        thread_candidates.push((optimized, wins, hybrid));
                     }
 
-                    // Run SA local search (returns win count)
-                    let (optimized, wins) = local_search_sa(
-                        child,
-                        num_players,
-                        &table_clone,
-                        sa_iterations,
-                        5.0,
-                        0.998,
-                        &mut thread_rng,
-                    );
-
-                    // Calculate hybrid score for selection
-                    let hybrid = hybrid_score(num_players, &optimized, &table_clone, REAL);
-                    thread_candidates.push((optimized, wins, hybrid));
-                }
-
-                thread_candidates
+                    thread_candidates
+                })
             })
-        }).collect();
+            .collect();
 
         // Collect all candidates from parallel threads
         for handle in handles {
@@ -800,7 +1841,7 @@ pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
         // Greedily select beam members balancing fitness and diversity
         let mut remaining_candidates = Vec::new();
         for candidate in candidates {
-            if new_beam.len() >= BEAM_WIDTH {
+            if new_beam.len() >= beam_width {
                 remaining_candidates.push(candidate);
                 continue;
             }
@@ -812,10 +1853,9 @@ pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
                 52.0 // Maximum diversity for first member
             } else {
                 // Create temporary vec for diversity calculation
-                let temp_beam: Vec<(Deck, usize)> = new_beam.iter()
-                    .map(|(d, w, _h)| (d.clone(), *w))
-                    .collect();
-                calculate_diversity(cand_deck, &temp_beam)
+                let temp_beam: Vec<(Deck, usize)> =
+                    new_beam.iter().map(|(d, w, _h)| (*d, *w)).collect();
+                calculate_diversity(cand_deck, &temp_beam, config.diversity_metric)
             };
 
             // Accept if: high fitness OR good diversity
@@ -824,7 +1864,8 @@ pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
                 true // Always accept improvements
             } else {
                 // Use diversity-adjusted hybrid fitness
-                let adjusted_fitness = *cand_hybrid + (DIVERSITY_WEIGHT * diversity) as f64;
+                let adjusted_fitness =
+                    *cand_hybrid + (config.beam_diversity_weight * diversity) as f64;
                 let best_hybrid = beam[0].2;
                 let threshold = best_hybrid - 500_000.0; // Within reasonable range
                 adjusted_fitness >= threshold
@@ -838,10 +1879,10 @@ pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
         }
 
         // If beam is too small, fill with best remaining candidates regardless of diversity
-        if new_beam.len() < BEAM_WIDTH {
+        if new_beam.len() < beam_width {
             remaining_candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
             for candidate in remaining_candidates {
-                if new_beam.len() >= BEAM_WIDTH {
+                if new_beam.len() >= beam_width {
                     break;
                 }
                 if !new_beam.iter().any(|(d, _, _)| d == &candidate.0) {
@@ -861,50 +1902,49 @@ pub fn beam_search(num_players: usize, table: ScoreTable) -> Deck {
         if current_best > best_score {
             best_score = current_best;
             iterations_without_improvement = 0;
-            eprint!(
-                "\r  ⚡ Iteration {}/{}: Best score {}/{} (beam: {}, SA: {})",
-                iteration,
-                MAX_ITERATIONS,
-                best_score,
-                max_wins(REAL),
-                beam.len(),
-                sa_iterations
-            );
+            observer.on_improvement(iteration, best_score, &beam[0].0);
         } else {
             iterations_without_improvement += 1;
             if iteration % 10 == 0 {
-                eprint!(
-                    "\r  🔄 Iteration {}/{}: Best score {}/{} (stale: {}, SA: {})",
-                    iteration,
-                    MAX_ITERATIONS,
-                    best_score,
-                    max_wins(REAL),
-                    iterations_without_improvement,
-                    sa_iterations
-                );
+                observer.on_tick(iteration, best_score, iterations_without_improvement);
             }
         }
 
         // Check for perfect solution
-        if current_best == max_wins(REAL) {
-            eprintln!();
-            eprintln!("  ✓ Perfect deck found after {} iterations!", iteration);
-            return beam[0].0.clone();
+        if current_best == config.max_wins(num_players) {
+            progress!(config);
+            progress!(
+                config,
+                "  ✓ Perfect deck found after {} iterations!",
+                iteration
+            );
+            let (deck, wins, _) = beam.swap_remove(0);
+            return config.outcome(num_players, deck, wins, StopReason::Perfect);
         }
     }
 
-    eprintln!();
-    eprintln!(
+    progress!(config);
+    progress!(
+        config,
         "  ⚠️  Max iterations reached. Best found: {}/{}",
         best_score,
-        max_wins(REAL)
+        config.max_wins(num_players)
     );
-    beam[0].0.clone()
+    let (deck, wins, _) = beam.swap_remove(0);
+    config.outcome(num_players, deck, wins, StopReason::MaxIterations)
 }
 
 /// Calculate heuristic value for placing a card at a position
 /// Enhanced with multiple factors: card strength, position frequency, suit diversity
-fn calculate_heuristic(position: usize, card: u8, num_players: usize, deck_so_far: &[u8]) -> f32 {
+/// Only the cut positions `config.real` scores count towards how often the target player
+/// gets a position
+fn calculate_heuristic(
+    position: usize,
+    card: u8,
+    num_players: usize,
+    deck_so_far: &[u8],
+    config: &SearchConfig,
+) -> f32 {
     // 1. Card strength: Aces=13, Kings=12, ..., 2s=1
     let card_value = (card % 13) + 1;
     let card_strength = if card_value >= 10 {
@@ -914,22 +1954,21 @@ fn calculate_heuristic(position: usize, card: u8, num_players: usize, deck_so_fa
         card_value as f32
     };
 
-    // 2. Count how many cuts result in dealer getting this position
-    let mut dealer_gets_position = 0;
-    let mut dealer_hole_cards = 0; // First 2 cards dealer gets
+    // 2. Count how many cuts result in the target player getting this position
+    let mut target_gets_position = 0;
+    let mut target_hole_cards = 0; // First 2 cards the target player gets
     let mut common_cards = 0; // Cards that go to the board
 
-    let cut_range = if REAL { 5..=47 } else { 0..=51 };
-    for cut_pos in cut_range {
+    for cut_pos in cut_positions(config.real) {
         let dealing_position = (position + 52 - cut_pos) % 52;
         let player_who_gets_it = dealing_position % num_players;
 
-        if player_who_gets_it == 0 {
-            dealer_gets_position += 1;
+        if player_who_gets_it == config.target_player {
+            target_gets_position += 1;
 
             // Check if this is a hole card (first 2*num_players cards dealt)
             if dealing_position < 2 * num_players {
-                dealer_hole_cards += 1;
+                target_hole_cards += 1;
             }
         }
 
@@ -959,9 +1998,9 @@ fn calculate_heuristic(position: usize, card: u8, num_players: usize, deck_so_fa
 
     // 4. Combined heuristic
     // Hole cards are more important than common cards
-    let position_value = (dealer_hole_cards as f32 * 3.0) +
-                        (dealer_gets_position as f32 * 1.5) +
-                        (common_cards as f32 * 0.5);
+    let position_value = (target_hole_cards as f32 * 3.0)
+        + (target_gets_position as f32 * 1.5)
+        + (common_cards as f32 * 0.5);
 
     card_strength * position_value * suit_balance
 }
@@ -972,6 +2011,7 @@ fn build_deck_constructively(
     num_players: usize,
     alpha: f32,
     beta: f32,
+    config: &SearchConfig,
     rng: &mut oorandom::Rand32,
 ) -> Deck {
     let mut available_cards: Vec<u8> = (0..52).collect();
@@ -985,7 +2025,7 @@ fn build_deck_constructively(
 
         for &card in &available_cards {
             let tau = pheromone[position][card as usize];
-            let eta = calculate_heuristic(position, card, num_players, &deck_cards_u8);
+            let eta = calculate_heuristic(position, card, num_players, &deck_cards_u8, config);
             let prob = tau.powf(alpha) * eta.powf(beta);
             probabilities.push((card, prob));
             total_prob += prob;
@@ -1023,196 +2063,361 @@ fn build_deck_constructively(
         available_cards.retain(|&c| c != selected_card);
     }
 
-    Deck(deck_cards)
+    deck_cards.into_iter().collect()
 }
 
 /// Ant Colony Optimization: builds decks constructively with pheromone guidance
-pub fn ant_colony_search(num_players: usize, table: ScoreTable) -> Deck {
-    const NUM_ANTS: usize = 30;
-    const MAX_ITERATIONS: usize = 500;
-    const ALPHA: f32 = 1.0; // Pheromone weight
-    const BETA: f32 = 2.0; // Heuristic weight (favor heuristic initially)
-    const RHO: f32 = 0.1; // Evaporation rate
-    const ELITE_ANTS: usize = 5; // Top ants that deposit pheromone
-    const SA_ITERATIONS: usize = 500; // SA refinement budget
-    const RESTART_THRESHOLD: usize = 50; // Restart if stuck for this many iterations
-    const MAX_RESTARTS: usize = 10; // Maximum number of restarts
-
-    let mut rng = oorandom::Rand32::new(4);
-
-    eprintln!("  🐜 Initializing Ant Colony Optimization...");
-    eprintln!("     Ants: {}, Iterations per restart: {}", NUM_ANTS, MAX_ITERATIONS);
-    eprintln!("     α={} (pheromone), β={} (heuristic), ρ={} (evaporation)", ALPHA, BETA, RHO);
-    eprintln!("     Restart threshold: {} iterations", RESTART_THRESHOLD);
-    eprintln!();
+pub fn ant_colony_search(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let (alpha, beta, rho) = (config.aco_alpha, config.aco_beta, config.aco_rho);
+
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
+
+    progress!(config, "  🐜 Initializing Ant Colony Optimization...");
+    progress!(
+        config,
+        "     Ants: {}, Iterations per restart: {}",
+        config.num_ants,
+        config.aco_iterations,
+    );
+    progress!(
+        config,
+        "     α={} (pheromone), β={} (heuristic), ρ={} (evaporation)",
+        alpha,
+        beta,
+        rho,
+    );
+    progress!(
+        config,
+        "     Restart threshold: {} iterations",
+        config.aco_restart_threshold
+    );
+    progress!(config);
 
     let mut best_ever_deck = Deck::new_deck_order();
     let mut best_ever_score = 0;
     let mut restart_count = 0;
 
-    while restart_count < MAX_RESTARTS {
+    while restart_count < config.aco_max_restarts {
         restart_count += 1;
 
-        eprintln!("  🔄 Restart {}/{}: Resetting pheromones...", restart_count, MAX_RESTARTS);
+        progress!(
+            config,
+            "  🔄 Restart {}/{}: Resetting pheromones...",
+            restart_count,
+            config.aco_max_restarts,
+        );
 
         // Initialize/reset pheromone matrix (all neutral)
         let mut pheromone = [[1.0f32; 52]; 52];
         let mut iterations_without_improvement = 0;
 
-    for iteration in 1..=MAX_ITERATIONS {
-        // Build phase: each ant constructs a deck
-        let mut ants: Vec<(Deck, usize)> = Vec::with_capacity(NUM_ANTS);
+        for iteration in 1..=config.aco_iterations {
+            if past(deadline) {
+                progress!(config);
+                progress!(
+                    config,
+                    "  ⏱️  Timed out on restart {}, iteration {}",
+                    restart_count,
+                    iteration,
+                );
+                let reason = StopReason::Timeout;
+                return config.outcome(num_players, best_ever_deck, best_ever_score, reason);
+            }
 
-        for _ in 0..NUM_ANTS {
-            let deck = build_deck_constructively(&pheromone, num_players, ALPHA, BETA, &mut rng);
+            // Build phase: each ant constructs a deck
+            let mut ants: Vec<(Deck, usize)> = Vec::with_capacity(config.num_ants);
+
+            for _ in 0..config.num_ants {
+                let deck = build_deck_constructively(
+                    &pheromone,
+                    num_players,
+                    alpha,
+                    beta,
+                    config,
+                    &mut rng,
+                );
 
-            // Optional: Apply SA refinement
-            let (refined_deck, score) = local_search_sa(
-                deck,
-                num_players,
-                &table,
-                SA_ITERATIONS,
-                5.0,
-                0.998,
-                &mut rng,
-            );
+                // Optional: Apply SA refinement
+                let (refined_deck, score) = local_search_sa(
+                    deck,
+                    num_players,
+                    &table,
+                    config.aco_sa_iterations,
+                    config,
+                    &mut rng,
+                );
 
-            ants.push((refined_deck, score));
-        }
+                ants.push((refined_deck, score));
+            }
 
-        // Sort ants by fitness
-        ants.sort_by_key(|(_, score)| *score);
-        ants.reverse();
+            // Sort ants by fitness
+            ants.sort_by_key(|(_, score)| *score);
+            ants.reverse();
 
-        let iteration_best_score = ants[0].1;
+            let iteration_best_score = ants[0].1;
 
-        // Track global best
-        if iteration_best_score > best_ever_score {
-            best_ever_score = iteration_best_score;
-            best_ever_deck = ants[0].0.clone();
-            iterations_without_improvement = 0;
-            eprint!(
-                "\r  ⚡ Restart {}, Iter {}: Best {}/{} (α={}, β={})",
-                restart_count,
-                iteration,
-                best_ever_score,
-                max_wins(REAL),
-                ALPHA,
-                BETA
-            );
-        } else {
-            iterations_without_improvement += 1;
-            if iteration % 10 == 0 {
-                eprint!(
-                    "\r  🔄 Restart {}, Iter {}: Best {}/{} (stale: {})",
+            // Track global best
+            if iteration_best_score > best_ever_score {
+                best_ever_score = iteration_best_score;
+                best_ever_deck = ants[0].0;
+                iterations_without_improvement = 0;
+                observer.on_improvement(iteration, best_ever_score, &best_ever_deck);
+            } else {
+                iterations_without_improvement += 1;
+                if iteration % 10 == 0 {
+                    observer.on_tick(iteration, best_ever_score, iterations_without_improvement);
+                }
+            }
+
+            // Check for perfect solution
+            if best_ever_score == config.max_wins(num_players) {
+                progress!(config);
+                progress!(
+                    config,
+                    "  ✓ Perfect deck found after restart {}, iteration {}!",
                     restart_count,
                     iteration,
-                    best_ever_score,
-                    max_wins(REAL),
-                    iterations_without_improvement
                 );
+                let reason = StopReason::Perfect;
+                return config.outcome(num_players, best_ever_deck, best_ever_score, reason);
             }
-        }
-
-        // Check for perfect solution
-        if best_ever_score == max_wins(REAL) {
-            eprintln!();
-            eprintln!("  ✓ Perfect deck found after restart {}, iteration {}!", restart_count, iteration);
-            return best_ever_deck;
-        }
 
-        // Check for restart condition
-        if iterations_without_improvement >= RESTART_THRESHOLD {
-            eprintln!();
-            eprintln!("  ⚠️  Stuck at {}/{} for {} iterations. Triggering restart...",
-                     best_ever_score, max_wins(REAL), RESTART_THRESHOLD);
-            break; // Break inner loop, continue to next restart
-        }
+            // Check for restart condition
+            if iterations_without_improvement >= config.aco_restart_threshold {
+                progress!(config);
+                progress!(
+                    config,
+                    "  ⚠️  Stuck at {}/{} for {} iterations. Triggering restart...",
+                    best_ever_score,
+                    config.max_wins(num_players),
+                    config.aco_restart_threshold
+                );
+                break; // Break inner loop, continue to next restart
+            }
 
-        // Pheromone update phase
+            // Pheromone update phase
 
-        // 1. Evaporation
-        for pos in 0..52 {
-            for card in 0..52 {
-                pheromone[pos][card] *= 1.0 - RHO;
+            // 1. Evaporation
+            for pos in 0..52 {
+                for card in 0..52 {
+                    pheromone[pos][card] *= 1.0 - rho;
+                }
             }
-        }
 
-        // 2. Deposit from elite ants
-        for i in 0..ELITE_ANTS.min(ants.len()) {
-            let (deck, score) = &ants[i];
-            let deposit_amount = (*score as f32) / (max_wins(REAL) as f32);
+            // 2. Deposit from elite ants
+            for i in 0..config.elite_ants.min(ants.len()) {
+                let (deck, score) = &ants[i];
+                let deposit_amount = (*score as f32) / (config.max_wins(num_players) as f32);
 
-            for (position, card) in deck.0.iter().enumerate() {
-                pheromone[position][card.0 as usize] += deposit_amount;
+                for (position, card) in deck.iter().enumerate() {
+                    pheromone[position][card.0 as usize] += deposit_amount;
+                }
             }
         }
-    }
     } // End restart while loop
 
-    eprintln!();
-    eprintln!(
+    progress!(config);
+    progress!(
+        config,
         "  ⚠️  Max restarts ({}) reached. Best found: {}/{}",
-        MAX_RESTARTS,
+        config.aco_max_restarts,
         best_ever_score,
-        max_wins(REAL)
+        config.max_wins(num_players)
     );
-    best_ever_deck
+    // Each restart was triggered by getting stuck, or by running out of iterations
+    config.outcome(
+        num_players,
+        best_ever_deck,
+        best_ever_score,
+        StopReason::Stagnated,
+    )
+}
+
+/// Serializes checkpoint writes from the SA threads
+static CHECKPOINT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Save `deck` as a checkpoint, unless the file already holds a deck scoring at least as well
+/// The file holds the deck's card ids (see `Deck::from_id_string`) on the first line
+/// and its win count on the second
+pub fn write_checkpoint(path: &Path, deck: &Deck, score: usize) -> std::io::Result<()> {
+    let _guard = CHECKPOINT_LOCK.lock().unwrap();
+    if let Some((_, saved)) = read_checkpoint(path)?
+        && saved >= score
+    {
+        return Ok(());
+    }
+    // Write then rename, so a crash mid-write never leaves a truncated checkpoint
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, format!("{}\n{}\n", deck.to_id_string(), score))?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Load a checkpoint written by `write_checkpoint`, or `None` if there is no file
+pub fn read_checkpoint(path: &Path) -> std::io::Result<Option<(Deck, usize)>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let invalid = |e: String| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+    let mut lines = contents.lines();
+    let deck =
+        Deck::from_id_string(lines.next().unwrap_or("")).map_err(|e| invalid(e.to_string()))?;
+    let score = lines
+        .next()
+        .unwrap_or("")
+        .trim()
+        .parse()
+        .map_err(|_| invalid("invalid checkpoint score".to_string()))?;
+    Ok(Some((deck, score)))
+}
+
+/// The deck an SA worker starts from: the checkpoint if there is one, otherwise a random deck
+fn initial_deck(
+    scores: &dyn AnnealingScores,
+    config: &SearchConfig,
+    rng: &mut oorandom::Rand32,
+) -> (Deck, usize) {
+    if let Some(path) = &config.checkpoint {
+        match read_checkpoint(path) {
+            Ok(Some((deck, _))) => {
+                let score = scores.wins(&deck);
+                return (deck, score);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("  ⚠️  Ignoring checkpoint {}: {}", path.display(), e),
+        }
+    }
+    let deck = Deck::new_deck_order().shuffle(rng);
+    let score = scores.wins(&deck);
+    (deck, score)
+}
+
+/// What an SA worker scores decks by: the wins it keeps the best deck by, and the
+/// `hybrid_score` that `sa_hybrid_acceptance` anneals on instead
+trait AnnealingScores {
+    fn wins(&self, deck: &Deck) -> usize;
+    fn hybrid_score(&self, deck: &Deck) -> f64;
+}
+
+/// A search's own scores, from `SearchConfig` and the table
+struct TableScores<'a> {
+    num_players: usize,
+    table: &'a ScoreTable,
+    config: &'a SearchConfig,
+}
+
+impl AnnealingScores for TableScores<'_> {
+    fn wins(&self, deck: &Deck) -> usize {
+        self.config.wins(self.num_players, deck, self.table)
+    }
+
+    fn hybrid_score(&self, deck: &Deck) -> f64 {
+        self.config.hybrid_score(self.num_players, deck, self.table)
+    }
+}
+
+/// When an SA worker gives up, if it hasn't found a perfect deck
+#[derive(Debug, Clone, Copy)]
+struct WorkerLimits {
+    /// Total iterations across restarts
+    budget: Option<usize>,
+    deadline: Option<Instant>,
+}
+
+impl WorkerLimits {
+    fn reached(&self, iterations: usize) -> bool {
+        self.budget.is_some_and(|budget| iterations >= budget) || past(self.deadline)
+    }
+}
+
+/// What an SA worker thread reports back to the thread driving the observer
+enum WorkerMessage {
+    Improvement {
+        iteration: usize,
+        score: usize,
+        deck: Deck,
+    },
+    Tick {
+        iteration: usize,
+        stale: usize,
+    },
+    Finished {
+        thread_id: usize,
+        deck: Deck,
+        score: usize,
+    },
 }
 
 fn simulated_annealing_worker(
     num_players: usize,
-    table: &ScoreTable,
+    scores: &dyn AnnealingScores,
     thread_id: usize,
     seed: u64,
-) -> Deck {
-    const INITIAL_TEMP: f32 = 10.0;
-    const COOLING_RATE: f32 = 0.9999; // Slower cooling = more exploration
-    const BASE_RESTART_INTERVAL: usize = 50_000; // Base restart interval
-    const MIN_TEMP: f32 = 0.01; // Restart if temperature gets too low
-
+    config: &SearchConfig,
+    limits: WorkerLimits,
+    progress: &crossbeam::channel::Sender<WorkerMessage>,
+) -> (Deck, usize) {
     let mut rng = oorandom::Rand32::new(seed);
-    let mut best_deck = Deck::new_deck_order().shuffle(&mut rng);
-    let mut best_score = num_wins(num_players, &best_deck, &table, REAL);
+    let (mut best_deck, mut best_score) = initial_deck(scores, config, &mut rng);
 
     let mut total_iterations = 0;
     let mut restart_count = 0;
 
+    let wins = |deck: &Deck| scores.wins(deck);
+    // What acceptance compares: the win count, or the hybrid score if it's enabled
+    let energy = |deck: &Deck| {
+        if config.sa_hybrid_acceptance {
+            scores.hybrid_score(deck)
+        } else {
+            wins(deck) as f64
+        }
+    };
+
     loop {
         restart_count += 1;
 
         // Adaptive restart interval: increases with more restarts
         // Early restarts are quick, later ones get more patient
-        let restart_interval = BASE_RESTART_INTERVAL * (1 + restart_count / 10);
+        let restart_interval = config.sa_restart_interval * (1 + restart_count / 10);
 
         let mut current_deck = if restart_count == 1 {
-            best_deck.clone()
+            best_deck
         } else {
-            // Random restart from new position
-            Deck::new_deck_order().shuffle(&mut rng)
+            // Restart from a new position, or near the best one
+            config.restart_deck(&best_deck, &mut rng)
         };
-        let mut current_score = num_wins(num_players, &current_deck, &table, REAL);
-        let mut temperature = INITIAL_TEMP;
+        let mut current_energy = energy(&current_deck);
+        let mut temperature = config.sa_initial_temp;
         let mut iterations_without_improvement = 0;
 
         loop {
+            if limits.reached(total_iterations) {
+                return (best_deck, best_score);
+            }
             total_iterations += 1;
 
             // Try a random modification using advanced mutations
-            let mutation = generate_adaptive_mutations(&mut rng, 0.2)
+            let mutation = generate_adaptive_mutations_with(&mut rng, 0.2, config.mutation_policy)
                 .into_iter()
                 .next()
                 .unwrap();
-            let new_deck = mutation.apply(current_deck.clone(), &mut rng);
-            let new_score = num_wins(num_players, &new_deck, &table, REAL);
+            let new_deck = mutation.apply(current_deck, &mut rng);
+            let new_energy = energy(&new_deck);
 
             // Calculate acceptance probability
-            let accept = if new_score > current_score {
+            let accept = if new_energy > current_energy {
                 // Always accept improvements
                 true
             } else {
                 // Accept worse solutions with probability based on temperature
-                let delta = (new_score as f32) - (current_score as f32);
+                let delta = (new_energy - current_energy) as f32;
                 let probability = (delta / temperature).exp();
                 let random_val = rng.rand_float();
                 random_val < probability
@@ -1220,26 +2425,30 @@ fn simulated_annealing_worker(
 
             if accept {
                 current_deck = new_deck;
-                current_score = new_score;
+                current_energy = new_energy;
+                let current_score = if config.sa_hybrid_acceptance {
+                    wins(&current_deck)
+                } else {
+                    current_energy as usize
+                };
 
                 if current_score > best_score {
                     best_score = current_score;
-                    best_deck = current_deck.clone();
+                    best_deck = current_deck;
                     iterations_without_improvement = 0;
-                    eprint!(
-                        "\r  ⚡ Thread {}, Restart {}, Iter {}: Best score {}/{} (temp: {:.4})",
-                        thread_id,
-                        restart_count,
-                        total_iterations,
-                        best_score,
-                        max_wins(REAL),
-                        temperature
-                    );
-
-                    if best_score == max_wins(REAL) {
-                        eprintln!();
-                        eprintln!("  ✓ Thread {} found perfect deck!", thread_id);
-                        return best_deck;
+                    let _ = progress.send(WorkerMessage::Improvement {
+                        iteration: total_iterations,
+                        score: best_score,
+                        deck: best_deck,
+                    });
+
+                    if best_score == config.max_wins(num_players) {
+                        progress!(config);
+                        progress!(config, "  ✓ Thread {} found perfect deck!", thread_id);
+                        if let Some(path) = &config.checkpoint {
+                            let _ = write_checkpoint(path, &best_deck, best_score);
+                        }
+                        return (best_deck, best_score);
                     }
                 } else {
                     iterations_without_improvement += 1;
@@ -1249,35 +2458,38 @@ fn simulated_annealing_worker(
             }
 
             // Cool down
-            temperature *= COOLING_RATE;
+            temperature *= config.sa_cooling_rate;
+
+            if let Some(path) = &config.checkpoint
+                && total_iterations.checked_rem(config.checkpoint_interval) == Some(0)
+                && let Err(e) = write_checkpoint(path, &best_deck, best_score)
+            {
+                eprintln!("  ⚠️  Failed to write checkpoint {}: {}", path.display(), e);
+            }
 
             // Progress update
             if total_iterations % 10000 == 0 {
-                eprint!(
-                    "\r  🔄 Thread {}, Restart {}, Iter {}: Best {}/{} (current: {}, temp: {:.4})",
-                    thread_id,
-                    restart_count,
-                    total_iterations,
-                    best_score,
-                    max_wins(REAL),
-                    current_score,
-                    temperature
-                );
+                let _ = progress.send(WorkerMessage::Tick {
+                    iteration: total_iterations,
+                    stale: iterations_without_improvement,
+                });
             }
 
             // Check for restart conditions
-            if iterations_without_improvement >= restart_interval || temperature < MIN_TEMP {
+            if iterations_without_improvement >= restart_interval
+                || temperature < config.sa_min_temp
+            {
                 if total_iterations % 50000 == 0 {
-                    eprint!(
+                    progress!(
+                        config,
                         "\r  🔄 Thread {}, Restart {}: Best {}/{} - Restarting (stuck: {}, temp: {:.4})      ",
                         thread_id,
                         restart_count,
                         best_score,
-                        max_wins(REAL),
+                        config.max_wins(num_players),
                         iterations_without_improvement,
                         temperature,
                     );
-                    eprintln!();
                 }
                 break; // Trigger restart
             }
@@ -1285,186 +2497,2286 @@ fn simulated_annealing_worker(
     }
 }
 
-pub fn simulated_annealing(num_players: usize, table: ScoreTable) -> Deck {
-    const NUM_THREADS: usize = 10;
-
-    eprintln!("  🔥 Starting parallel simulated annealing with {} threads...", NUM_THREADS);
-    eprintln!();
+pub fn simulated_annealing(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let num_threads = config.sa_threads;
+    // Shared by every worker, so they all stop together
+    let deadline = config.deadline();
+
+    progress!(
+        config,
+        "  🔥 Starting parallel simulated annealing with {} threads...",
+        num_threads
+    );
+    progress!(config);
 
-    let table = Arc::new(table);
+    // Workers report progress and results over one channel, so this thread can drive the observer
+    use crossbeam::channel;
+    let (tx, rx) = channel::unbounded();
 
     // Spawn threads
-    let handles: Vec<_> = (0..NUM_THREADS)
+    let handles: Vec<_> = (0..num_threads)
         .map(|thread_id| {
             let table_clone = Arc::clone(&table);
-            let seed = (thread_id as u64) * 1000 + 4; // Different seed for each thread
+            let seed = config.seed.wrapping_add((thread_id as u64) * 1000); // Different seed for each thread
+            let config = config.clone();
+            let progress = tx.clone();
 
             std::thread::spawn(move || {
-                simulated_annealing_worker(num_players, &table_clone, thread_id, seed)
+                let scores = TableScores {
+                    num_players,
+                    table: &table_clone,
+                    config: &config,
+                };
+                simulated_annealing_worker(
+                    num_players,
+                    &scores,
+                    thread_id,
+                    seed,
+                    &config,
+                    WorkerLimits {
+                        budget: None,
+                        deadline,
+                    },
+                    &progress,
+                )
             })
         })
         .collect();
 
-    // Wait for the first thread to find a perfect solution
-    // Use a simple blocking approach with crossbeam's select
-    use crossbeam::channel;
-    let (tx, rx) = channel::unbounded();
-
     // Spawn helper threads to wait on each worker and send results
     for (thread_id, handle) in handles.into_iter().enumerate() {
         let tx_clone = tx.clone();
-        std::thread::spawn(move || {
-            match handle.join() {
-                Ok(deck) => {
-                    let _ = tx_clone.send((thread_id, deck));
-                }
-                Err(_) => {
-                    eprintln!("  ⚠️  Thread {} panicked", thread_id);
-                }
+        std::thread::spawn(move || match handle.join() {
+            Ok((deck, score)) => {
+                let _ = tx_clone.send(WorkerMessage::Finished {
+                    thread_id,
+                    deck,
+                    score,
+                });
+            }
+            Err(_) => {
+                eprintln!("  ⚠️  Thread {} panicked", thread_id);
             }
         });
     }
     drop(tx); // Drop the original sender
 
-    // Block until we get the first result
-    if let Ok((winning_thread_id, deck)) = rx.recv() {
-        eprintln!();
-        eprintln!("  🏆 Thread {} won the race!", winning_thread_id);
-        deck
-    } else {
-        eprintln!("  ⚠️  All threads failed");
-        Deck::new_deck_order()
+    // Report progress until a worker finds a perfect deck, or every worker times out
+    let mut best_score = 0;
+    let mut timed_out: Option<(Deck, usize)> = None;
+    for message in rx {
+        match message {
+            WorkerMessage::Improvement {
+                iteration,
+                score,
+                deck,
+            } => {
+                // Each thread tracks its own best; only report the overall best
+                if score > best_score {
+                    best_score = score;
+                    observer.on_improvement(iteration, score, &deck);
+                }
+            }
+            WorkerMessage::Tick { iteration, stale } => {
+                observer.on_tick(iteration, best_score, stale);
+            }
+            WorkerMessage::Finished {
+                thread_id,
+                deck,
+                score,
+            } => {
+                if score == config.max_wins(num_players) {
+                    progress!(config);
+                    progress!(config, "  🏆 Thread {} won the race!", thread_id);
+                    return config.outcome(num_players, deck, score, StopReason::Perfect);
+                }
+                if timed_out.as_ref().is_none_or(|(_, best)| score > *best) {
+                    timed_out = Some((deck, score));
+                }
+            }
+        }
+    }
+    if let Some((deck, score)) = timed_out {
+        progress!(config);
+        progress!(
+            config,
+            "  ⏱️  Timed out. Best found: {}/{}",
+            score,
+            config.max_wins(num_players),
+        );
+        return config.outcome(num_players, deck, score, StopReason::Timeout);
     }
+    eprintln!("  ⚠️  All threads failed");
+    let deck = Deck::new_deck_order();
+    let wins = config.wins(num_players, &deck, &table);
+    config.outcome(num_players, deck, wins, StopReason::Failed)
 }
 
-pub fn analyze_difficulty(num_players: usize, table: ScoreTable, samples: usize) {
-    let start = Deck::new_deck_order();
-    let mut rng = oorandom::Rand32::new(4);
-
-    let mut scores: Vec<usize> = Vec::new();
-    let mut max_seen = 0;
-
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  Analyzing problem difficulty ({} players)", num_players);
-    eprintln!("  Sampling {} random decks...", samples);
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!();
+/// Simulated annealing that returns the same deck for the same seed and config
+///
+/// Runs `sa_threads` workers like `simulated_annealing`, but instead of taking whichever
+/// finishes first, runs each for `sa_iteration_budget` iterations and returns the best
+/// deck across all of them, breaking ties by the lowest thread id. Checkpointing is
+/// disabled, since workers reading each other's checkpoints would race.
+/// A timeout cuts the workers short, so the result is only reproducible without one.
+pub fn simulated_annealing_deterministic(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let config = SearchConfig {
+        checkpoint: None,
+        ..config.clone()
+    };
+    // Workers report progress over one channel, so this thread can drive the observer
+    let (tx, rx) = crossbeam::channel::unbounded();
+    let deadline = config.deadline();
+
+    let results: Vec<(Deck, usize)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..config.sa_threads)
+            .map(|thread_id| {
+                let seed = config.seed.wrapping_add((thread_id as u64) * 1000);
+                let (table, config, progress) = (&table, &config, tx.clone());
+                scope.spawn(move || {
+                    let scores = TableScores {
+                        num_players,
+                        table,
+                        config,
+                    };
+                    simulated_annealing_worker(
+                        num_players,
+                        &scores,
+                        thread_id,
+                        seed,
+                        config,
+                        WorkerLimits {
+                            budget: Some(config.sa_iteration_budget),
+                            deadline,
+                        },
+                        &progress,
+                    )
+                })
+            })
+            .collect();
+        drop(tx);
 
-    for i in 0..samples {
-        let deck = start.clone().shuffle(&mut rng);
-        let score = num_wins(num_players, &deck, &table, REAL);
-        scores.push(score);
-
-        if score > max_seen {
-            max_seen = score;
-            eprint!(
-                "\r  New best: {}/{} (sample {}/{})",
-                max_seen,
-                max_wins(REAL),
-                i + 1,
-                samples
-            );
-        } else if i % 100 == 0 {
-            eprint!(
-                "\r  Progress: {}/{} samples (best: {}/{})",
-                i + 1,
-                samples,
-                max_seen,
-                max_wins(REAL)
-            );
+        // The channel closes once every worker has finished
+        let mut best_score = 0;
+        for message in &rx {
+            match message {
+                WorkerMessage::Improvement {
+                    iteration,
+                    score,
+                    deck,
+                } => {
+                    // Each thread tracks its own best; only report the overall best
+                    if score > best_score {
+                        best_score = score;
+                        observer.on_improvement(iteration, score, &deck);
+                    }
+                }
+                WorkerMessage::Tick { iteration, stale } => {
+                    observer.on_tick(iteration, best_score, stale);
+                }
+                WorkerMessage::Finished { .. } => {}
+            }
+        }
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    // Results are in thread order, so keeping the first best breaks ties by thread id
+    let mut best: Option<(Deck, usize)> = None;
+    for (deck, score) in results {
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_score)| score > *best_score)
+        {
+            best = Some((deck, score));
         }
     }
+    let (deck, wins) = best.unwrap_or_else(|| {
+        let deck = Deck::new_deck_order();
+        let wins = config.wins(num_players, &deck, &table);
+        (deck, wins)
+    });
+    let reason = if past(deadline) {
+        StopReason::Timeout
+    } else {
+        StopReason::MaxIterations
+    };
+    config.outcome(num_players, deck, wins, reason)
+}
 
-    eprintln!();
-    eprintln!();
-
-    // Calculate statistics
-    scores.sort();
-    let min = scores[0];
-    let max = scores[scores.len() - 1];
-    let median = scores[scores.len() / 2];
-    let mean: f64 = scores.iter().sum::<usize>() as f64 / scores.len() as f64;
-
-    // Count how many hit certain thresholds
-    let perfect = scores.iter().filter(|&&s| s == max_wins(REAL)).count();
-    let near_perfect = scores.iter().filter(|&&s| s >= 50).count();
-    let good = scores.iter().filter(|&&s| s >= 45).count();
-    let decent = scores.iter().filter(|&&s| s >= 40).count();
-
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  STATISTICS");
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!("  Min score:        {}/{}", min, max_wins(REAL));
-    eprintln!("  Max score:        {}/{}", max, max_wins(REAL));
-    eprintln!("  Median score:     {}/{}", median, max_wins(REAL));
-    eprintln!("  Mean score:       {:.1}/{}", mean, max_wins(REAL));
-    eprintln!();
-    eprintln!(
-        "  Perfect (52/52):  {} ({:.2}%)",
-        perfect,
-        perfect as f64 / samples as f64 * 100.0
-    );
-    eprintln!(
-        "  ≥50/52:           {} ({:.2}%)",
-        near_perfect,
-        near_perfect as f64 / samples as f64 * 100.0
-    );
-    eprintln!(
-        "  ≥45/52:           {} ({:.2}%)",
-        good,
-        good as f64 / samples as f64 * 100.0
+/// Tabu search over swap moves
+///
+/// Each step samples `tabu_neighbors` random swaps and takes the best one, even if it's worse
+/// than the current deck. Swapped position pairs stay tabu for `tabu_tenure` steps so the
+/// search can't immediately undo them and cycle, unless the swap beats the best deck so far.
+pub fn tabu_search(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let mut rng = oorandom::Rand32::new(config.seed);
+    let start = Deck::new_deck_order().shuffle(&mut rng);
+
+    progress!(
+        config,
+        "  🚫 Starting tabu search (tenure: {}, neighbors: {})...",
+        config.tabu_tenure,
+        config.tabu_neighbors
     );
-    eprintln!(
-        "  ≥40/52:           {} ({:.2}%)",
-        decent,
-        decent as f64 / samples as f64 * 100.0
+    progress!(config);
+
+    let deadline = config.deadline();
+    let (deck, score) = tabu_search_from(
+        start,
+        num_players,
+        &table,
+        config,
+        deadline,
+        &mut rng,
+        observer,
     );
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    eprintln!();
-
-    // Distribution by score
-    eprintln!("  SCORE DISTRIBUTION");
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    let mut histogram = vec![0; max_wins(REAL) + 1];
-    for &score in &scores {
-        histogram[score] += 1;
-    }
-
-    for (score, &count) in histogram.iter().enumerate() {
-        if count > 0 {
-            let bar_len = (count as f64 / samples as f64 * 50.0) as usize;
-            let bar = "█".repeat(bar_len);
-            eprintln!("  {:2}/52: {:4} {}", score, count, bar);
-        }
+    progress!(config);
+    if score == config.max_wins(num_players) {
+        progress!(config, "  ✓ Perfect deck found!");
+    } else if past(deadline) {
+        progress!(
+            config,
+            "  ⏱️  Timed out. Best found: {}/{}",
+            score,
+            config.max_wins(num_players),
+        );
+        return config.outcome(num_players, deck, score, StopReason::Timeout);
+    } else {
+        progress!(
+            config,
+            "  ⚠️  Max iterations ({}) reached. Best found: {}/{}",
+            config.tabu_iterations,
+            score,
+            config.max_wins(num_players)
+        );
     }
-    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    config.outcome(num_players, deck, score, StopReason::MaxIterations)
 }
 
-pub fn random_search_for_deck(num_players: usize, table: ScoreTable) -> Deck {
-    let start = Deck::new_deck_order();
-    let mut random = oorandom::Rand32::new(4);
-    let mut iterations = 0;
-    let mut best_score = 0;
-
-    loop {
-        iterations += 1;
-        let shuffled = start.clone().shuffle(&mut random);
-        let score = num_wins(num_players, &shuffled, &table, REAL);
+/// Run tabu search from `start`, returning the best deck seen and its win count
+fn tabu_search_from(
+    start: Deck,
+    num_players: usize,
+    table: &ScoreTable,
+    config: &SearchConfig,
+    deadline: Option<Instant>,
+    rng: &mut oorandom::Rand32,
+    observer: &mut dyn SearchObserver,
+) -> (Deck, usize) {
+    let mut current = start;
+    let mut current_mask = config.winning_positions(num_players, &current, table);
+    let mut best_score = current_mask.count_ones() as usize;
+    let mut best_deck = current;
+    let mut tabu: std::collections::VecDeque<(usize, usize)> =
+        std::collections::VecDeque::with_capacity(config.tabu_tenure);
+    let mut iterations_without_improvement = 0;
 
-        if score > best_score {
-            best_score = score;
-            eprint!(
-                "\r  ⚡ Iteration {}: Found deck with score {}/{}",
-                iterations,
-                score,
-                max_wins(REAL)
-            );
+    for iteration in 1..=config.tabu_iterations {
+        if best_score == config.max_wins(num_players) || past(deadline) {
+            break;
         }
 
-        if score == max_wins(REAL) {
-            eprintln!();
-            eprintln!("  ✓ Perfect deck found after {} iterations!", iterations);
-            return shuffled;
+        // Best admissible neighbor: not tabu, or better than anything seen (aspiration)
+        let mut best_move: Option<((usize, usize), u64)> = None;
+        for _ in 0..config.tabu_neighbors {
+            let a = rng.rand_range(0..52) as usize;
+            let b = rng.rand_range(0..52) as usize;
+            if a == b {
+                continue;
+            }
+            let pair = (a.min(b), a.max(b));
+
+            current.swap(a, b);
+            let mask = num_wins_delta(
+                num_players,
+                &current,
+                pair,
+                current_mask,
+                config.real,
+                config.deal,
+                |cut_deck| config.wins_game(num_players, cut_deck, table),
+            );
+            current.swap(a, b); // undo
+
+            let score = mask.count_ones();
+            let admissible = !tabu.contains(&pair) || score as usize > best_score;
+            if admissible && best_move.is_none_or(|(_, m)| score > m.count_ones()) {
+                best_move = Some((pair, mask));
+            }
+        }
+
+        let Some(((a, b), mask)) = best_move else {
+            continue;
+        };
+        current.swap(a, b);
+        current_mask = mask;
+        let score = mask.count_ones() as usize;
+        tabu.push_back((a, b));
+        if tabu.len() > config.tabu_tenure {
+            tabu.pop_front();
+        }
+
+        if score > best_score {
+            best_score = score;
+            best_deck = current;
+            iterations_without_improvement = 0;
+            observer.on_improvement(iteration, best_score, &best_deck);
+        } else {
+            iterations_without_improvement += 1;
+            if iteration % 10 == 0 {
+                observer.on_tick(iteration, best_score, iterations_without_improvement);
+            }
+        }
+    }
+
+    (best_deck, best_score)
+}
+
+/// Print statistics on the win counts of `samples` random decks, scored on `num_threads` threads
+/// and dealt with `config`
+/// `Human` prints a report and histogram to stderr; `Json` prints a `DifficultyStats` to stdout
+/// With `positions`, a `PositionStats` for the same decks follows, as a second JSON line
+/// `quiet` skips the banner and progress line, leaving only the report
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_difficulty(
+    num_players: usize,
+    table: ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    num_threads: usize,
+    real: bool,
+    config: DealConfig,
+    positions: bool,
+    format: OutputFormat,
+    quiet: bool,
+) -> std::io::Result<()> {
+    if !quiet {
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        eprintln!("  Analyzing problem difficulty ({} players)", num_players);
+        match riffles {
+            Some(riffles) => eprintln!("  Sampling {samples} decks riffled {riffles} times..."),
+            None => eprintln!("  Sampling {} random decks...", samples),
+        }
+        eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        eprintln!();
+    }
+
+    let counts = difficulty_counts(
+        num_players,
+        &table,
+        samples,
+        riffles,
+        num_threads,
+        real,
+        config,
+        quiet,
+    );
+    if !quiet {
+        eprintln!();
+        eprintln!();
+    }
+
+    let stats = DifficultyStats::new(counts.histogram, max_wins(num_players, real));
+    match format {
+        OutputFormat::Human => eprint!("{stats}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&stats)?),
+    }
+    if positions {
+        let stats = PositionStats::new(samples, &counts.position_wins, real);
+        match format {
+            OutputFormat::Human => eprint!("\n{stats}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string(&stats)?),
+        }
+    }
+    Ok(())
+}
+
+/// `analyze_difficulty` for every player count from 2 to `max_players`, printing one summary
+/// line per count
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_player_counts(
+    max_players: usize,
+    table: ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    num_threads: usize,
+    real: bool,
+    config: DealConfig,
+    format: OutputFormat,
+    quiet: bool,
+) -> std::io::Result<()> {
+    let sweep = player_count_sweep(
+        max_players,
+        &table,
+        samples,
+        riffles,
+        num_threads,
+        real,
+        config,
+        quiet,
+    );
+    match format {
+        OutputFormat::Human => {
+            for counts in sweep {
+                eprintln!("{counts}");
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string(&sweep)?),
+    }
+    Ok(())
+}
+
+/// The `DifficultyStats` of `analyze_difficulty` at one player count
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PlayerCountStats {
+    pub num_players: usize,
+    #[serde(flatten)]
+    pub stats: DifficultyStats,
+}
+
+impl std::fmt::Display for PlayerCountStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stats = &self.stats;
+        let max_wins = stats.max_wins;
+        write!(
+            f,
+            "  {:2} players: mean {:.1}/{max_wins}, max {}/{max_wins}, perfect {} ({:.2}%)",
+            self.num_players,
+            stats.mean,
+            stats.max,
+            stats.perfect,
+            stats.perfect as f64 / stats.samples as f64 * 100.0
+        )
+    }
+}
+
+/// `difficulty_histogram`'s statistics for each player count from 2 to `max_players`, sharing
+/// one table
+#[allow(clippy::too_many_arguments)]
+pub fn player_count_sweep(
+    max_players: usize,
+    table: &ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    num_threads: usize,
+    real: bool,
+    config: DealConfig,
+    quiet: bool,
+) -> Vec<PlayerCountStats> {
+    (2..=max_players)
+        .map(|num_players| {
+            if !quiet {
+                eprintln!("  Sampling {samples} decks for {num_players} players...");
+            }
+            let histogram = difficulty_histogram(
+                num_players,
+                table,
+                samples,
+                riffles,
+                num_threads,
+                real,
+                config,
+                quiet,
+            );
+            if !quiet {
+                eprintln!();
+            }
+            let stats = DifficultyStats::new(histogram, max_wins(num_players, real));
+            PlayerCountStats { num_players, stats }
+        })
+        .collect()
+}
+
+/// Summary statistics of a `difficulty_histogram`, as printed by `analyze`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DifficultyStats {
+    pub samples: usize,
+    /// The most games any deck can win
+    pub max_wins: usize,
+    pub min: usize,
+    pub max: usize,
+    pub median: usize,
+    pub mean: f64,
+    /// Decks that won every game
+    pub perfect: usize,
+    /// Decks that came close to perfect, at thresholds a few wins below `max_wins`
+    pub near_perfect: Vec<WinThreshold>,
+    /// `histogram[wins]` decks won `wins` games
+    pub histogram: Vec<usize>,
+}
+
+/// How many decks won at least `wins` games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct WinThreshold {
+    pub wins: usize,
+    pub decks: usize,
+}
+
+impl DifficultyStats {
+    pub fn new(histogram: Vec<usize>, max_wins: usize) -> Self {
+        let samples: usize = histogram.iter().sum();
+        let scored = |score: usize| histogram[score] > 0;
+        let min = (0..histogram.len())
+            .find(|&s| scored(s))
+            .expect("no samples");
+        let max = (0..histogram.len()).rev().find(|&s| scored(s)).unwrap();
+        // The score at index `samples / 2` of the sorted scores
+        let mut below = 0;
+        let median = (0..histogram.len())
+            .find(|&s| {
+                below += histogram[s];
+                below > samples / 2
+            })
+            .unwrap();
+        let total: usize = histogram.iter().enumerate().map(|(s, &n)| s * n).sum();
+        let mean = total as f64 / samples as f64;
+        let perfect = histogram[max_wins];
+        let mut stats = Self {
+            samples,
+            max_wins,
+            min,
+            max,
+            median,
+            mean,
+            perfect,
+            near_perfect: Vec::new(),
+            histogram,
+        };
+        stats.near_perfect = Self::near_perfect_thresholds(max_wins)
+            .into_iter()
+            .map(|wins| WinThreshold {
+                wins,
+                decks: stats.at_least(wins),
+            })
+            .collect();
+        stats
+    }
+
+    /// The win counts `near_perfect` reports on: 2, 7 and 12 short of `max_wins`
+    /// (50, 45 and 40 of all 52 cuts), skipping any that would fall below 0
+    fn near_perfect_thresholds(max_wins: usize) -> Vec<usize> {
+        [2, 7, 12]
+            .into_iter()
+            .filter_map(|short| max_wins.checked_sub(short))
+            .collect()
+    }
+
+    /// How many decks won at least `threshold` games
+    pub fn at_least(&self, threshold: usize) -> usize {
+        self.histogram.iter().skip(threshold).sum()
+    }
+}
+
+impl std::fmt::Display for DifficultyStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |count: usize| count as f64 / self.samples as f64 * 100.0;
+        let max_wins = self.max_wins;
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        writeln!(f, "  STATISTICS")?;
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        writeln!(f, "  Min score:        {}/{}", self.min, max_wins)?;
+        writeln!(f, "  Max score:        {}/{}", self.max, max_wins)?;
+        writeln!(f, "  Median score:     {}/{}", self.median, max_wins)?;
+        writeln!(f, "  Mean score:       {:.1}/{}", self.mean, max_wins)?;
+        writeln!(f)?;
+        let perfect = format!("Perfect ({max_wins}/{max_wins}):");
+        writeln!(
+            f,
+            "  {perfect:<18}{} ({:.2}%)",
+            self.perfect,
+            percent(self.perfect)
+        )?;
+        for &WinThreshold { wins, decks } in &self.near_perfect {
+            let label = format!("≥{wins}/{max_wins}:");
+            writeln!(f, "  {label:<18}{decks} ({:.2}%)", percent(decks))?;
+        }
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        writeln!(f)?;
+
+        // Distribution by score
+        writeln!(f, "  SCORE DISTRIBUTION")?;
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        for (score, &count) in self.histogram.iter().enumerate() {
+            if count > 0 {
+                let bar_len = (count as f64 / self.samples as f64 * 50.0) as usize;
+                let bar = "█".repeat(bar_len);
+                writeln!(f, "  {:2}/{}: {:4} {}", score, max_wins, count, bar)?;
+            }
+        }
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+    }
+}
+
+/// How many of `samples` random decks win each number of games, indexed by win count
+///
+/// The samples are split into one batch per thread, and batch `i` shuffles with its own
+/// RNG seeded `4 + 1000 * i`, so the histogram depends only on `samples` and `num_threads`
+/// (one thread draws the same decks the analysis always has)
+/// Progress is reported on stderr unless `quiet`
+/// With `riffles`, each deck is `Deck::riffle_shuffle`d that many times instead of shuffled
+/// uniformly, to see how decks a dealer really shuffles fare. Each cut is dealt with `config`
+#[allow(clippy::too_many_arguments)]
+pub fn difficulty_histogram(
+    num_players: usize,
+    table: &ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    num_threads: usize,
+    real: bool,
+    config: DealConfig,
+    quiet: bool,
+) -> Vec<usize> {
+    let counts = difficulty_counts(
+        num_players,
+        table,
+        samples,
+        riffles,
+        num_threads,
+        real,
+        config,
+        quiet,
+    );
+    counts.histogram
+}
+
+/// What `difficulty_counts` tallies over a sample of decks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifficultyCounts {
+    /// `histogram[wins]` decks won `wins` games
+    pub histogram: Vec<usize>,
+    /// `position_wins[cut_pos]` decks won at cut `cut_pos`
+    pub position_wins: [usize; DECK_SIZE],
+}
+
+/// `difficulty_histogram`, also counting how many of the same decks win at each cut position
+#[allow(clippy::too_many_arguments)]
+pub fn difficulty_counts(
+    num_players: usize,
+    table: &ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    num_threads: usize,
+    real: bool,
+    config: DealConfig,
+    quiet: bool,
+) -> DifficultyCounts {
+    let batches = difficulty_batches(samples, num_threads);
+    let progress = AtomicUsize::new(0);
+    let best = AtomicUsize::new(0);
+
+    let batches: Vec<DifficultyCounts> = std::thread::scope(|scope| {
+        let handles: Vec<_> = batches
+            .iter()
+            .map(|&(seed, batch)| {
+                let (progress, best) = (&progress, &best);
+                scope.spawn(move || {
+                    score_batch(
+                        num_players,
+                        table,
+                        batch,
+                        riffles,
+                        seed,
+                        real,
+                        config,
+                        progress,
+                        best,
+                    )
+                })
+            })
+            .collect();
+
+        // Report progress until every sample is scored
+        if !quiet {
+            loop {
+                let done = handles.iter().all(|h| h.is_finished());
+                eprint!(
+                    "\r  Progress: {}/{} samples (best: {}/{})",
+                    progress.load(Ordering::Relaxed),
+                    samples,
+                    best.load(Ordering::Relaxed),
+                    max_wins(num_players, real)
+                );
+                if done {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+        }
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut counts = DifficultyCounts {
+        histogram: vec![0; max_wins(num_players, real) + 1],
+        position_wins: [0; DECK_SIZE],
+    };
+    for batch in batches {
+        for (total, count) in counts.histogram.iter_mut().zip(batch.histogram) {
+            *total += count;
+        }
+        for (total, count) in counts.position_wins.iter_mut().zip(batch.position_wins) {
+            *total += count;
+        }
+    }
+    counts
+}
+
+/// The `(seed, samples)` of each thread's batch, as even as possible
+fn difficulty_batches(samples: usize, num_threads: usize) -> Vec<(u64, usize)> {
+    let num_threads = num_threads.max(1);
+    (0..num_threads)
+        .map(|i| {
+            let seed = 4u64.wrapping_add(i as u64 * 1000);
+            (
+                seed,
+                samples / num_threads + usize::from(i < samples % num_threads),
+            )
+        })
+        .collect()
+}
+
+/// Score `samples` decks shuffled from one RNG, returning their win counts
+#[allow(clippy::too_many_arguments)]
+fn score_batch(
+    num_players: usize,
+    table: &ScoreTable,
+    samples: usize,
+    riffles: Option<usize>,
+    seed: u64,
+    real: bool,
+    config: DealConfig,
+    progress: &AtomicUsize,
+    best: &AtomicUsize,
+) -> DifficultyCounts {
+    let start = Deck::new_deck_order();
+    let mut rng = oorandom::Rand32::new(seed);
+    let mut counts = DifficultyCounts {
+        histogram: vec![0; max_wins(num_players, real) + 1],
+        position_wins: [0; DECK_SIZE],
+    };
+    for _ in 0..samples {
+        let deck = match riffles {
+            Some(riffles) => Deck::riffle_shuffle(riffles, &mut rng),
+            None => start.shuffle(&mut rng),
+        };
+        let mask = winning_positions_with(num_players, &deck, table, real, config);
+        let score = mask.count_ones() as usize;
+        counts.histogram[score] += 1;
+        for (cut_pos, count) in counts.position_wins.iter_mut().enumerate() {
+            *count += (mask >> cut_pos & 1) as usize;
+        }
+        best.fetch_max(score, Ordering::Relaxed);
+        progress.fetch_add(1, Ordering::Relaxed);
+    }
+    counts
+}
+
+/// How often random decks win at each cut position, as printed by `analyze --positions`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PositionStats {
+    pub samples: usize,
+    /// The scored cut positions, in order
+    pub positions: Vec<PositionWins>,
+}
+
+/// How many of the sampled decks the dealer wins at one cut position
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct PositionWins {
+    pub cut_pos: usize,
+    pub wins: usize,
+}
+
+impl std::fmt::Display for PositionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  WINS BY CUT POSITION")?;
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        for &PositionWins { cut_pos, wins } in &self.positions {
+            let rate = wins as f64 / self.samples as f64;
+            let bar = "█".repeat((rate * 50.0) as usize);
+            writeln!(
+                f,
+                "  Cut {cut_pos:2}: {wins:6} ({:5.1}%) {bar}",
+                rate * 100.0
+            )?;
+        }
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+    }
+}
+
+impl PositionStats {
+    /// The stats of `samples` decks, `position_wins[cut_pos]` of which won at `cut_pos`
+    /// Only the cut positions `real` scores are reported
+    pub fn new(samples: usize, position_wins: &[usize; DECK_SIZE], real: bool) -> Self {
+        let positions = cut_positions(real)
+            .map(|cut_pos| PositionWins {
+                cut_pos,
+                wins: position_wins[cut_pos],
+            })
+            .collect();
+        PositionStats { samples, positions }
+    }
+}
+
+/// A Monte-Carlo estimate of the mean number of winning cuts per random deck
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct WinRateEstimate {
+    /// Estimated mean wins per deck, out of `max_wins`
+    pub mean_wins: f64,
+    /// Half-width of the 95% confidence interval around `mean_wins`
+    pub margin: f64,
+}
+
+impl WinRateEstimate {
+    /// The estimated fraction of the cuts `real` scores that the dealer wins
+    pub fn win_rate(&self, num_players: usize, real: bool) -> f64 {
+        self.mean_wins / max_wins(num_players, real) as f64
+    }
+}
+
+/// A faster, approximate `analyze_difficulty`
+/// For each of `decks` random decks, only `cuts_per_deck` distinct cut positions are
+/// scored and the deck's win count is extrapolated from them. With every cut position
+/// sampled, each deck is scored exactly (as in `analyze_difficulty`, on the same decks)
+pub fn analyze_difficulty_sampled(
+    num_players: usize,
+    table: &ScoreTable,
+    decks: usize,
+    cuts_per_deck: usize,
+    real: bool,
+    config: DealConfig,
+) -> WinRateEstimate {
+    assert!(decks > 0, "need at least one deck to sample");
+    let start = Deck::new_deck_order();
+    // Separate generators, so the decks drawn don't depend on `cuts_per_deck`
+    let mut deck_rng = oorandom::Rand32::new(4);
+    let mut cut_rng = oorandom::Rand32::new(5);
+
+    let mut positions: Vec<usize> = cut_positions(real).collect();
+    let cuts_per_deck = cuts_per_deck.clamp(1, positions.len());
+    let scale = positions.len() as f64 / cuts_per_deck as f64;
+
+    let estimates: Vec<f64> = (0..decks)
+        .map(|_| {
+            let deck = start.shuffle(&mut deck_rng);
+            // Partial Fisher-Yates: the first `cuts_per_deck` positions are a random sample
+            for i in 0..cuts_per_deck {
+                let j = cut_rng.rand_range(i as u32..positions.len() as u32) as usize;
+                positions.swap(i, j);
+            }
+            let hits = positions[..cuts_per_deck]
+                .iter()
+                .filter(|&&pos| dealer_wins_game_with(num_players, deck.cut(pos), table, config))
+                .count();
+            hits as f64 * scale
+        })
+        .collect();
+
+    let n = estimates.len() as f64;
+    let mean_wins = estimates.iter().sum::<f64>() / n;
+    let variance = if estimates.len() > 1 {
+        estimates
+            .iter()
+            .map(|e| (e - mean_wins).powi(2))
+            .sum::<f64>()
+            / (n - 1.0)
+    } else {
+        0.0
+    };
+
+    WinRateEstimate {
+        mean_wins,
+        margin: 1.96 * (variance / n).sqrt(),
+    }
+}
+
+/// Print whether the dealer wins at each cut position of a fixed deck, dealt with `config`
+/// The total only counts the cut positions `real` scores
+pub fn verify_deck(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    config: DealConfig,
+) {
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    eprintln!("  Verifying deck ({} players)", num_players);
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("{}", deck);
+
+    let mask = winning_positions_with(num_players, deck, table, false, config);
+    let mut losses = Vec::new();
+    for cut_pos in 0..52 {
+        let wins = mask & (1 << cut_pos) != 0;
+        println!("  Cut {:2}: {}", cut_pos, if wins { "win" } else { "loss" });
+        if !wins {
+            losses.push(cut_pos);
+        }
+    }
+
+    println!();
+    println!(
+        "  Total wins: {}/{}",
+        num_wins_with(num_players, deck, table, real, config),
+        max_wins(num_players, real)
+    );
+    if losses.is_empty() {
+        println!("  ✓ Dealer wins at every cut position");
+    } else {
+        println!("  Dealer loses at cut positions: {:?}", losses);
+    }
+    // The losses closest to flipping, which a swap or two might turn into wins
+    let margins = margin_profile(num_players, 0, deck, table, real, config);
+    let mut close: Vec<(usize, i32)> = cut_positions(real)
+        .zip(margins)
+        .filter(|&(_, margin)| margin <= 0)
+        .collect();
+    close.sort_by_key(|&(cut_pos, margin)| (-margin, cut_pos));
+    if !close.is_empty() {
+        let close: Vec<String> = close
+            .iter()
+            .take(3)
+            .map(|(cut_pos, margin)| format!("cut {cut_pos} ({margin})"))
+            .collect();
+        println!("  Closest losses: {}", close.join(", "));
+    }
+
+    println!("  Wins by dealer hand:");
+    let counts = win_category_histogram(num_players, deck, table, real, config);
+    for (rank, &count) in counts.iter().enumerate().filter(|&(_, &count)| count > 0) {
+        println!(
+            "    {:<16} {}",
+            HandCategory::from(rank as u8).to_string(),
+            count
+        );
+    }
+}
+
+/// `verify_deck` for a 36-card short deck, scored under short-deck rules at every cut
+pub fn verify_short_deck(num_players: usize, deck: &Deck, table: &ScoreTable, config: DealConfig) {
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    eprintln!("  Verifying short deck ({} players)", num_players);
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("{}", deck);
+
+    let mask = winning_positions_short_deck(num_players, deck, table, config);
+    let mut losses = Vec::new();
+    for cut_pos in 0..SHORT_DECK_SIZE {
+        let wins = mask & (1 << cut_pos) != 0;
+        println!("  Cut {:2}: {}", cut_pos, if wins { "win" } else { "loss" });
+        if !wins {
+            losses.push(cut_pos);
+        }
+    }
+
+    println!();
+    println!("  Total wins: {}/{}", mask.count_ones(), SHORT_DECK_SIZE);
+    if losses.is_empty() {
+        println!("  ✓ Dealer wins at every cut position");
+    } else {
+        println!("  Dealer loses at cut positions: {:?}", losses);
+    }
+}
+
+/// Print a table of the hands and margin at every cut position `real` scores of a fixed deck
+/// With `equity`, also the dealer's `position_equity` at each cut, which is exhaustive and slow
+pub fn print_positions(
+    num_players: usize,
+    deck: &Deck,
+    table: &ScoreTable,
+    real: bool,
+    equity: bool,
+) {
+    println!("{}", deck);
+    println!(
+        "  {:>3}  {:<16} {:<16} {:>9}  {:<6}{} Dealer's cards",
+        "Cut",
+        "Dealer",
+        "Best opponent",
+        "Margin",
+        "Result",
+        if equity { " Equity " } else { "" }
+    );
+    for cut_pos in cut_positions(real) {
+        let summary = position_summary(num_players, deck, cut_pos, table);
+        let equity = if equity {
+            let equity = position_equity(num_players, deck, cut_pos, table);
+            format!(" {:>6.1}%", equity * 100.0)
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:>3}  {:<16} {:<16} {:>9}  {:<6}{} made with {}",
+            cut_pos,
+            summary.dealer.category().to_string(),
+            opponent_name(summary.best_opponent),
+            summary.margin,
+            result_name(&summary),
+            equity,
+            spaced_cards(&summary.dealer_cards)
+        );
+    }
+}
+
+/// Where every card goes when `deck` is cut at `cut_pos` and dealt with `config`
+pub fn print_deal_trace(num_players: usize, deck: &Deck, cut_pos: usize, config: DealConfig) {
+    let (_, trace) = deal_a_round_traced_with(num_players, deck.cut(cut_pos), config);
+    println!();
+    println!("  Deal at cut {cut_pos}:");
+    for line in trace.to_string().lines() {
+        println!("    {line}");
+    }
+}
+
+/// Cards separated by spaces, e.g. "K♠ K♥ 7♦ 7♣ A♠"
+fn spaced_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(Card::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The best opponent's hand, or "none" when the dealer plays alone
+fn opponent_name(best_opponent: Option<HandCategory>) -> String {
+    best_opponent.map_or_else(|| "none".to_string(), |category| category.to_string())
+}
+
+/// How the dealer does at a cut: "win", "split" when they tie for the best hand, or "loss"
+fn result_name(summary: &PositionSummary) -> &'static str {
+    if summary.dealer_wins {
+        "win"
+    } else if summary.splits_pot {
+        "split"
+    } else {
+        "loss"
+    }
+}
+
+/// Print how often each of two hands wins, ties, and loses all-in before the flop
+/// See `heads_up_equity`: every board the other 48 cards can make is scored
+pub fn print_heads_up(p0: Player, p1: Player, table: &ScoreTable) {
+    let (win, tie, loss) = heads_up_equity(p0, p1, table);
+    println!(
+        "{} vs {}: win {:.1}%, tie {:.1}%, loss {:.1}%",
+        spaced_cards(&p0.0),
+        spaced_cards(&p1.0),
+        win * 100.0,
+        tie * 100.0,
+        loss * 100.0
+    );
+}
+
+/// Print the cut position that's best for the dealer out of those `real` scores,
+/// and what happens there
+pub fn print_best_cut(num_players: usize, deck: &Deck, table: &ScoreTable, real: bool) {
+    let (cut_pos, margin) = best_cut_for_dealer(num_players, deck, table, real);
+    let summary = position_summary(num_players, deck, cut_pos, table);
+    println!("{}", deck);
+    println!("Best cut: {} (margin {})", cut_pos, margin);
+    println!(
+        "  Dealer: {} made with {}, best opponent: {}, {}",
+        summary.dealer.describe(),
+        spaced_cards(&summary.dealer_cards),
+        opponent_name(summary.best_opponent),
+        result_name(&summary)
+    );
+}
+
+pub fn random_search_for_deck(
+    num_players: usize,
+    table: Arc<ScoreTable>,
+    config: &SearchConfig,
+    observer: &mut dyn SearchObserver,
+) -> SearchOutcome {
+    let start = Deck::new_deck_order();
+    let mut random = oorandom::Rand32::new(config.seed);
+    let deadline = config.deadline();
+    let mut iterations = 0;
+    let mut best_deck = start;
+    let mut best_score = 0;
+
+    loop {
+        iterations += 1;
+        let shuffled = start.shuffle(&mut random);
+        let score = config.wins(num_players, &shuffled, &table);
+
+        if score > best_score {
+            best_score = score;
+            best_deck = shuffled;
+            observer.on_improvement(iterations, score, &shuffled);
+        }
+
+        if score == config.max_wins(num_players) {
+            progress!(config);
+            progress!(
+                config,
+                "  ✓ Perfect deck found after {} iterations!",
+                iterations
+            );
+            return config.outcome(num_players, shuffled, score, StopReason::Perfect);
         }
+
+        if past(deadline) {
+            return config.outcome(num_players, best_deck, best_score, StopReason::Timeout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::known_decks;
+
+    /// Tests score every cut position, like `SearchConfig::default`
+    const REAL: bool = false;
+
+    /// A config small enough to run against the direct-scoring table
+    fn tiny_config() -> SearchConfig {
+        SearchConfig {
+            pop_size: 4,
+            elite_size: 1,
+            num_crossovers: 2,
+            num_mutations: 2,
+            max_generations: 2,
+            local_sa_iterations: 5,
+            local_sa_iterations_stuck: 5,
+            ..SearchConfig::default()
+        }
+    }
+
+    /// Records every improvement and tick it's told about, and each generation's best score
+    #[derive(Default)]
+    struct Recorder {
+        improvements: Vec<(usize, usize)>,
+        ticks: usize,
+        generation_bests: Vec<usize>,
+    }
+
+    impl SearchObserver for Recorder {
+        fn on_improvement(&mut self, step: usize, score: usize, deck: &Deck) {
+            assert!(deck.is_valid_permutation());
+            self.improvements.push((step, score));
+        }
+
+        fn on_tick(&mut self, _step: usize, _best_score: usize, _stale: usize) {
+            self.ticks += 1;
+        }
+
+        fn on_generation(&mut self, _generation: usize, population: &[(Deck, usize)]) {
+            self.generation_bests
+                .push(population.iter().map(|&(_, s)| s).max().unwrap());
+        }
+    }
+
+    #[test]
+    fn quiet_observer_does_not_change_the_outcome() {
+        let config = SearchConfig {
+            seed: 23,
+            ..tiny_config()
+        };
+        let table = Arc::new(ScoreTable::direct());
+        let mut recorder = Recorder::default();
+        let observed = genetic_search(2, Arc::clone(&table), &config, &mut recorder);
+        let quiet = genetic_search(2, table, &config, &mut QuietObserver);
+        assert_eq!(quiet, observed);
+        assert_eq!(
+            recorder.improvements.last().map(|&(_, s)| s),
+            Some(quiet.wins)
+        );
+    }
+
+    #[test]
+    fn genetic_search_is_reproducible_per_seed() {
+        let config = SearchConfig {
+            seed: 17,
+            ..tiny_config()
+        };
+        let first = genetic_search(
+            2,
+            Arc::new(ScoreTable::direct()),
+            &config,
+            &mut Recorder::default(),
+        );
+        let second = genetic_search(
+            2,
+            Arc::new(ScoreTable::direct()),
+            &config,
+            &mut Recorder::default(),
+        );
+        assert_eq!(first, second);
+
+        let other = SearchConfig {
+            seed: 18,
+            ..tiny_config()
+        };
+        assert_ne!(
+            first,
+            genetic_search(
+                2,
+                Arc::new(ScoreTable::direct()),
+                &other,
+                &mut Recorder::default()
+            )
+        );
+    }
+
+    #[test]
+    fn adaptive_crossover_with_one_operator_matches_plain_crossover() {
+        let table = Arc::new(ScoreTable::direct());
+        let plain = SearchConfig {
+            num_crossovers: 6,
+            max_generations: 4,
+            ..tiny_config()
+        };
+        let adaptive = SearchConfig {
+            adaptive_crossover: true,
+            crossover_operators: vec![CrossoverOperator::TwoPoint],
+            ..plain.clone()
+        };
+        let run = |config: &SearchConfig| {
+            genetic_search(2, Arc::clone(&table), config, &mut QuietObserver)
+        };
+        assert_eq!(run(&plain), run(&adaptive));
+
+        let pmx_only = SearchConfig {
+            crossover_operators: vec![CrossoverOperator::Pmx],
+            ..adaptive.clone()
+        };
+        assert!(run(&pmx_only).deck.is_valid_permutation());
+    }
+
+    #[test]
+    fn crossover_stats_favour_operators_that_improve() {
+        let config = SearchConfig {
+            adaptive_crossover: true,
+            crossover_operators: vec![CrossoverOperator::TwoPoint, CrossoverOperator::Cycle],
+            ..SearchConfig::default()
+        };
+        let mut stats = CrossoverStats::new(&config);
+        for _ in 0..20 {
+            stats.record(0, false);
+            stats.record(1, true);
+        }
+        let mut rng = oorandom::Rand32::new(2);
+        let cycles = (0..1000).filter(|_| stats.pick(&mut rng) == 1).count();
+        assert!(cycles > 850, "{cycles}");
+
+        // Off, only two-point crossover is used whatever the list says
+        let off = CrossoverStats::new(&SearchConfig::default());
+        assert_eq!(off.operators, [CrossoverOperator::TwoPoint]);
+    }
+
+    #[test]
+    fn top_n_decks_are_distinct_and_sorted() {
+        let top = genetic_search_top_n(
+            2,
+            Arc::new(ScoreTable::direct()),
+            &tiny_config(),
+            3,
+            &mut Recorder::default(),
+        );
+        assert!(!top.is_empty() && top.len() <= 3);
+        for (i, (deck, score)) in top.iter().enumerate() {
+            assert!(deck.is_valid_permutation());
+            for (other, other_score) in &top[i + 1..] {
+                assert_ne!(deck, other);
+                assert!(score >= other_score);
+            }
+        }
+    }
+
+    #[test]
+    fn top_n_distinct_drops_duplicates() {
+        let mut rng = oorandom::Rand32::new(1);
+        let a = Deck::new_deck_order().shuffle(&mut rng);
+        let b = Deck::new_deck_order().shuffle(&mut rng);
+        let c = Deck::new_deck_order().shuffle(&mut rng);
+        let population = vec![(a, 10), (b, 30), (a, 10), (c, 20)];
+        assert_eq!(
+            top_n_distinct(population.clone(), 2),
+            vec![(b, 30), (c, 20)]
+        );
+        assert_eq!(
+            top_n_distinct(population, 5),
+            vec![(b, 30), (c, 20), (a, 10)]
+        );
+    }
+
+    fn checkpoint_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("poker_wins_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn checkpoint_roundtrip() {
+        let path = checkpoint_path("roundtrip");
+        assert!(read_checkpoint(&path).unwrap().is_none());
+
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(3));
+        write_checkpoint(&path, &deck, 17).unwrap();
+        assert_eq!(read_checkpoint(&path).unwrap(), Some((deck, 17)));
+
+        // A worse deck doesn't overwrite a better checkpoint
+        let worse = Deck::new_deck_order();
+        write_checkpoint(&path, &worse, 10).unwrap();
+        assert_eq!(read_checkpoint(&path).unwrap(), Some((deck, 17)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn zero_checkpoint_interval_never_checkpoints() {
+        let path = checkpoint_path("zero_interval");
+        let table = ScoreTable::direct();
+        let config = SearchConfig {
+            checkpoint: Some(path.clone()),
+            checkpoint_interval: 0,
+            ..tiny_config()
+        };
+        let scores = TableScores {
+            num_players: 2,
+            table: &table,
+            config: &config,
+        };
+        let (progress, _) = crossbeam::channel::unbounded();
+        let limits = WorkerLimits {
+            budget: Some(100),
+            deadline: None,
+        };
+        let (deck, _) = simulated_annealing_worker(2, &scores, 0, 7, &config, limits, &progress);
+        assert!(deck.is_valid_permutation());
+        assert!(read_checkpoint(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn resumed_search_starts_from_checkpoint() {
+        let path = checkpoint_path("resume");
+        let table = ScoreTable::direct();
+        let mut rng = oorandom::Rand32::new(5);
+
+        // Checkpoint the best of a handful of random decks
+        let (deck, score) = (0..8)
+            .map(|_| {
+                let deck = Deck::new_deck_order().shuffle(&mut rng);
+                let score = num_wins(2, &deck, &table, REAL);
+                (deck, score)
+            })
+            .max_by_key(|(_, score)| *score)
+            .unwrap();
+        write_checkpoint(&path, &deck, score).unwrap();
+
+        let config = SearchConfig {
+            checkpoint: Some(path.clone()),
+            ..SearchConfig::default()
+        };
+        let scores = TableScores {
+            num_players: 2,
+            table: &table,
+            config: &config,
+        };
+        let (resumed, resumed_score) = initial_deck(&scores, &config, &mut rng);
+        assert_eq!(resumed, deck);
+        assert!(resumed_score >= score);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn observer_sees_increasing_improvements() {
+        let config = SearchConfig {
+            max_generations: 12,
+            ..tiny_config()
+        };
+        let mut recorder = Recorder::default();
+        genetic_search(2, Arc::new(ScoreTable::direct()), &config, &mut recorder);
+
+        for pair in recorder.improvements.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+            assert!(pair[0].1 < pair[1].1);
+        }
+        // A tick every 10 generations without improvement
+        assert!(recorder.ticks <= 1);
+    }
+
+    #[test]
+    fn folding_opponents_only_add_wins() {
+        let table = ScoreTable::direct();
+        let showdown = SearchConfig::default();
+        let folding = SearchConfig {
+            opponent_model: OpponentModel::FoldsBelow(10),
+            ..SearchConfig::default()
+        };
+        let mut rng = oorandom::Rand32::new(102);
+        let mut gained = 0;
+        for _ in 0..20 {
+            let deck = Deck::new_deck_order().shuffle(&mut rng);
+            let before = showdown.winning_positions(3, &deck, &table);
+            let after = folding.winning_positions(3, &deck, &table);
+            assert_eq!(after & before, before);
+            gained += (after & !before).count_ones();
+            let (wins, _) = folding.wins_and_margin(3, &deck, &table);
+            assert_eq!(wins, after.count_ones() as usize);
+        }
+        assert!(gained > 0);
+
+        // Searches count wins the same way
+        let config = SearchConfig {
+            opponent_model: folding.opponent_model,
+            ..tiny_config()
+        };
+        let table = Arc::new(table);
+        let outcome = genetic_search(3, Arc::clone(&table), &config, &mut QuietObserver);
+        assert_eq!(outcome.wins, folding.wins(3, &outcome.deck, &table));
+    }
+
+    #[test]
+    fn promote_best_moves_only_the_best() {
+        let [a, b, c, d] = [0, 1, 2, 3]
+            .map(|seed| Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(seed)));
+        let mut population = [(a, 3), (b, 5), (c, 2), (d, 5)];
+        promote_best(&mut population);
+        assert_eq!(population, [(b, 5), (a, 3), (c, 2), (d, 5)]);
+    }
+
+    #[test]
+    fn genetic_population_never_loses_its_best() {
+        // Stagnating from the start, so every generation is sorted on diversity; with this seed
+        // that ranks the best deck below the cutoff
+        let config = SearchConfig {
+            seed: 33,
+            pop_size: 6,
+            max_generations: 25,
+            stagnation_threshold: 0,
+            ..tiny_config()
+        };
+        let mut recorder = Recorder::default();
+        let result = genetic_search(2, Arc::new(ScoreTable::direct()), &config, &mut recorder);
+
+        assert!(!recorder.generation_bests.is_empty());
+        for pair in recorder.generation_bests.windows(2) {
+            assert!(pair[0] <= pair[1], "{:?}", recorder.generation_bests);
+        }
+        assert_eq!(recorder.generation_bests.last(), Some(&result.wins));
+    }
+
+    #[test]
+    fn tabu_search_improves_on_a_random_deck() {
+        let table = ScoreTable::direct();
+        let config = SearchConfig {
+            tabu_iterations: 15,
+            tabu_neighbors: 10,
+            tabu_tenure: 5,
+            ..SearchConfig::default()
+        };
+        let mut rng = oorandom::Rand32::new(config.seed);
+        let start = Deck::new_deck_order().shuffle(&mut rng);
+        let start_score = num_wins(2, &start, &table, REAL);
+
+        let mut recorder = Recorder::default();
+        let (deck, score) =
+            tabu_search_from(start, 2, &table, &config, None, &mut rng, &mut recorder);
+        assert!(deck.is_valid_permutation());
+        assert_eq!(score, num_wins(2, &deck, &table, REAL));
+        assert!(score > start_score);
+        assert_eq!(recorder.improvements.last().map(|&(_, s)| s), Some(score));
+    }
+
+    #[test]
+    fn run_search_rejects_too_many_players() {
+        // Fails before it tries to load the table, rather than panicking mid-deal
+        let err = run_search(
+            23,
+            Path::new(DEFAULT_TABLE_PATH),
+            genetic_search,
+            "genetic",
+            &SearchConfig::default(),
+            OutputFormat::Human,
+            &mut Recorder::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "23 players need 54 cards but only 52 are dealable"
+        );
+    }
+
+    #[test]
+    fn search_result_json_roundtrips_through_card_ids() {
+        let deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(12));
+        let outcome = SearchOutcome::new(52, deck, 40, StopReason::MaxIterations);
+        let result = SearchResult::new(2, &outcome, "genetic");
+        let json = serde_json::to_string(&result).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["num_players"], 2);
+        assert_eq!(value["wins"], 40);
+        assert_eq!(value["algorithm"], "genetic");
+        assert_eq!(value["perfect"], false);
+        let ids: Vec<u8> = serde_json::from_value(value["deck"].clone()).unwrap();
+        assert_eq!(Deck::from_card_ids(&ids), Ok(deck));
+    }
+
+    #[test]
+    fn searches_score_the_target_player() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            target_player: 1,
+            tabu_iterations: 3,
+            tabu_neighbors: 5,
+            num_ants: 2,
+            aco_iterations: 2,
+            aco_sa_iterations: 5,
+            aco_max_restarts: 1,
+            ..tiny_config()
+        };
+        let searches: [(SearchFn, &str); 3] = [
+            (genetic_search, "genetic"),
+            (tabu_search, "tabu"),
+            (ant_colony_search, "aco"),
+        ];
+        for (search, name) in searches {
+            let outcome = search(3, Arc::clone(&table), &config, &mut Recorder::default());
+            let wins = num_wins_for(3, 1, &outcome.deck, &table, REAL);
+            assert_eq!(outcome.wins, wins, "{name}");
+        }
+    }
+
+    #[test]
+    fn aco_heuristic_counts_the_target_players_positions() {
+        let seat = |target_player| SearchConfig {
+            target_player,
+            ..SearchConfig::default()
+        };
+        let (dealer, other) = (seat(0), seat(1));
+        assert!((0..52).any(|position| {
+            calculate_heuristic(position, 12, 3, &[], &dealer)
+                != calculate_heuristic(position, 12, 3, &[], &other)
+        }));
+    }
+
+    #[test]
+    fn target_player_must_be_dealt_in() {
+        let config = SearchConfig {
+            target_player: 2,
+            ..SearchConfig::default()
+        };
+        assert!(check_search(3, &config).is_ok());
+        assert!(check_search(2, &config).is_err());
+
+        let config = SearchConfig {
+            active_opponents: Some(vec![1]),
+            ..SearchConfig::default()
+        };
+        assert!(check_search(3, &config).is_ok());
+        assert!(check_search(1, &config).is_err());
+        let config = SearchConfig {
+            active_opponents: Some(vec![0, 1]),
+            ..SearchConfig::default()
+        };
+        assert!(check_search(3, &config).is_err());
+    }
+
+    #[test]
+    fn searches_score_only_the_active_opponents() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            active_opponents: Some(vec![2]),
+            ..tiny_config()
+        };
+        let outcome = genetic_search(4, Arc::clone(&table), &config, &mut Recorder::default());
+        assert_eq!(
+            outcome.wins,
+            num_wins_vs(4, &[2], &outcome.deck, &table, config.real)
+        );
+    }
+
+    #[test]
+    fn searches_deal_with_the_configured_procedure() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            deal: DealConfig::no_burns(),
+            ..tiny_config()
+        };
+        let outcome = genetic_search(2, Arc::clone(&table), &config, &mut Recorder::default());
+        let wins = num_wins_with(2, &outcome.deck, &table, config.real, config.deal);
+        assert_eq!(outcome.wins, wins);
+    }
+
+    #[test]
+    fn genetic_search_respects_config() {
+        let table = Arc::new(ScoreTable::direct());
+        let outcome = genetic_search(
+            2,
+            Arc::clone(&table),
+            &tiny_config(),
+            &mut Recorder::default(),
+        );
+        assert!(outcome.deck.is_valid_permutation());
+        assert_eq!(outcome.wins, num_wins(2, &outcome.deck, &table, REAL));
+        assert_eq!(outcome.reason, StopReason::MaxIterations);
+    }
+
+    #[test]
+    fn sampled_difficulty_converges_to_exact() {
+        let table = ScoreTable::direct();
+        let decks = 20;
+
+        // The exact mean over the same decks `analyze_difficulty_sampled` draws
+        let mut rng = oorandom::Rand32::new(4);
+        let exact = (0..decks)
+            .map(|_| num_wins(2, &Deck::new_deck_order().shuffle(&mut rng), &table, REAL))
+            .sum::<usize>() as f64
+            / decks as f64;
+
+        let config = DealConfig::default();
+        let all_cuts =
+            analyze_difficulty_sampled(2, &table, decks, max_wins(2, REAL), REAL, config);
+        assert!((all_cuts.mean_wins - exact).abs() < 1e-9);
+
+        let some_cuts = analyze_difficulty_sampled(2, &table, decks, 16, REAL, config);
+        let most_cuts = analyze_difficulty_sampled(2, &table, decks, 40, REAL, config);
+        assert!((some_cuts.mean_wins - exact).abs() <= some_cuts.margin);
+        assert!((most_cuts.mean_wins - exact).abs() <= (some_cuts.mean_wins - exact).abs());
+        assert!(all_cuts.margin <= some_cuts.margin);
+    }
+
+    #[test]
+    fn deterministic_sa_is_reproducible() {
+        let config = SearchConfig {
+            seed: 23,
+            sa_threads: 2,
+            sa_iteration_budget: 50,
+            ..tiny_config()
+        };
+        let table = Arc::new(ScoreTable::direct());
+        let mut recorder = Recorder::default();
+        let first =
+            simulated_annealing_deterministic(2, Arc::clone(&table), &config, &mut recorder);
+        let second = simulated_annealing_deterministic(2, table, &config, &mut QuietObserver);
+        assert!(first.deck.is_valid_permutation());
+        assert_eq!(first.deck.to_id_string(), second.deck.to_id_string());
+        assert_eq!(first.wins, second.wins);
+        // The overall best is reported as it improves, ending at the returned deck's wins
+        let scores: Vec<usize> = recorder
+            .improvements
+            .iter()
+            .map(|&(_, score)| score)
+            .collect();
+        assert!(scores.windows(2).all(|w| w[0] < w[1]), "{scores:?}");
+        assert_eq!(scores.last(), Some(&first.wins));
+    }
+
+    #[test]
+    fn select_parent_is_fitness_proportionate() {
+        let mut rng = oorandom::Rand32::new(31);
+        // Far beyond u32 when summed as integers
+        let fitness = [0.0, 1e12, 2e12, 3e12, 4e12];
+        let draws = 100_000;
+        let mut counts = [0usize; 5];
+        for _ in 0..draws {
+            counts[select_parent(&fitness, &mut rng)] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        for (i, &count) in counts.iter().enumerate().skip(1) {
+            let share = count as f64 / draws as f64;
+            let expected = i as f64 / 10.0;
+            assert!(
+                (share - expected).abs() < 0.01,
+                "index {i}: {share} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn select_parent_is_uniform_when_all_fitness_is_zero() {
+        let mut rng = oorandom::Rand32::new(32);
+        let mut seen = [false; 4];
+        for _ in 0..200 {
+            seen[select_parent(&[0.0; 4], &mut rng)] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn pareto_order_ranks_dominating_individuals_first() {
+        // (wins, margin): 1 dominates 0 and 3, 2 dominates 3, and 1 and 2 trade off
+        let objectives = [(10, 1.0), (12, 5.0), (11, 9.0), (9, 0.5)];
+        let order = pareto_order(&objectives);
+        assert_eq!(order, vec![1, 2, 0, 3]);
+
+        let mut rng = oorandom::Rand32::new(64);
+        for _ in 0..50 {
+            let objectives: Vec<(usize, f64)> = (0..20)
+                .map(|_| (rng.rand_range(0..6) as usize, rng.rand_range(0..6) as f64))
+                .collect();
+            let order = pareto_order(&objectives);
+            let mut rank = vec![0; objectives.len()];
+            for (position, &i) in order.iter().enumerate() {
+                rank[i] = position;
+            }
+            for i in 0..objectives.len() {
+                for j in 0..objectives.len() {
+                    if dominates(objectives[i], objectives[j]) {
+                        assert!(
+                            rank[i] < rank[j],
+                            "{:?} after {:?}",
+                            objectives[i],
+                            objectives[j]
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pareto_genetic_search_reports_its_best_deck() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            pareto_selection: true,
+            ..tiny_config()
+        };
+        let outcome = genetic_search(2, Arc::clone(&table), &config, &mut Recorder::default());
+        assert!(outcome.deck.is_valid_permutation());
+        assert_eq!(outcome.wins, config.wins(2, &outcome.deck, &table));
+    }
+
+    #[test]
+    fn seeding_a_perfect_deck_stops_the_search_at_once() {
+        let table = Arc::new(ScoreTable::direct());
+        let perfect = Deck::from_card_ids(&known_decks::three_player_perfect_deck()).unwrap();
+        let config = SearchConfig {
+            seed_decks: vec![perfect],
+            beam_width: 3,
+            ..tiny_config()
+        };
+        assert_eq!(config.wins(3, &perfect, &table), config.max_wins(3));
+
+        let searches: [SearchFn; 2] = [genetic_search, beam_search];
+        for search in searches {
+            let mut recorder = Recorder::default();
+            let outcome = search(3, Arc::clone(&table), &config, &mut recorder);
+            assert_eq!(outcome.reason, StopReason::Perfect);
+            assert_eq!(outcome.deck, perfect);
+            assert!(recorder.improvements.is_empty() && recorder.ticks == 0);
+        }
+
+        // Without the seed, the first population is random as before
+        let mut rng = oorandom::Rand32::new(config.seed);
+        let seeded = config.initial_decks(3, &table, 3, &mut rng);
+        let mut rng = oorandom::Rand32::new(config.seed);
+        let unseeded = tiny_config().initial_decks(3, &table, 3, &mut rng);
+        assert_eq!(seeded[0], perfect);
+        assert_eq!(seeded[1], unseeded[0]);
+    }
+
+    #[test]
+    fn heuristic_seed_decks_beat_random_ones() {
+        let table = ScoreTable::direct();
+        let config = SearchConfig {
+            heuristic_seeds: 20,
+            ..tiny_config()
+        };
+        for num_players in [2, 3] {
+            let mut rng = oorandom::Rand32::new(config.seed);
+            let decks = config.initial_decks(num_players, &table, 40, &mut rng);
+            let wins: Vec<usize> = decks
+                .iter()
+                .map(|deck| config.wins(num_players, deck, &table))
+                .collect();
+            assert!(decks.iter().all(Deck::is_valid_permutation));
+            let (heuristic, random) = wins.split_at(20);
+            let (heuristic, random): (usize, usize) = (heuristic.iter().sum(), random.iter().sum());
+            assert!(
+                heuristic > random,
+                "{num_players} players: {heuristic} vs {random}"
+            );
+        }
+    }
+
+    #[test]
+    fn single_player_searches_report_perfect() {
+        // With no opponents the dealer wins every cut, so the first deck tried is optimal
+        let table = Arc::new(ScoreTable::direct());
+        let config = tiny_config();
+        let searches: [SearchFn; 4] = [
+            genetic_search,
+            hill_climbing,
+            tabu_search,
+            random_search_for_deck,
+        ];
+        for search in searches {
+            let outcome = search(1, Arc::clone(&table), &config, &mut Recorder::default());
+            assert_eq!(outcome.reason, StopReason::Perfect);
+            assert!(outcome.is_perfect());
+            assert_eq!(outcome.wins, max_wins(1, REAL));
+        }
+    }
+
+    #[test]
+    fn steepest_swaps_climb_to_a_true_local_optimum() {
+        let table = ScoreTable::direct();
+        let config = tiny_config();
+        let mut deck = Deck::new_deck_order().shuffle(&mut oorandom::Rand32::new(6));
+        let mut mask = config.winning_positions(2, &deck, &table);
+        while let Some(((i, j), new_mask)) = steepest_swap(2, deck, mask, &config, &table) {
+            assert!(new_mask.count_ones() > mask.count_ones());
+            deck.swap(i, j);
+            assert_eq!(new_mask, config.winning_positions(2, &deck, &table));
+            mask = new_mask;
+        }
+
+        // Rescoring from scratch, no single swap does better
+        let wins = config.wins(2, &deck, &table);
+        for i in 0..52 {
+            for j in (i + 1)..52 {
+                let mut swapped = deck;
+                swapped.swap(i, j);
+                assert!(
+                    config.wins(2, &swapped, &table) <= wins,
+                    "swapping {i} and {j}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn real_scores_only_the_allowed_cuts() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            real: true,
+            ..tiny_config()
+        };
+        assert_eq!(config.max_wins(2), 42);
+        assert_eq!(SearchConfig::default().max_wins(2), 52);
+
+        // Every cut position wins for a lone dealer, but only 42 of them are scored
+        let outcome = hill_climbing(1, Arc::clone(&table), &config, &mut Recorder::default());
+        assert_eq!(outcome.wins, 42);
+        assert_eq!(outcome.reason, StopReason::Perfect);
+        let deck = Deck::new_deck_order();
+        assert!(config.wins(2, &deck, &table) <= tiny_config().wins(2, &deck, &table));
+    }
+
+    #[test]
+    fn outcome_is_perfect_whenever_every_game_is_won() {
+        let deck = Deck::new_deck_order();
+        let max = max_wins(2, REAL);
+        let outcome = SearchOutcome::new(max, deck, max, StopReason::MaxIterations);
+        assert_eq!(outcome.reason, StopReason::Perfect);
+        let outcome = SearchOutcome::new(max, deck, max - 1, StopReason::Stagnated);
+        assert_eq!(outcome.reason, StopReason::Stagnated);
+        assert!(!outcome.is_perfect());
+        // A failed search stays failed, rather than passing for one that stagnated
+        let outcome = SearchOutcome::new(max, deck, max - 1, StopReason::Failed);
+        assert_eq!(outcome.reason, StopReason::Failed);
+    }
+
+    #[test]
+    fn searches_stop_at_the_timeout() {
+        let table = Arc::new(ScoreTable::direct());
+        let timeout = Duration::from_millis(200);
+        // Cheap steps, and step limits the searches would never reach in time
+        let config = SearchConfig {
+            timeout: Some(timeout),
+            sa_threads: 2,
+            max_generations: usize::MAX,
+            num_islands: 2,
+            beam_width: 3,
+            mutations_per_beam: 2,
+            beam_iterations: usize::MAX,
+            beam_sa_iterations_early: 5,
+            beam_sa_iterations_late: 5,
+            num_ants: 2,
+            aco_sa_iterations: 5,
+            aco_iterations: usize::MAX,
+            aco_restart_threshold: usize::MAX,
+            tabu_iterations: usize::MAX,
+            ..tiny_config()
+        };
+        let searches: [(SearchFn, &str); 8] = [
+            (genetic_search, "genetic"),
+            (island_genetic_search, "island"),
+            (beam_search, "beam"),
+            (ant_colony_search, "aco"),
+            (simulated_annealing, "simulated-annealing"),
+            (hill_climbing, "hill-climbing"),
+            (tabu_search, "tabu"),
+            (random_search_for_deck, "random"),
+        ];
+        // Build the five-card lookup table before timing anything
+        num_wins(3, &Deck::new_deck_order(), &table, REAL);
+        for (search, name) in searches {
+            let started = Instant::now();
+            let outcome = search(3, Arc::clone(&table), &config, &mut Recorder::default());
+            let elapsed = started.elapsed();
+            assert_eq!(outcome.reason, StopReason::Timeout, "{name}");
+            assert_eq!(
+                outcome.wins,
+                num_wins(3, &outcome.deck, &table, REAL),
+                "{name}"
+            );
+            assert!(
+                elapsed < timeout + Duration::from_secs(5),
+                "{name} took {elapsed:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn bounded_island_search_is_reproducible_per_seed() {
+        let table = Arc::new(ScoreTable::direct());
+        let config = SearchConfig {
+            seed: 9,
+            num_islands: 3,
+            migration_interval: 1,
+            num_migrants: 1,
+            max_cycles: Some(3),
+            ..tiny_config()
+        };
+        let run =
+            || island_genetic_search(2, Arc::clone(&table), &config, &mut Recorder::default());
+        let (first, second) = (run(), run());
+        assert_eq!(first.reason, StopReason::MaxIterations);
+        assert_eq!(first, second);
+        assert_eq!(first.wins, num_wins(2, &first.deck, &table, REAL));
+    }
+
+    #[test]
+    fn bench_is_reproducible_from_its_seed() {
+        let table = Arc::new(ScoreTable::direct());
+        // Every search finishes its budget well before the timeout
+        let config = SearchConfig {
+            timeout: Some(Duration::from_secs(1)),
+            sa_threads: 2,
+            sa_iteration_budget: 200,
+            num_islands: 2,
+            migration_interval: 1,
+            max_cycles: Some(2),
+            beam_width: 3,
+            mutations_per_beam: 2,
+            beam_iterations: 2,
+            beam_sa_iterations_early: 5,
+            beam_sa_iterations_late: 5,
+            num_ants: 2,
+            aco_iterations: 2,
+            aco_max_restarts: 1,
+            aco_sa_iterations: 5,
+            ..tiny_config()
+        };
+        // Build the five-card lookup table before timing anything
+        num_wins(2, &Deck::new_deck_order(), &table, REAL);
+        let first = bench(2, Arc::clone(&table), &config);
+        let second = bench(2, Arc::clone(&table), &config);
+        assert_eq!(first.len(), BENCH_SEARCHES.len());
+        for (a, b) in first.iter().zip(&second) {
+            // Under load either run may be cut short, and then the two needn't agree
+            let timed_out = [a, b]
+                .iter()
+                .any(|r| r.outcome.reason == StopReason::Timeout);
+            if !timed_out {
+                assert_eq!(a.outcome, b.outcome, "{}", a.algorithm);
+            }
+            assert_eq!(a.outcome.wins, num_wins(2, &a.outcome.deck, &table, REAL));
+            assert!(a.time_to_best <= a.elapsed, "{}", a.algorithm);
+        }
+    }
+
+    /// A contrived table: a deck wins once for every `width` cards left where new deck order
+    /// has them, so decks sit on wide plateaus of equal wins, while the hybrid score counts
+    /// each card in place and slopes up towards the next win
+    struct HomePlateau {
+        width: usize,
+    }
+
+    impl HomePlateau {
+        fn cards_home(deck: &Deck) -> usize {
+            let home = Deck::new_deck_order();
+            (0..52).filter(|&i| deck[i] == home[i]).count()
+        }
+    }
+
+    impl AnnealingScores for HomePlateau {
+        fn wins(&self, deck: &Deck) -> usize {
+            Self::cards_home(deck) / self.width
+        }
+
+        fn hybrid_score(&self, deck: &Deck) -> f64 {
+            self.wins(deck) as f64 * WIN_WEIGHT + Self::cards_home(deck) as f64
+        }
+    }
+
+    #[test]
+    fn hybrid_acceptance_escapes_a_win_plateau() {
+        // Cold enough that neither run takes a step down: flat acceptance can only wander
+        // the zero-win plateau, while the hybrid run climbs the cards in place off it
+        let plateau = HomePlateau { width: 13 };
+        let flat = SearchConfig {
+            sa_initial_temp: 0.05,
+            ..SearchConfig::default()
+        };
+        let hybrid = SearchConfig {
+            sa_hybrid_acceptance: true,
+            ..flat.clone()
+        };
+        let (progress, _) = crossbeam::channel::unbounded();
+        let limits = WorkerLimits {
+            budget: Some(20_000),
+            deadline: None,
+        };
+        for seed in 0..3 {
+            let run = |config: &SearchConfig| {
+                simulated_annealing_worker(2, &plateau, 0, seed, config, limits, &progress).1
+            };
+            assert_eq!(run(&flat), 0, "seed {seed}");
+            assert!(run(&hybrid) > 0, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn plausibility_weight_penalizes_unshuffled_decks() {
+        let table = ScoreTable::direct();
+        let unweighted = SearchConfig::default();
+        let weighted = SearchConfig {
+            plausibility_weight: 2.0,
+            ..SearchConfig::default()
+        };
+        let new_order = Deck::new_deck_order();
+        let penalty = unweighted.hybrid_score(2, &new_order, &table)
+            - weighted.hybrid_score(2, &new_order, &table);
+        assert_eq!(penalty, 2.0 * WIN_WEIGHT);
+
+        let shuffled = new_order.shuffle(&mut oorandom::Rand32::new(1));
+        let penalty = unweighted.hybrid_score(2, &shuffled, &table)
+            - weighted.hybrid_score(2, &shuffled, &table);
+        assert!(penalty < 0.5 * WIN_WEIGHT, "{penalty}");
+    }
+
+    #[test]
+    fn perturbed_restarts_solve_no_slower_than_random_restarts() {
+        // Every card put in place is a win, but the last few are rare finds, so the search
+        // restarts near the top; a perturbed restart keeps most of the cards already placed
+        let random = SearchConfig {
+            quiet: true,
+            sa_initial_temp: 0.05,
+            sa_hybrid_acceptance: true,
+            sa_restart_interval: 300,
+            ..SearchConfig::default()
+        };
+        let perturbed = SearchConfig {
+            restart_perturbation_chance: 1.0,
+            ..random.clone()
+        };
+        let limits = WorkerLimits {
+            budget: Some(100_000),
+            deadline: None,
+        };
+        // The iteration the worker finds a perfect deck at, if it does within the budget
+        let time_to_solution = |config: &SearchConfig| {
+            let (progress, messages) = crossbeam::channel::unbounded();
+            let plateau = HomePlateau { width: 1 };
+            simulated_annealing_worker(2, &plateau, 0, 2, config, limits, &progress);
+            messages.try_iter().find_map(|message| match message {
+                WorkerMessage::Improvement {
+                    iteration,
+                    score: 52,
+                    ..
+                } => Some(iteration),
+                _ => None,
+            })
+        };
+        let solved = time_to_solution(&perturbed).expect("perturbed restarts solve it");
+        let random_time = time_to_solution(&random).unwrap_or(usize::MAX);
+        assert!(solved <= random_time, "{solved} vs {random_time}");
+    }
+
+    #[test]
+    fn difficulty_stats_json_matches_the_report() {
+        let table = ScoreTable::direct();
+        let config = DealConfig::default();
+        let histogram = difficulty_histogram(2, &table, 20, None, 1, REAL, config, true);
+        let stats = DifficultyStats::new(histogram, max_wins(2, REAL));
+        let json: serde_json::Value = serde_json::to_value(&stats).unwrap();
+        let report = stats.to_string();
+
+        assert_eq!(
+            json["histogram"].as_array().unwrap().len(),
+            max_wins(2, REAL) + 1
+        );
+        assert_eq!(json["perfect"], stats.perfect);
+        let line = |label: &str, value: String| format!("  {label:<18}{value}/52\n");
+        assert!(report.contains(&line("Min score:", json["min"].to_string())));
+        assert!(report.contains(&line("Max score:", json["max"].to_string())));
+        let mean = json["mean"].as_f64().unwrap();
+        assert!(
+            report.contains(&line("Mean score:", format!("{mean:.1}"))),
+            "{report}"
+        );
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+        for threshold in json["near_perfect"].as_array().unwrap() {
+            let wins = threshold["wins"].as_u64().unwrap() as usize;
+            assert!(report.contains(&format!("≥{wins}/52:")), "{report}");
+            assert_eq!(threshold["decks"], stats.at_least(wins));
+        }
+    }
+
+    #[test]
+    fn near_perfect_thresholds_follow_max_wins() {
+        let histogram = |max_wins: usize| {
+            let mut histogram = vec![0; max_wins + 1];
+            histogram[max_wins / 2] = 3;
+            histogram[max_wins] = 1;
+            histogram
+        };
+        let thresholds = |stats: &DifficultyStats| -> Vec<usize> {
+            stats.near_perfect.iter().map(|t| t.wins).collect()
+        };
+
+        let all_cuts = DifficultyStats::new(histogram(52), 52);
+        assert_eq!(thresholds(&all_cuts), [50, 45, 40]);
+        let real = DifficultyStats::new(histogram(42), 42);
+        assert_eq!(thresholds(&real), [40, 35, 30]);
+        assert!(real.near_perfect.iter().all(|t| t.decks == 1));
+        assert!(!real.to_string().contains("≥50/42"));
+        assert_eq!(thresholds(&DifficultyStats::new(histogram(8), 8)), [6, 1]);
+    }
+
+    #[test]
+    fn parallel_histogram_matches_serial_batches() {
+        let table = ScoreTable::direct();
+        let samples = 30;
+        let config = DealConfig::default();
+        let parallel = difficulty_histogram(2, &table, samples, None, 3, REAL, config, true);
+
+        let (progress, best) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let mut serial = vec![0; max_wins(2, REAL) + 1];
+        for (seed, batch) in difficulty_batches(samples, 3) {
+            let batch = score_batch(2, &table, batch, None, seed, REAL, config, &progress, &best);
+            for (total, count) in serial.iter_mut().zip(batch.histogram) {
+                *total += count;
+            }
+        }
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.iter().sum::<usize>(), samples);
+        assert_eq!(progress.load(Ordering::Relaxed), samples);
+
+        // One thread draws the same decks as a single RNG seeded with 4
+        let mut rng = oorandom::Rand32::new(4);
+        let mut single = vec![0; max_wins(2, REAL) + 1];
+        for _ in 0..10 {
+            single[num_wins(2, &Deck::new_deck_order().shuffle(&mut rng), &table, REAL)] += 1;
+        }
+        assert_eq!(
+            difficulty_histogram(2, &table, 10, None, 1, REAL, config, true),
+            single
+        );
+    }
+
+    #[test]
+    fn quiet_histogram_matches_the_reported_one() {
+        let table = ScoreTable::direct();
+        let config = DealConfig::default();
+        let reported = difficulty_histogram(2, &table, 12, None, 2, REAL, config, false);
+        assert_eq!(
+            difficulty_histogram(2, &table, 12, None, 2, REAL, config, true),
+            reported
+        );
+    }
+
+    #[test]
+    fn many_riffles_win_like_uniform_shuffles() {
+        let table = ScoreTable::direct();
+        let config = DealConfig::default();
+        let mean = |riffles| {
+            let histogram = difficulty_histogram(2, &table, 200, riffles, 4, REAL, config, true);
+            DifficultyStats::new(histogram, max_wins(2, REAL)).mean
+        };
+        let uniform = mean(None);
+        assert!(
+            (mean(Some(10)) - uniform).abs() < 1.5,
+            "{} vs {uniform}",
+            mean(Some(10))
+        );
+        // One riffle leaves new deck order's pairs of each value close together
+        assert!(
+            uniform - mean(Some(1)) > 5.0,
+            "{} vs {uniform}",
+            mean(Some(1))
+        );
+    }
+
+    #[test]
+    fn position_stats_cover_the_sampled_wins() {
+        let table = ScoreTable::direct();
+        let config = DealConfig::default();
+        let counts = difficulty_counts(2, &table, 30, None, 3, REAL, config, true);
+        assert_eq!(
+            counts.histogram,
+            difficulty_histogram(2, &table, 30, None, 3, REAL, config, true)
+        );
+        let stats = PositionStats::new(30, &counts.position_wins, REAL);
+        assert_eq!(stats.samples, 30);
+        let cuts: Vec<usize> = stats.positions.iter().map(|p| p.cut_pos).collect();
+        assert_eq!(cuts, cut_positions(REAL).collect::<Vec<_>>());
+        // Every thread's decks count towards both
+        let total_wins: usize = counts
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(wins, n)| wins * n)
+            .sum();
+        assert_eq!(
+            stats.positions.iter().map(|p| p.wins).sum::<usize>(),
+            total_wins
+        );
+        assert!(stats.to_string().contains("WINS BY CUT POSITION"));
+    }
+
+    #[test]
+    fn player_count_sweep_matches_single_counts() {
+        let table = ScoreTable::direct();
+        let config = DealConfig::default();
+        let sweep = player_count_sweep(4, &table, 12, None, 2, REAL, config, true);
+        assert_eq!(
+            sweep.iter().map(|c| c.num_players).collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+        for counts in &sweep {
+            let num_players = counts.num_players;
+            let histogram =
+                difficulty_histogram(num_players, &table, 12, None, 2, REAL, config, true);
+            let stats = DifficultyStats::new(histogram, max_wins(num_players, REAL));
+            assert_eq!(counts.stats, stats);
+        }
+
+        let json: serde_json::Value = serde_json::to_value(&sweep).unwrap();
+        assert_eq!(json[1]["num_players"], 3);
+        assert_eq!(json[1]["mean"], sweep[1].stats.mean);
+        assert!(sweep[0].to_string().starts_with("   2 players: mean "));
+    }
+
+    #[test]
+    fn difficulty_batches_cover_every_sample() {
+        assert_eq!(
+            difficulty_batches(10, 3),
+            vec![(4, 4), (1004, 3), (2004, 3)]
+        );
+        assert_eq!(difficulty_batches(2, 0), vec![(4, 2)]);
     }
 }
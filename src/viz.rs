@@ -11,17 +11,14 @@
 
 use crate::cards::Card;
 use crate::deck::{generate_adaptive_mutations, AdvancedMutation, Deck};
-use crate::game::{hybrid_score, num_wins, position_margin};
+use crate::game::{cut_positions, hybrid_score, max_wins, num_wins, position_margin};
 use crate::precompute::ScoreTable;
-use crate::search::local_search_sa;
+use crate::search::{SearchConfig, local_search_sa};
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::Path;
 
-/// We score all 52 rotational cut positions (matches `search::REAL == false`).
-const REAL: bool = false;
-const MAX_WINS: usize = 52;
 const NUM_CARDS: usize = 52;
 const NUM_NEIGHBORS: usize = NUM_CARDS * (NUM_CARDS - 1) / 2; // 1326
 /// Delta histogram index: bucket `d + DELTA_OFFSET` holds neighbors whose score
@@ -51,6 +48,7 @@ fn enumerate_neighbors(
     deck: &mut Deck,
     num_players: usize,
     table: &ScoreTable,
+    real: bool,
     current: usize,
 ) -> Neighborhood {
     let mut hist = [0u32; DELTA_BUCKETS];
@@ -59,9 +57,9 @@ fn enumerate_neighbors(
 
     for i in 0..NUM_CARDS {
         for j in (i + 1)..NUM_CARDS {
-            deck.0.swap(i, j);
-            let s = num_wins(num_players, deck, table, REAL);
-            deck.0.swap(i, j); // undo
+            deck.swap(i, j);
+            let s = num_wins(num_players, deck, table, real);
+            deck.swap(i, j); // undo
 
             let d = s as i32 - current as i32;
             hist[(d + DELTA_OFFSET) as usize] += 1;
@@ -99,20 +97,26 @@ fn hamming(a: &[u8], b: &[u8]) -> usize {
 /// the score of `a` and the last element is the score of `b`. Every step is a
 /// legal move in the swap-neighborhood graph, so this is a real cross-section of
 /// the landscape between two decks.
-fn swap_path_scores(a: &[u8], b: &[u8], num_players: usize, table: &ScoreTable) -> Vec<usize> {
-    let mut deck = Deck(a.iter().map(|&x| Card(x)).collect());
-    let mut scores = vec![num_wins(num_players, &deck, table, REAL)];
+fn swap_path_scores(
+    a: &[u8],
+    b: &[u8],
+    num_players: usize,
+    table: &ScoreTable,
+    real: bool,
+) -> Vec<usize> {
+    let mut deck: Deck = a.iter().map(|&x| Card(x)).collect();
+    let mut scores = vec![num_wins(num_players, &deck, table, real)];
     for i in 0..NUM_CARDS {
-        if deck.0[i].0 == b[i] {
+        if deck[i].0 == b[i] {
             continue;
         }
         // Find the card b[i] somewhere ahead and swap it into place.
         let mut j = i + 1;
-        while j < NUM_CARDS && deck.0[j].0 != b[i] {
+        while j < NUM_CARDS && deck[j].0 != b[i] {
             j += 1;
         }
-        deck.0.swap(i, j);
-        scores.push(num_wins(num_players, &deck, table, REAL));
+        deck.swap(i, j);
+        scores.push(num_wins(num_players, &deck, table, real));
     }
     scores
 }
@@ -146,10 +150,11 @@ struct ClimbRecord {
 fn instrumented_climb(
     num_players: usize,
     table: &ScoreTable,
+    real: bool,
     rng: &mut oorandom::Rand32,
 ) -> ClimbRecord {
     let mut deck = Deck::new_deck_order().shuffle(rng);
-    let mut score = num_wins(num_players, &deck, table, REAL);
+    let mut score = num_wins(num_players, &deck, table, real);
 
     let mut trajectory = vec![score];
     let mut steps = Vec::new();
@@ -157,7 +162,7 @@ fn instrumented_climb(
     let last_hist;
 
     loop {
-        let nb = enumerate_neighbors(&mut deck, num_players, table, score);
+        let nb = enumerate_neighbors(&mut deck, num_players, table, real, score);
         let (worse, equal, better) = counts_from_hist(&nb.hist);
         steps.push(StepCounts {
             score,
@@ -172,10 +177,10 @@ fn instrumented_climb(
         match nb.best_swap {
             Some((i, j)) => {
                 // Steepest ascent: take the single best-improving swap.
-                deck.0.swap(i, j);
+                deck.swap(i, j);
                 score = nb.best_score;
                 trajectory.push(score);
-                // Note: we do NOT early-exit at MAX_WINS. The next iteration
+                // Note: we do NOT early-exit at max_wins. The next iteration
                 // enumerates the perfect deck, finds no improving neighbor, and
                 // breaks — giving us a correct peak_hist for that case too.
             }
@@ -193,7 +198,7 @@ fn instrumented_climb(
         steps,
         start_hist: start_hist.expect("at least one step recorded"),
         peak_hist: last_hist,
-        peak_deck: deck.0.iter().map(|c| c.0).collect(),
+        peak_deck: deck.iter().map(|c| c.0).collect(),
         peak_score: score,
     }
 }
@@ -205,6 +210,7 @@ fn instrumented_climb(
 fn run_climbs(
     num_players: usize,
     table: &ScoreTable,
+    real: bool,
     restarts: usize,
     seed: u64,
 ) -> Vec<ClimbRecord> {
@@ -226,7 +232,7 @@ fn run_climbs(
                         ^ ((num_players as u64) << 32)
                         ^ (r as u64);
                     let mut rng = oorandom::Rand32::new(restart_seed);
-                    let rec = instrumented_climb(num_players, table, &mut rng);
+                    let rec = instrumented_climb(num_players, table, real, &mut rng);
                     out.push((r, rec));
                     r += num_threads;
                 }
@@ -395,18 +401,23 @@ fn hamming_cards(a: &[Card], b: &[Card]) -> usize {
 
 /// At a local optimum, compare the swap-neighborhood judged by raw wins vs. by
 /// the margin-refined hybrid score.
-fn margin_gradient(base: &[u8], num_players: usize, table: &ScoreTable) -> MarginGradient {
-    let mut deck = Deck(base.iter().map(|&x| Card(x)).collect());
-    let w0 = num_wins(num_players, &deck, table, REAL);
-    let h0 = hybrid_score(num_players, &deck, table, REAL);
+fn margin_gradient(
+    base: &[u8],
+    num_players: usize,
+    table: &ScoreTable,
+    real: bool,
+) -> MarginGradient {
+    let mut deck: Deck = base.iter().map(|&x| Card(x)).collect();
+    let w0 = num_wins(num_players, &deck, table, real);
+    let h0 = hybrid_score(num_players, &deck, table, real);
     let (mut wu, mut we, mut wd) = (0u32, 0u32, 0u32);
     let (mut hu, mut he, mut hd) = (0u32, 0u32, 0u32);
     for i in 0..NUM_CARDS {
         for j in (i + 1)..NUM_CARDS {
-            deck.0.swap(i, j);
-            let w = num_wins(num_players, &deck, table, REAL);
-            let h = hybrid_score(num_players, &deck, table, REAL);
-            deck.0.swap(i, j);
+            deck.swap(i, j);
+            let w = num_wins(num_players, &deck, table, real);
+            let h = hybrid_score(num_players, &deck, table, real);
+            deck.swap(i, j);
             match w.cmp(&w0) {
                 std::cmp::Ordering::Greater => wu += 1,
                 std::cmp::Ordering::Equal => we += 1,
@@ -438,7 +449,7 @@ fn beam_record(beam: &[(Deck, usize, f64)], iter: usize) -> BeamIter {
     let mut cnt = 0u64;
     for i in 0..beam.len() {
         for j in (i + 1)..beam.len() {
-            sum += hamming_cards(&beam[i].0 .0, &beam[j].0 .0) as u64;
+            sum += hamming_cards(&beam[i].0, &beam[j].0) as u64;
             cnt += 1;
         }
     }
@@ -456,27 +467,33 @@ fn beam_record(beam: &[(Deck, usize, f64)], iter: usize) -> BeamIter {
 /// round refined by simulated-annealing local search and selected by the
 /// margin-refined hybrid score with a diversity guard. Records per-iteration
 /// stats and stops when a perfect deck appears (or the iteration cap is hit).
-fn instrumented_beam(num_players: usize, table: &ScoreTable, seed: u64) -> BeamRun {
+fn instrumented_beam(num_players: usize, table: &ScoreTable, real: bool, seed: u64) -> BeamRun {
     const W: usize = 40; // beam width
     const M: usize = 8; // mutations spawned per beam member
     const SA: usize = 450; // SA local-search budget per candidate
     const MAX_IT: usize = 160;
     const ELITE: usize = 4;
     const DIV_MIN: usize = 4; // treat neighbors within this Hamming as duplicates
+    // temp 5.0, cooling 0.998
+    let sa_config = &SearchConfig {
+        real,
+        ..SearchConfig::default()
+    };
+    let max_wins = max_wins(num_players, real);
 
     let mut rng = oorandom::Rand32::new(seed);
     let mut beam: Vec<(Deck, usize, f64)> = (0..W)
         .map(|_| {
             let d = Deck::new_deck_order().shuffle(&mut rng);
-            let w = num_wins(num_players, &d, table, REAL);
-            let h = hybrid_score(num_players, &d, table, REAL);
+            let w = num_wins(num_players, &d, table, real);
+            let h = hybrid_score(num_players, &d, table, real);
             (d, w, h)
         })
         .collect();
     beam.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
 
     let mut iters = vec![beam_record(&beam, 0)];
-    let mut reached = beam[0].1 == MAX_WINS;
+    let mut reached = beam[0].1 == max_wins;
 
     for it in 1..=MAX_IT {
         if reached {
@@ -488,13 +505,13 @@ fn instrumented_beam(num_players: usize, table: &ScoreTable, seed: u64) -> BeamR
                 .iter()
                 .enumerate()
                 .map(|(bi, (bd, _, _))| {
-                    let bd = bd.clone();
+                    let bd = *bd;
                     let sd = seed ^ ((it as u64) << 20) ^ (bi as u64).wrapping_mul(0x9E37_79B1);
                     scope.spawn(move || {
                         let mut r = oorandom::Rand32::new(sd);
                         let mut out = Vec::with_capacity(M);
                         for _ in 0..M {
-                            let mut child = bd.clone();
+                            let mut child = bd;
                             let k = r.rand_range(1..3);
                             for _ in 0..k {
                                 let mu = generate_adaptive_mutations(&mut r, 0.15)
@@ -504,8 +521,8 @@ fn instrumented_beam(num_players: usize, table: &ScoreTable, seed: u64) -> BeamR
                                 child = mu.apply(child, &mut r);
                             }
                             let (opt, w) =
-                                local_search_sa(child, num_players, table, SA, 5.0, 0.998, &mut r);
-                            let h = hybrid_score(num_players, &opt, table, REAL);
+                                local_search_sa(child, num_players, table, SA, sa_config, &mut r);
+                            let h = hybrid_score(num_players, &opt, table, real);
                             out.push((opt, w, h));
                         }
                         out
@@ -522,16 +539,14 @@ fn instrumented_beam(num_players: usize, table: &ScoreTable, seed: u64) -> BeamR
         // score while keeping members mutually diverse.
         let mut pool = candidates;
         for i in 0..ELITE.min(beam.len()) {
-            pool.push(beam[i].clone());
+            pool.push(beam[i]);
         }
         pool.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
 
         let mut nb: Vec<(Deck, usize, f64)> = Vec::with_capacity(W);
         let mut leftover: Vec<(Deck, usize, f64)> = Vec::new();
         for c in pool {
-            let diverse = nb
-                .iter()
-                .all(|(d, _, _)| hamming_cards(&d.0, &c.0 .0) > DIV_MIN);
+            let diverse = nb.iter().all(|(d, _, _)| hamming_cards(d, &c.0) > DIV_MIN);
             if nb.len() < W && diverse {
                 nb.push(c);
             } else {
@@ -547,7 +562,7 @@ fn instrumented_beam(num_players: usize, table: &ScoreTable, seed: u64) -> BeamR
         beam = nb;
         beam.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
 
-        reached = beam[0].1 == MAX_WINS;
+        reached = beam[0].1 == max_wins;
         iters.push(beam_record(&beam, it));
     }
 
@@ -602,6 +617,7 @@ fn operator_stats(
     bases: &[Vec<u8>],
     num_players: usize,
     table: &ScoreTable,
+    real: bool,
     rng: &mut oorandom::Rand32,
     samples_per_base: usize,
 ) -> Vec<OpStat> {
@@ -613,12 +629,12 @@ fn operator_stats(
         let mut all_deltas: Vec<i32> = Vec::new();
 
         for base in bases {
-            let deck0 = Deck(base.iter().map(|&x| Card(x)).collect());
-            let s0 = num_wins(num_players, &deck0, table, REAL) as i32;
+            let deck0: Deck = base.iter().map(|&x| Card(x)).collect();
+            let s0 = num_wins(num_players, &deck0, table, real) as i32;
             for _ in 0..samples_per_base {
                 let op = gen_operator(kind, rng);
-                let mutated = op.apply(deck0.clone(), rng);
-                let d = num_wins(num_players, &mutated, table, REAL) as i32 - s0;
+                let mutated = op.apply(deck0, rng);
+                let d = num_wins(num_players, &mutated, table, real) as i32 - s0;
                 hist[(d.clamp(-52, 52) + DELTA_OFFSET) as usize] += 1;
                 match d.cmp(&0) {
                     std::cmp::Ordering::Greater => improve += 1,
@@ -707,12 +723,12 @@ fn profile_from_hist(hist: &[u64; DELTA_BUCKETS], restarts: usize) -> Profile {
 }
 
 /// Reconstruct a [`Deck`] from raw card bytes and compute per-cut margins.
-fn deck_view(cards: &[u8], num_players: usize, table: &ScoreTable) -> DeckView {
-    let deck = Deck(cards.iter().map(|&b| Card(b)).collect());
+fn deck_view(cards: &[u8], num_players: usize, table: &ScoreTable, real: bool) -> DeckView {
+    let deck: Deck = cards.iter().map(|&b| Card(b)).collect();
     let margins: Vec<i32> = (0..NUM_CARDS)
         .map(|pos| position_margin(num_players, &deck, pos, table))
         .collect();
-    let score = num_wins(num_players, &deck, table, REAL);
+    let score = num_wins(num_players, &deck, table, real);
     DeckView {
         deck: cards.to_vec(),
         margins,
@@ -723,6 +739,7 @@ fn deck_view(cards: &[u8], num_players: usize, table: &ScoreTable) -> DeckView {
 fn build_player_data(
     num_players: usize,
     table: &ScoreTable,
+    real: bool,
     restarts: usize,
     seed: u64,
 ) -> PlayerData {
@@ -730,8 +747,9 @@ fn build_player_data(
         "  ⛰️  n={}: running {} instrumented climbs...",
         num_players, restarts
     );
-    let records = run_climbs(num_players, table, restarts, seed);
+    let records = run_climbs(num_players, table, real, restarts, seed);
 
+    let max_wins = max_wins(num_players, real);
     let mut trajectories = Vec::with_capacity(records.len());
     let mut peaks = Vec::with_capacity(records.len());
     let mut reached_optimum_count = 0;
@@ -751,7 +769,7 @@ fn build_player_data(
         trajectories.push(rec.trajectory.clone());
         peaks.push(rec.peak_score);
         all_peaks.push(rec.peak_deck.clone());
-        if rec.peak_score == MAX_WINS {
+        if rec.peak_score == max_wins {
             reached_optimum_count += 1;
         }
 
@@ -796,8 +814,8 @@ fn build_player_data(
 
     let example_optimum = example_optimum.unwrap_or_else(|| best_deck.clone());
     let inspector = Inspector {
-        optimum: deck_view(&example_optimum, num_players, table),
-        best_found: deck_view(&best_deck, num_players, table),
+        optimum: deck_view(&example_optimum, num_players, table, real),
+        best_found: deck_view(&best_deck, num_players, table, real),
     };
 
     // ---- topology of the local optima ----
@@ -830,15 +848,15 @@ fn build_player_data(
     if r >= 1 {
         ridge_scores.push(num_wins(
             num_players,
-            &Deck(distinct[0].iter().map(|&x| Card(x)).collect()),
+            &distinct[0].iter().map(|&x| Card(x)).collect::<Deck>(),
             table,
-            REAL,
+            real,
         ));
         ridge_peaks.push(0);
         for k in 1..r {
             // swap_path_scores includes both endpoints; skip the first (it equals
             // the previous peak we already recorded) to keep the walk continuous.
-            let seg = swap_path_scores(&distinct[k - 1], &distinct[k], num_players, table);
+            let seg = swap_path_scores(&distinct[k - 1], &distinct[k], num_players, table, real);
             ridge_scores.extend(seg.into_iter().skip(1));
             ridge_peaks.push(ridge_scores.len() - 1);
         }
@@ -855,27 +873,21 @@ fn build_player_data(
     );
     const N_BASES: usize = 40;
     let random_bases: Vec<Vec<u8>> = (0..N_BASES)
-        .map(|_| {
-            Deck::new_deck_order()
-                .shuffle(&mut op_rng)
-                .0
-                .iter()
-                .map(|c| c.0)
-                .collect()
-        })
+        .map(|_| Deck::new_deck_order().shuffle(&mut op_rng).as_card_ids())
         .collect();
     let opt_bases: Vec<Vec<u8>> = (0..N_BASES)
         .map(|k| distinct[k % distinct.len()].clone())
         .collect();
     let operators = Operators {
-        random: operator_stats(&random_bases, num_players, table, &mut op_rng, 25),
-        optimum: operator_stats(&opt_bases, num_players, table, &mut op_rng, 25),
+        random: operator_stats(&random_bases, num_players, table, real, &mut op_rng, 25),
+        optimum: operator_stats(&opt_bases, num_players, table, real, &mut op_rng, 25),
     };
 
     // Why beam search escapes: the margin gradient at a local optimum, and an
     // instrumented beam-search run that breaks through the ceiling.
-    let margin_gradient = margin_gradient(&distinct[0], num_players, table);
-    let beam = instrumented_beam(num_players, table, seed.wrapping_add(97 + num_players as u64));
+    let margin_gradient = margin_gradient(&distinct[0], num_players, table, real);
+    let beam_seed = seed.wrapping_add(97 + num_players as u64);
+    let beam = instrumented_beam(num_players, table, real, beam_seed);
 
     // A representative climb (median trajectory length) to draw as a tree.
     let mut order: Vec<usize> = (0..records.len()).collect();
@@ -890,9 +902,16 @@ fn build_player_data(
 
     let best_peak = peaks.iter().copied().max().unwrap_or(0);
     eprintln!(
-        "  ✓ n={}: best peak {}/{}, reached 52 in {}/{} restarts · {} distinct optima, ~{:.0}/{} apart",
-        num_players, best_peak, MAX_WINS, reached_optimum_count, restarts,
-        distinct.len(), mean_pairwise_distance, NUM_CARDS
+        "  ✓ n={}: best peak {}/{}, reached {} in {}/{} restarts · {} distinct optima, ~{:.0}/{} apart",
+        num_players,
+        best_peak,
+        max_wins,
+        max_wins,
+        reached_optimum_count,
+        restarts,
+        distinct.len(),
+        mean_pairwise_distance,
+        NUM_CARDS
     );
 
     PlayerData {
@@ -921,8 +940,10 @@ fn build_player_data(
 // ---------------------------------------------------------------------------
 
 /// Run the landscape experiments and write a self-contained HTML file.
+/// Only the cut positions `real` selects are scored
 pub fn export(
     table: &ScoreTable,
+    real: bool,
     players: &[usize],
     restarts: usize,
     seed: u64,
@@ -939,12 +960,12 @@ pub fn export(
 
     let player_data: Vec<PlayerData> = players
         .iter()
-        .map(|&n| build_player_data(n, table, restarts, seed))
+        .map(|&n| build_player_data(n, table, real, restarts, seed))
         .collect();
 
     let landscape = Landscape {
         meta: Meta {
-            max_wins: MAX_WINS,
+            max_wins: cut_positions(real).len(),
             num_cards: NUM_CARDS,
             num_neighbors: NUM_NEIGHBORS,
             restarts,